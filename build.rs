@@ -41,6 +41,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     fs::write(output_path, format!("{db}"))?;
 
+    // JPEG 2000 code streams are decoded with OpenJPEG through `openjpeg-sys` on
+    // native targets. On wasm32, where it does not build, they are decoded with
+    // the pure-Rust `hayro-jpeg2000` only when the `jpeg2000-wasm` feature is
+    // enabled.
+    println!("cargo:rustc-check-cfg=cfg(jpeg2000)");
+    let is_wasm32 = env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "wasm32");
+    if env::var_os("CARGO_FEATURE_STD").is_some()
+        && (!is_wasm32 || env::var_os("CARGO_FEATURE_JPEG2000_WASM").is_some())
+    {
+        println!("cargo:rustc-cfg=jpeg2000");
+    }
+
     println!("cargo:rerun-if-changed=build.rs");
     Ok(())
 }
@@ -87,6 +87,55 @@ impl std::str::FromStr for CliMessageIndex {
     }
 }
 
+/// Level specified as a value with a unit, such as `500hPa` and `2m`.
+///
+/// The value is held in the unit used in Code Table 4.5.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CliLevel {
+    pub(crate) value: f64,
+    pub(crate) unit: &'static str,
+}
+
+impl CliLevel {
+    pub(crate) fn matches(&self, surface: &grib::FixedSurface) -> bool {
+        surface.unit() == Some(self.unit) && (surface.value() - self.value).abs() < 1e-6
+    }
+}
+
+impl std::str::FromStr for CliLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(
+                r"(?x)                      # insignificant whitespace mode
+                ^
+                ([0-9]+(?:\.[0-9]+)?)      # value
+                (hPa|Pa|m|K)                # unit
+                $",
+            )
+            .unwrap()
+        });
+        let cap = RE.captures(s).ok_or_else(|| {
+            anyhow::anyhow!(
+                "level must be specified as a number followed by a unit (hPa, Pa, m or K)"
+            )
+        })?;
+        let value = f64::from_str(cap.get(1).unwrap().as_str()).unwrap();
+        let level = match cap.get(2).unwrap().as_str() {
+            "hPa" => Self {
+                value: value * 100.,
+                unit: "Pa",
+            },
+            "Pa" => Self { value, unit: "Pa" },
+            "m" => Self { value, unit: "m" },
+            "K" => Self { value, unit: "K" },
+            _ => unreachable!(),
+        };
+        Ok(level)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,4 +165,29 @@ mod tests {
         (message_index_parsing_failure_due_to_garbase_before_index, "_1.1"),
         (message_index_parsing_failure_due_to_garbase_after_index, "1.1_"),
     }
+
+    macro_rules! test_level_parsing {
+        ($(($name:ident, $input:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let result = $input.parse::<CliLevel>().ok();
+                assert_eq!(result, $expected);
+            }
+        )*);
+    }
+
+    test_level_parsing! {
+        (
+            level_parsing_in_hpa,
+            "500hPa",
+            Some(CliLevel { value: 50000., unit: "Pa" })
+        ),
+        (
+            level_parsing_in_m_with_decimal_point,
+            "2.5m",
+            Some(CliLevel { value: 2.5, unit: "m" })
+        ),
+        (level_parsing_failure_due_to_missing_unit, "500", None),
+        (level_parsing_failure_due_to_unknown_unit, "500mb", None),
+    }
 }
@@ -7,6 +7,8 @@ pub fn cli() -> Vec<Command> {
         info::cli(),
         inspect::cli(),
         list::cli(),
+        merge::cli(),
+        split::cli(),
     ]
 }
 
@@ -17,6 +19,8 @@ pub fn dispatch(matches: ArgMatches) -> anyhow::Result<()> {
         Some(("info", args)) => info::exec(args),
         Some(("inspect", args)) => inspect::exec(args),
         Some(("list", args)) => list::exec(args),
+        Some(("merge", args)) => merge::exec(args),
+        Some(("split", args)) => split::exec(args),
         _ => unreachable!(),
     }
 }
@@ -26,3 +30,5 @@ pub mod decode;
 pub mod info;
 pub mod inspect;
 pub mod list;
+pub mod merge;
+pub mod split;
@@ -13,10 +13,22 @@ use grib::GribError;
 use crate::cli;
 
 pub fn cli() -> Command {
-    Command::new("decode")
+    let cmd = Command::new("decode")
         .about("Export decoded data with latitudes and longitudes")
         .arg(arg!(<FILE> "Target file").value_parser(clap::value_parser!(PathBuf)))
-        .arg(arg!(<INDEX> "Submessage index"))
+        .arg(
+            arg!([INDEX] "Submessage index")
+                .required_unless_present_any(["param", "level"])
+                .conflicts_with_all(["param", "level"]),
+        )
+        .arg(
+            arg!(--param <NAME> "Select the submessage by parameter abbreviation (e.g. PRMSL) or description")
+                .required(false),
+        )
+        .arg(
+            arg!(--level <LEVEL> "Select the submessage by first fixed surface (e.g. 500hPa)")
+                .required(false),
+        )
         .arg(
             arg!(-b --"big-endian" <OUT_FILE> "Export (without lat/lon) as a big-endian flat binary file")
                 .required(false) // There is no syntax yet for optional options.
@@ -28,6 +40,85 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(PathBuf))
                 .conflicts_with("big-endian"),
         )
+        .arg(
+            arg!(--scale <FACTOR> "Multiply decoded values by the factor (e.g. 0.01 for Pa to hPa)")
+                .required(false)
+                .value_parser(clap::value_parser!(f32)),
+        )
+        .arg(
+            arg!(--offset <VALUE> "Add the value to decoded values after scaling (e.g. -273.15 for K to °C)")
+                .required(false)
+                .allow_negative_numbers(true)
+                .value_parser(clap::value_parser!(f32)),
+        );
+    #[cfg(feature = "netcdf")]
+    let cmd = cmd
+        .arg(
+            arg!(--format <FORMAT> "Export with lat/lon in the format (regular lat/lon grids only)")
+                .required(false)
+                .value_parser(["netcdf"])
+                .requires("out")
+                .conflicts_with_all(["big-endian", "little-endian"]),
+        )
+        .arg(
+            arg!(--out <OUT_FILE> "Output file for --format")
+                .required(false)
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("format"),
+        );
+    cmd
+}
+
+// Finds the only submessage with the specified parameter and level.
+fn select<R: grib::Grib2Read>(
+    grib: &grib::Grib2<R>,
+    param: Option<&str>,
+    level: Option<&cli::CliLevel>,
+) -> Result<grib::MessageIndex> {
+    let indices = grib
+        .iter_filtered(|submessage| {
+            let param_matches = param.map_or(true, |name| {
+                submessage
+                    .parameter()
+                    .is_some_and(|p| parameter_has_name(&p, name))
+            });
+            let level_matches = level.map_or(true, |level| {
+                submessage
+                    .prod_def()
+                    .fixed_surfaces()
+                    .is_some_and(|(first, _)| level.matches(&first))
+            });
+            param_matches && level_matches
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    match indices[..] {
+        [index] => Ok(index),
+        [] => anyhow::bail!("no submessage matches the specified parameter and level"),
+        _ => {
+            let indices = indices
+                .iter()
+                .map(|(m, s)| format!("{m}.{s}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::bail!("multiple submessages match the specified parameter and level: {indices}")
+        }
+    }
+}
+
+fn parameter_has_name(param: &grib::Parameter, name: &str) -> bool {
+    let abbrev_matches = grib::codetables::NCEP::try_from(param)
+        .is_ok_and(|abbrev| format!("{abbrev:?}").eq_ignore_ascii_case(name));
+    abbrev_matches
+        || param
+            .description()
+            .is_some_and(|desc| desc.eq_ignore_ascii_case(name))
+}
+
+// Applies `value * scale + offset` to the value. NaN values remain NaN.
+fn convert(value: f32, scale: Option<f32>, offset: Option<f32>) -> f32 {
+    let value = scale.map_or(value, |scale| value * scale);
+    offset.map_or(value, |offset| value + offset)
 }
 
 fn write_output(
@@ -45,15 +136,44 @@ fn write_output(
 pub fn exec(args: &ArgMatches) -> Result<()> {
     let file_name = args.get_one::<PathBuf>("FILE").unwrap();
     let grib = cli::grib(file_name)?;
-    let index = args.get_one::<String>("INDEX").unwrap();
-    let cli::CliMessageIndex(message_index) = index.parse()?;
+    let message_index = match args.get_one::<String>("INDEX") {
+        Some(index) => {
+            let cli::CliMessageIndex(message_index) = index.parse()?;
+            message_index
+        }
+        None => {
+            let param = args.get_one::<String>("param");
+            let level = args
+                .get_one::<String>("level")
+                .map(|level| level.parse::<cli::CliLevel>())
+                .transpose()?;
+            select(&grib, param.map(String::as_str), level.as_ref())?
+        }
+    };
     let (_, submessage) = grib
         .iter()
         .find(|(index, _)| *index == message_index)
         .ok_or_else(|| anyhow::anyhow!("no such index: {}.{}", message_index.0, message_index.1))?;
     let latlons = submessage.latlons();
+    #[cfg(feature = "netcdf")]
+    let netcdf_field = if args.contains_id("format") {
+        Some(netcdf_field(&submessage)?)
+    } else {
+        None
+    };
     let decoder = grib::Grib2SubmessageDecoder::from(submessage)?;
-    let values = decoder.dispatch()?;
+    let scale = args.get_one::<f32>("scale").copied();
+    let offset = args.get_one::<f32>("offset").copied();
+    let values = decoder
+        .dispatch()?
+        .map(|value| convert(value, scale, offset));
+
+    #[cfg(feature = "netcdf")]
+    if let Some(mut field) = netcdf_field {
+        let out_path = args.get_one::<PathBuf>("out").unwrap();
+        field.values = values.collect();
+        return netcdf::write(out_path, &field);
+    }
 
     if args.contains_id("big-endian") {
         let out_path = args.get_one::<PathBuf>("big-endian").unwrap();
@@ -65,7 +185,7 @@ pub fn exec(args: &ArgMatches) -> Result<()> {
         let values = values.collect::<Vec<_>>().into_iter(); // workaround for mutability
         let latlons = match latlons {
             Ok(iter) => LatLonIteratorWrapper::LatLon(iter),
-            Err(GribError::NotSupported(_)) => {
+            Err(GribError::NotSupported(_) | GribError::UnsupportedTemplate { .. }) => {
                 let nan_iter = vec![(f32::NAN, f32::NAN); values.len()].into_iter();
                 LatLonIteratorWrapper::NaN(nan_iter)
             }
@@ -77,6 +197,40 @@ pub fn exec(args: &ArgMatches) -> Result<()> {
     }
 }
 
+// Checks that the grid can be exported and returns a field with coordinates and
+// attributes but without values.
+#[cfg(feature = "netcdf")]
+fn netcdf_field<R>(submessage: &grib::SubMessage<R>) -> Result<netcdf::RegularLatLonField> {
+    let def = submessage.grid_definition_values().map_err(|e| {
+        anyhow::anyhow!("NetCDF output is supported only for regular lat/lon grids: {e}")
+    })?;
+    let (ni, nj) = match def {
+        grib::GridDefinitionTemplateValues::Template0(ref def)
+            if def.points_per_row.is_none()
+                && def.scanning_mode.is_consecutive_for_i()
+                && !def.scanning_mode.scans_alternating_rows() =>
+        {
+            def.grid_shape()
+        }
+        _ => anyhow::bail!(
+            "NetCDF output is supported only for regular lat/lon grids scanned row by row, not for {}",
+            def.short_name()
+        ),
+    };
+    let latlons = submessage.latlons()?.collect::<Vec<_>>();
+    let parameter = submessage.parameter();
+    Ok(netcdf::RegularLatLonField {
+        lats: (0..nj).map(|j| latlons[j * ni].0).collect(),
+        lons: (0..ni).map(|i| latlons[i].1).collect(),
+        values: Vec::new(),
+        long_name: parameter.as_ref().and_then(|p| p.description()),
+        units: parameter.as_ref().and_then(|p| p.unit()),
+        grib_parameter: parameter
+            .map(|p| format!("{}.{}.{}", p.discipline, p.category, p.num))
+            .unwrap_or_default(),
+    })
+}
+
 #[derive(Clone)]
 enum LatLonIteratorWrapper<L, N> {
     LatLon(L),
@@ -136,3 +290,6 @@ where
         Ok(())
     }
 }
+
+#[cfg(feature = "netcdf")]
+mod netcdf;
@@ -0,0 +1,73 @@
+//! Export of a single 2D field on a regular lat/lon grid as a CF-compliant
+//! NetCDF classic format file.
+
+use std::path::Path;
+
+use anyhow::Result;
+use netcdf3::{DataSet, FileWriter, InvalidDataSet, Version, NC_FILL_F32};
+
+pub(crate) struct RegularLatLonField {
+    pub(crate) lats: Vec<f32>,
+    pub(crate) lons: Vec<f32>,
+    pub(crate) values: Vec<f32>,
+    pub(crate) long_name: Option<String>,
+    pub(crate) units: Option<&'static str>,
+    pub(crate) grib_parameter: String,
+}
+
+const VALUE_VAR_NAME: &str = "values";
+
+pub(crate) fn write(out_path: &Path, field: &RegularLatLonField) -> Result<()> {
+    // Neither `netcdf3::InvalidDataSet`, which is not `Send`, nor
+    // `netcdf3::WriteError`, which does not implement `std::error::Error`, can
+    // be converted into `anyhow::Error`.
+    let data_set = definition(field)
+        .map_err(|e| anyhow::anyhow!("failed to define a NetCDF data set: {e}"))?;
+    let write_error = |e| anyhow::anyhow!("failed to write a NetCDF file: {e:?}");
+    let mut writer = FileWriter::open(out_path).map_err(write_error)?;
+    writer
+        .set_def(&data_set, Version::Classic, 0)
+        .map_err(write_error)?;
+    writer
+        .write_var_f32("lat", &field.lats)
+        .map_err(write_error)?;
+    writer
+        .write_var_f32("lon", &field.lons)
+        .map_err(write_error)?;
+    // Missing values decoded as NaN are stored as the fill value.
+    let values = field
+        .values
+        .iter()
+        .map(|v| if v.is_nan() { NC_FILL_F32 } else { *v })
+        .collect::<Vec<_>>();
+    writer
+        .write_var_f32(VALUE_VAR_NAME, &values)
+        .map_err(write_error)?;
+    writer.close().map_err(write_error)?;
+    Ok(())
+}
+
+fn definition(field: &RegularLatLonField) -> Result<DataSet, InvalidDataSet> {
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("lat", field.lats.len())?;
+    data_set.add_fixed_dim("lon", field.lons.len())?;
+    data_set.add_global_attr_string("Conventions", "CF-1.8")?;
+
+    data_set.add_var_f32("lat", &["lat"])?;
+    data_set.add_var_attr_string("lat", "standard_name", "latitude")?;
+    data_set.add_var_attr_string("lat", "units", "degrees_north")?;
+    data_set.add_var_f32("lon", &["lon"])?;
+    data_set.add_var_attr_string("lon", "standard_name", "longitude")?;
+    data_set.add_var_attr_string("lon", "units", "degrees_east")?;
+
+    data_set.add_var_f32(VALUE_VAR_NAME, &["lat", "lon"])?;
+    if let Some(long_name) = &field.long_name {
+        data_set.add_var_attr_string(VALUE_VAR_NAME, "long_name", long_name)?;
+    }
+    if let Some(units) = field.units {
+        data_set.add_var_attr_string(VALUE_VAR_NAME, "units", units)?;
+    }
+    data_set.add_var_attr_string(VALUE_VAR_NAME, "grib_parameter", &field.grib_parameter)?;
+    data_set.add_var_attr_f32(VALUE_VAR_NAME, "_FillValue", vec![NC_FILL_F32])?;
+    Ok(data_set)
+}
@@ -6,7 +6,10 @@ use std::{
 
 use clap::{arg, ArgAction, ArgMatches, Command};
 use console::Style;
-use grib::{SectionInfo, SubMessageSection, SubmessageIterator, TemplateInfo};
+use grib::{
+    Identification, SectionBody, SectionInfo, SubMessageSection, SubmessageIterator, TemplateInfo,
+};
+use serde_json::{json, Value};
 
 use crate::cli;
 
@@ -25,6 +28,11 @@ pub fn cli() -> Command {
             arg!(-t --templates "Print templates used in the GRIB message")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            arg!(-r --repr "Print packing parameters in Section 5 of submessages")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(arg!(--json "Print the data structure as a JSON document").action(ArgAction::SetTrue))
         .arg(arg!(<FILE> "Target file").value_parser(clap::value_parser!(PathBuf)))
         .after_help(
             "\
@@ -54,6 +62,9 @@ pub fn exec(args: &ArgMatches) -> anyhow::Result<()> {
         let tmpls = grib.list_templates();
         view.add(InspectItem::Templates(InspectTemplatesItem::new(tmpls)));
     }
+    if args.get_flag("repr") {
+        view.add(InspectItem::Repr(InspectReprItem::new(grib.submessages())));
+    }
     if view.items.is_empty() {
         view.add(InspectItem::Sections(InspectSectionsItem::new(
             grib.sections(),
@@ -65,7 +76,11 @@ pub fn exec(args: &ArgMatches) -> anyhow::Result<()> {
         view.add(InspectItem::Templates(InspectTemplatesItem::new(tmpls)));
     }
 
-    cli::display_in_pager(view);
+    if args.get_flag("json") {
+        println!("{:#}", view.to_json());
+    } else {
+        cli::display_in_pager(view);
+    }
     Ok(())
 }
 
@@ -85,6 +100,23 @@ impl<'i, R> InspectView<'i, R> {
     fn with_headers(&self) -> bool {
         self.items.len() >= 2
     }
+
+    fn to_json(&self) -> Value {
+        let items = self
+            .items
+            .iter()
+            .map(|item| {
+                let value = match item {
+                    InspectItem::Sections(item) => item.to_json(),
+                    InspectItem::SubMessages(item) => item.to_json(),
+                    InspectItem::Templates(item) => item.to_json(),
+                    InspectItem::Repr(item) => item.to_json(),
+                };
+                (item.key().to_owned(), value)
+            })
+            .collect();
+        Value::Object(items)
+    }
 }
 
 impl<R> cli::PredictableNumLines for InspectView<'_, R> {
@@ -121,6 +153,7 @@ impl<R> Display for InspectView<'_, R> {
                 InspectItem::Sections(item) => write!(f, "{item}")?,
                 InspectItem::SubMessages(item) => write!(f, "{item}")?,
                 InspectItem::Templates(item) => write!(f, "{item}")?,
+                InspectItem::Repr(item) => write!(f, "{item}")?,
             }
 
             if items.peek().is_some() {
@@ -136,6 +169,7 @@ enum InspectItem<'i, R> {
     Sections(InspectSectionsItem<'i>),
     SubMessages(InspectSubMessagesItem<'i, R>),
     Templates(InspectTemplatesItem),
+    Repr(InspectReprItem<'i, R>),
 }
 
 impl<R> InspectItem<'_, R> {
@@ -144,6 +178,16 @@ impl<R> InspectItem<'_, R> {
             InspectItem::Sections(_) => "Sections",
             InspectItem::SubMessages(_) => "SubMessages",
             InspectItem::Templates(_) => "Templates",
+            InspectItem::Repr(_) => "Packing parameters",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            InspectItem::Sections(_) => "sections",
+            InspectItem::SubMessages(_) => "submessages",
+            InspectItem::Templates(_) => "templates",
+            InspectItem::Repr(_) => "repr",
         }
     }
 
@@ -152,6 +196,7 @@ impl<R> InspectItem<'_, R> {
             InspectItem::Sections(item) => item.len(),
             InspectItem::SubMessages(item) => item.len(),
             InspectItem::Templates(item) => item.len(),
+            InspectItem::Repr(item) => item.len(),
         }
     }
 }
@@ -168,6 +213,21 @@ impl<'i> InspectSectionsItem<'i> {
     fn len(&self) -> usize {
         self.data.len()
     }
+
+    fn to_json(&self) -> Value {
+        self.data
+            .clone()
+            .enumerate()
+            .map(|(i, sect)| {
+                json!({
+                    "index": i,
+                    "offset": sect.offset,
+                    "end": sect.offset + sect.size,
+                    "section": sect.num,
+                })
+            })
+            .collect()
+    }
 }
 
 impl Display for InspectSectionsItem<'_> {
@@ -199,6 +259,37 @@ impl<'i, R> InspectSubMessagesItem<'i, R> {
         let (size, _) = self.data.size_hint();
         size
     }
+
+    fn to_json(&self) -> Value {
+        fn template(section: &SubMessageSection) -> Value {
+            section
+                .template_code()
+                .map_or(Value::Null, |info| info.to_string().into())
+        }
+
+        (&self.data)
+            .into_iter()
+            .map(|(i, submessage)| {
+                let sect1 = identification(&submessage.1);
+                json!({
+                    "id": format!("{}.{}", i.0, i.1),
+                    "s1": submessage.1.index,
+                    "s2": submessage.2.as_ref().map(|s| s.index),
+                    "s3": submessage.3.index,
+                    "s4": submessage.4.index,
+                    "s5": submessage.5.index,
+                    "s6": submessage.6.index,
+                    "s7": submessage.7.index,
+                    "tmpl3": template(&submessage.3),
+                    "tmpl4": template(&submessage.4),
+                    "tmpl5": template(&submessage.5),
+                    "centre": sect1.map(|s| s.centre_id()),
+                    "master_table_version": sect1.map(|s| s.master_table_version()),
+                    "local_table_version": sect1.map(|s| s.local_table_version()),
+                })
+            })
+            .collect()
+    }
 }
 
 impl<R> Display for InspectSubMessagesItem<'_, R> {
@@ -224,18 +315,29 @@ impl<R> Display for InspectSubMessagesItem<'_, R> {
         }
 
         let header = format!(
-            "{:>8} │ {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} │ {:<7} {:<7} {:<7}",
-            "id", "S2", "S3", "S4", "S5", "S6", "S7", "Tmpl3", "Tmpl4", "Tmpl5",
+            "{:>8} │ {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} │ {:<7} {:<7} {:<7} │ {:>6} {:>6} {:>5}",
+            "id", "S1", "S2", "S3", "S4", "S5", "S6", "S7", "Tmpl3", "Tmpl4", "Tmpl5", "Centre", "Master", "Local",
         );
         let style = Style::new().bold();
         writeln!(f, "{}", style.apply_to(header.trim_end()))?;
 
         for (i, submessage) in &self.data {
             let id = format!("{}.{}", i.0, i.1);
+            let (centre, master, local) = identification(&submessage.1).map_or_else(
+                || ("-".to_string(), "-".to_string(), "-".to_string()),
+                |s| {
+                    (
+                        s.centre_id().to_string(),
+                        s.master_table_version().to_string(),
+                        s.local_table_version().to_string(),
+                    )
+                },
+            );
             writeln!(
                 f,
-                "{:>8} │ {} {} {} {} {} {} │ {} {} {}",
+                "{:>8} │ {} {} {} {} {} {} {} │ {} {} {} │ {:>6} {:>6} {:>5}",
                 id,
+                format_section_index(&submessage.1),
                 format_section_index_optional(&submessage.2),
                 format_section_index(&submessage.3),
                 format_section_index(&submessage.4),
@@ -245,6 +347,83 @@ impl<R> Display for InspectSubMessagesItem<'_, R> {
                 format_template(submessage.3.template_code()),
                 format_template(submessage.4.template_code()),
                 format_template(submessage.5.template_code()),
+                centre,
+                master,
+                local,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn identification<'a>(section: &SubMessageSection<'a>) -> Option<&'a Identification> {
+    match &section.body.body {
+        Some(SectionBody::Section1(body)) => Some(body),
+        _ => None,
+    }
+}
+
+struct InspectReprItem<'i, R> {
+    data: SubmessageIterator<'i, R>,
+}
+
+impl<'i, R> InspectReprItem<'i, R> {
+    fn new(data: SubmessageIterator<'i, R>) -> Self {
+        Self { data }
+    }
+
+    fn len(&self) -> usize {
+        let (size, _) = self.data.size_hint();
+        size + 1 // header
+    }
+
+    fn to_json(&self) -> Value {
+        (&self.data)
+            .into_iter()
+            .map(|(i, submessage)| {
+                let repr_def = submessage.repr_def();
+                json!({
+                    "id": format!("{}.{}", i.0, i.1),
+                    "tmpl5": format!("5.{}", repr_def.repr_tmpl_num()),
+                    "nbits": repr_def.num_bits(),
+                    "reference_value": repr_def.reference_value(),
+                    "binary_scale_factor": repr_def.binary_scale_factor(),
+                    "decimal_scale_factor": repr_def.decimal_scale_factor(),
+                    "num_groups": repr_def.num_groups(),
+                    "spatial_differencing_order": repr_def.spatial_differencing_order(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl<R> Display for InspectReprItem<'_, R> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fn format_optional<T: ToString>(value: Option<T>) -> String {
+            value.map_or_else(|| "-".to_string(), |v| v.to_string())
+        }
+
+        let header = format!(
+            "{:>8} │ {:<7} │ {:>5} {:>14} {:>5} {:>5} │ {:>8} {:>6}",
+            "id", "Tmpl5", "Nbits", "R", "E", "D", "NG", "SpDiff",
+        );
+        let style = Style::new().bold();
+        writeln!(f, "{}", style.apply_to(header.trim_end()))?;
+
+        for (i, submessage) in &self.data {
+            let id = format!("{}.{}", i.0, i.1);
+            let repr_def = submessage.repr_def();
+            writeln!(
+                f,
+                "{:>8} │ {:<7} │ {:>5} {:>14} {:>5} {:>5} │ {:>8} {:>6}",
+                id,
+                format!("5.{}", repr_def.repr_tmpl_num()),
+                format_optional(repr_def.num_bits()),
+                format_optional(repr_def.reference_value()),
+                format_optional(repr_def.binary_scale_factor()),
+                format_optional(repr_def.decimal_scale_factor()),
+                format_optional(repr_def.num_groups()),
+                format_optional(repr_def.spatial_differencing_order()),
             )?;
         }
         Ok(())
@@ -263,6 +442,18 @@ impl InspectTemplatesItem {
     fn len(&self) -> usize {
         self.data.len()
     }
+
+    fn to_json(&self) -> Value {
+        self.data
+            .iter()
+            .map(|tmpl| {
+                json!({
+                    "template": tmpl.to_string(),
+                    "description": tmpl.describe(),
+                })
+            })
+            .collect()
+    }
 }
 
 impl Display for InspectTemplatesItem {
@@ -17,6 +17,11 @@ pub fn cli() -> Command {
     Command::new("list")
         .about("List layers contained in the data")
         .arg(arg!(-d --dump "Show details of each data").action(ArgAction::SetTrue))
+        .arg(
+            arg!(-c --count "Show only the numbers of submessages and messages")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("dump"),
+        )
         .arg(arg!(<FILE> "Target file").value_parser(clap::value_parser!(PathBuf)))
 }
 
@@ -24,6 +29,13 @@ pub fn exec(args: &ArgMatches) -> anyhow::Result<()> {
     let file_name = args.get_one::<PathBuf>("FILE").unwrap();
     let grib = cli::grib(file_name)?;
 
+    if args.get_flag("count") {
+        let num_messages = grib.sections().filter(|sect| sect.num == 0).count();
+        println!("submessages: {}", grib.len());
+        println!("messages: {num_messages}");
+        return Ok(());
+    }
+
     let mode = if args.get_flag("dump") {
         ListViewMode::Dump
     } else {
@@ -109,10 +121,14 @@ impl<R> Display for ListView<'_, R> {
                     let grid_def = submessage.grid_def();
                     let num_grid_points = grid_def.num_points();
                     let num_points_represented = submessage.repr_def().num_points();
-                    let grid_type = grib::GridDefinitionTemplateValues::try_from(grid_def)
+                    let grid_type = submessage
+                        .grid_definition_values()
                         .map(|def| Cow::from(def.short_name()))
                         .unwrap_or_else(|_| {
-                            Cow::from(format!("unknown (template {})", grid_def.grid_tmpl_num()))
+                            Cow::from(format!(
+                                "unknown (template {})",
+                                submessage.grid_definition_template_number()
+                            ))
                         });
                     writeln!(
                         f,
@@ -0,0 +1,46 @@
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use anyhow::Result;
+use clap::{arg, ArgMatches, Command};
+
+use crate::cli;
+
+pub fn cli() -> Command {
+    Command::new("merge")
+        .about("Concatenate messages in multiple files into one file")
+        .arg(arg!(<FILE> ... "Target files").value_parser(clap::value_parser!(PathBuf)))
+        .arg(
+            arg!(-o --output <OUT_FILE> "Output file")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+}
+
+pub fn exec(args: &ArgMatches) -> Result<()> {
+    let file_names = args.get_many::<PathBuf>("FILE").unwrap();
+    let out_path = args.get_one::<PathBuf>("output").unwrap();
+
+    // All inputs are checked before the output file is created.
+    let gribs = file_names
+        .map(|file_name| {
+            cli::grib(file_name).map_err(|e| anyhow::anyhow!("{}: {e}", file_name.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let f = BufWriter::new(File::create(out_path)?);
+    let mut writer = grib::Grib2Writer::new(f);
+    for grib in gribs {
+        let message_indices = grib
+            .iter()
+            .filter(|((_, submessage_part), _)| *submessage_part == 0)
+            .map(|((message_index, _), _)| message_index)
+            .collect::<Vec<_>>();
+        for message_index in message_indices {
+            // Only bytes from Section 0 to Section 8 are extracted, so that data
+            // between and after messages is skipped.
+            writer.write_message(&grib.extract_message(message_index)?)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::{arg, ArgMatches, Command};
+use grib::{codetables::NCEP, Parameter};
+
+use crate::cli;
+
+pub fn cli() -> Command {
+    Command::new("split")
+        .about("Write each message to a separate file")
+        .arg(arg!(<FILE> "Target file").value_parser(clap::value_parser!(PathBuf)))
+        .arg(arg!(<OUT_DIR> "Output directory").value_parser(clap::value_parser!(PathBuf)))
+        .arg(arg!(-p --"with-param" "Include the parameter of the first submessage in file names"))
+}
+
+pub fn exec(args: &ArgMatches) -> Result<()> {
+    let file_name = args.get_one::<PathBuf>("FILE").unwrap();
+    let grib = cli::grib(file_name)?;
+    let out_dir = args.get_one::<PathBuf>("OUT_DIR").unwrap();
+    let with_param = args.get_flag("with-param");
+
+    // Names are determined before extracting messages, since submessages hold the
+    // reader while they are alive.
+    let out_names = grib
+        .iter()
+        .filter(|((_, submessage_part), _)| *submessage_part == 0)
+        .map(|((message_index, _), submessage)| {
+            let param = submessage.parameter().filter(|_| with_param);
+            let out_name = match param {
+                Some(param) => format!("{message_index}_{}.grib2", param_name(&param)),
+                None => format!("{message_index}.grib2"),
+            };
+            (message_index, out_name)
+        })
+        .collect::<Vec<_>>();
+
+    fs::create_dir_all(out_dir)?;
+    for (message_index, out_name) in out_names {
+        let message = grib.extract_message(message_index)?;
+        fs::write(out_dir.join(out_name), message)?;
+    }
+    Ok(())
+}
+
+fn param_name(param: &Parameter) -> String {
+    NCEP::try_from(param)
+        .map(|code| format!("{code:?}"))
+        .unwrap_or_else(|_| format!("{}_{}_{}", param.discipline, param.category, param.num))
+}
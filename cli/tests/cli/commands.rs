@@ -3,6 +3,8 @@ pub(crate) mod decode;
 pub(crate) mod info;
 pub(crate) mod inspect;
 pub(crate) mod list;
+pub(crate) mod merge;
+pub(crate) mod split;
 
 macro_rules! test_simple_display {
     ($(($name:ident, $command:expr, $input:expr, $options:expr, $expected_stdout:expr),)*) => ($(
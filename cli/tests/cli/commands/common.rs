@@ -32,7 +32,9 @@ test_subcommands_without_args! {
     (decode_without_args, "decode"),
     (info_without_args, "info"),
     (list_without_args, "list"),
+    (merge_without_args, "merge"),
     (inspect_without_args, "inspect"),
+    (split_without_args, "split"),
 }
 
 macro_rules! test_subcommands_with_nonexisting_file {
@@ -59,6 +61,8 @@ test_subcommands_with_nonexisting_file! {
     (info_with_nonexisting_file, "info", Vec::<&str>::new()),
     (inspect_with_nonexisting_file, "inspect", Vec::<&str>::new()),
     (list_with_nonexisting_file, "list", Vec::<&str>::new()),
+    (merge_with_nonexisting_file, "merge", vec!["-o", "out.grib2"]),
+    (split_with_nonexisting_file, "split", vec!["out"]),
 }
 
 macro_rules! test_subcommands_with_wrong_input_files {
@@ -108,6 +112,20 @@ test_subcommands_with_wrong_input_files! {
         Vec::<&str>::new(),
         predicate::str::diff("error: empty GRIB2 data\n")
     ),
+    (
+        merge_with_non_grib,
+        "merge",
+        utils::testdata::non_grib_file()?,
+        vec!["-o", "out.grib2"],
+        predicate::str::starts_with("error: ").and(predicate::str::ends_with(": empty GRIB2 data\n"))
+    ),
+    (
+        split_with_non_grib,
+        "split",
+        utils::testdata::non_grib_file()?,
+        vec!["out"],
+        predicate::str::diff("error: empty GRIB2 data\n")
+    ),
     (
         decode_with_empty_file,
         "decode",
@@ -136,6 +154,20 @@ test_subcommands_with_wrong_input_files! {
         Vec::<&str>::new(),
         predicate::str::diff("error: empty GRIB2 data\n")
     ),
+    (
+        merge_with_empty_file,
+        "merge",
+        utils::testdata::empty_file()?,
+        vec!["-o", "out.grib2"],
+        predicate::str::starts_with("error: ").and(predicate::str::ends_with(": empty GRIB2 data\n"))
+    ),
+    (
+        split_with_empty_file,
+        "split",
+        utils::testdata::empty_file()?,
+        vec!["out"],
+        predicate::str::diff("error: empty GRIB2 data\n")
+    ),
     (
         decode_with_too_small_file,
         "decode",
@@ -164,4 +196,72 @@ test_subcommands_with_wrong_input_files! {
         Vec::<&str>::new(),
         predicate::str::diff("error: empty GRIB2 data\n")
     ),
+    (
+        merge_with_too_small_file,
+        "merge",
+        utils::testdata::too_small_file()?,
+        vec!["-o", "out.grib2"],
+        predicate::str::starts_with("error: ").and(predicate::str::ends_with(": empty GRIB2 data\n"))
+    ),
+    (
+        split_with_too_small_file,
+        "split",
+        utils::testdata::too_small_file()?,
+        vec!["out"],
+        predicate::str::diff("error: empty GRIB2 data\n")
+    ),
+    (
+        decode_with_grib1_file,
+        "decode",
+        utils::testdata::grib1_file()?,
+        vec!["1.1"],
+        predicate::str::diff(
+            "error: GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        )
+    ),
+    (
+        info_with_grib1_file,
+        "info",
+        utils::testdata::grib1_file()?,
+        Vec::<&str>::new(),
+        predicate::str::diff(
+            "error: GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        )
+    ),
+    (
+        inspect_with_grib1_file,
+        "inspect",
+        utils::testdata::grib1_file()?,
+        Vec::<&str>::new(),
+        predicate::str::diff(
+            "error: GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        )
+    ),
+    (
+        list_with_grib1_file,
+        "list",
+        utils::testdata::grib1_file()?,
+        Vec::<&str>::new(),
+        predicate::str::diff(
+            "error: GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        )
+    ),
+    (
+        merge_with_grib1_file,
+        "merge",
+        utils::testdata::grib1_file()?,
+        vec!["-o", "out.grib2"],
+        predicate::str::starts_with("error: ").and(predicate::str::ends_with(
+            ": GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        ))
+    ),
+    (
+        split_with_grib1_file,
+        "split",
+        utils::testdata::grib1_file()?,
+        vec!["out"],
+        predicate::str::diff(
+            "error: GRIB edition 1 is not supported (this crate handles GRIB2 only)\n"
+        )
+    ),
 }
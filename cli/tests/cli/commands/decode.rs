@@ -160,6 +160,151 @@ fn decoding_run_length_packing_as_big_endian() -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[test]
+fn decoding_submessage_selected_by_parameter_and_level() -> Result<(), Box<dyn std::error::Error>> {
+    let input = utils::testdata::grib2::jma_meps()?;
+
+    let dir = TempDir::new()?;
+    let out_path_by_name = dir.path().join("by_name.bin");
+    let out_path_by_index = dir.path().join("by_index.bin");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("--param")
+        .arg("HGT")
+        .arg("--level")
+        .arg("500hPa")
+        .arg("-b")
+        .arg(&out_path_by_name);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("0.14")
+        .arg("-b")
+        .arg(&out_path_by_index);
+    cmd.assert().success();
+
+    let actual = std::fs::read(&out_path_by_name)?;
+    let expected = std::fs::read(&out_path_by_index)?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn decoding_with_unit_conversion() -> Result<(), Box<dyn std::error::Error>> {
+    let input = utils::testdata::grib2::jma_meps()?;
+
+    let dir = TempDir::new()?;
+    let out_path_in_kelvin = dir.path().join("kelvin.bin");
+    let out_path_in_celsius = dir.path().join("celsius.bin");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("0.2")
+        .arg("-l")
+        .arg(&out_path_in_kelvin);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("0.2")
+        .arg("--offset")
+        .arg("-273.15")
+        .arg("-l")
+        .arg(&out_path_in_celsius);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    let in_kelvin = std::fs::read(&out_path_in_kelvin)?;
+    let in_celsius = std::fs::read(&out_path_in_celsius)?;
+    assert_eq!(in_celsius.len(), in_kelvin.len());
+    let to_f32 =
+        |buf: &[u8], i: usize| f32::from_le_bytes(buf[i * 4..i * 4 + 4].try_into().unwrap());
+    for i in [0, 1000, in_kelvin.len() / 4 - 1] {
+        assert_eq!(to_f32(&in_celsius, i), to_f32(&in_kelvin, i) - 273.15);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn decoding_with_scaling_and_offset() -> Result<(), Box<dyn std::error::Error>> {
+    let input = utils::testdata::grib2::jma_tornado_nowcast()?;
+
+    let dir = TempDir::new()?;
+    let out_path = dir.path().join("out.bin");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("0.3")
+        .arg("--scale")
+        .arg("2")
+        .arg("--offset")
+        .arg("1")
+        .arg("-b")
+        .arg(&out_path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    let expected = utils::testdata::flat_binary::jma_tornado_nowcast_be()?;
+    let expected: Vec<_> = expected
+        .chunks(4)
+        .flat_map(|b| match b {
+            [0x62, 0x58, 0xd1, 0x9a] => f32::NAN.to_be_bytes(),
+            b => (f32::from_be_bytes(b.try_into().unwrap()) * 2. + 1.).to_be_bytes(),
+        })
+        .collect();
+    let actual = std::fs::read(&out_path)?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+macro_rules! test_submessage_selection_failures {
+    ($(($name:ident, $selection_args:expr, $message:expr),)*) => ($(
+        #[test]
+        fn $name() -> Result<(), Box<dyn std::error::Error>> {
+            let input = utils::testdata::grib2::jma_meps()?;
+
+            let mut cmd = Command::cargo_bin(CMD_NAME)?;
+            cmd.arg("decode").arg(input.path()).args($selection_args);
+            cmd.assert()
+                .failure()
+                .stdout(predicate::str::is_empty())
+                .stderr(predicate::str::contains($message));
+
+            Ok(())
+        }
+    )*);
+}
+
+test_submessage_selection_failures! {
+    (
+        submessage_selection_failure_due_to_ambiguity,
+        ["--param", "Geopotential height"],
+        "multiple submessages match the specified parameter and level: 0.14, 0.17"
+    ),
+    (
+        submessage_selection_failure_due_to_no_match,
+        ["--param", "PRMSL", "--level", "500hPa"],
+        "no submessage matches the specified parameter and level"
+    ),
+}
+
 macro_rules! test_operation_with_data_with_nan_values_as_little_endian {
     ($(($name:ident, $input:expr, $message_index:expr, $byte_order_flag:expr, $expected:expr),)*) => ($(
         #[test]
@@ -371,3 +516,128 @@ test_trial_to_decode_nonexisting_submessage! {
         "999.0"
     ),
 }
+
+#[cfg(feature = "netcdf")]
+macro_rules! test_operation_with_netcdf_output {
+    ($((
+        $name:ident,
+        $input:expr,
+        $message_index:expr,
+        $expected_le:expr,
+        $expected_shape:expr,
+        $expected_units:expr
+    ),)*) => ($(
+        #[test]
+        fn $name() -> Result<(), Box<dyn std::error::Error>> {
+            let input = $input;
+
+            let dir = TempDir::new()?;
+            let out_path = dir.path().join("out.nc");
+
+            let mut cmd = Command::cargo_bin(CMD_NAME)?;
+            cmd.arg("decode")
+                .arg(input.path())
+                .arg($message_index)
+                .arg("--format")
+                .arg("netcdf")
+                .arg("--out")
+                .arg(&out_path);
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::is_empty())
+                .stderr(predicate::str::is_empty());
+
+            let mut reader = netcdf3::FileReader::open(&out_path)?;
+            let data_set = reader.data_set();
+            let (nlat, nlon) = $expected_shape;
+            assert_eq!(data_set.dim_size("lat"), Some(nlat));
+            assert_eq!(data_set.dim_size("lon"), Some(nlon));
+            assert_eq!(
+                data_set.get_var_attr_as_string("lat", "units"),
+                Some("degrees_north".to_owned())
+            );
+            assert_eq!(
+                data_set.get_var_attr_as_string("lon", "units"),
+                Some("degrees_east".to_owned())
+            );
+            assert_eq!(
+                data_set.get_var_attr_as_string("values", "units"),
+                $expected_units.map(str::to_owned)
+            );
+            assert_eq!(
+                data_set.get_var_attr_f32("values", "_FillValue"),
+                Some([netcdf3::NC_FILL_F32].as_slice())
+            );
+
+            assert_eq!(reader.read_var("lat")?.len(), nlat);
+            assert_eq!(reader.read_var("lon")?.len(), nlon);
+            let actual = reader.read_var("values")?.get_f32_into().unwrap();
+            // Missing values, which are 9.999e20 in wgrib2 output, are stored
+            // as the fill value.
+            let expected = $expected_le
+                .chunks(4)
+                .map(|b| match b {
+                    [0x9a, 0xd1, 0x58, 0x62] => netcdf3::NC_FILL_F32,
+                    b => f32::from_le_bytes(b.try_into().unwrap()),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+
+            Ok(())
+        }
+    )*);
+}
+
+#[cfg(feature = "netcdf")]
+test_operation_with_netcdf_output! {
+    (
+        decoding_simple_packing_as_netcdf,
+        utils::testdata::grib2::jma_kousa()?,
+        "0.3",
+        utils::testdata::flat_binary::jma_kousa_le()?,
+        (61, 81),
+        None::<&str>
+    ),
+    (
+        decoding_simple_packing_with_bitmap_as_netcdf,
+        utils::testdata::grib2::jma_msmguid()?,
+        "0.0",
+        utils::testdata::flat_binary::jma_msmguid_le()?,
+        (560, 480),
+        None::<&str>
+    ),
+    (
+        decoding_complex_packing_with_wmo_parameter_as_netcdf,
+        utils::testdata::grib2::ncmrwf_wind_solar()?,
+        "0.0",
+        utils::testdata::flat_binary::ncmrwf_wind_solar_le()?,
+        (249, 249),
+        Some("gpm")
+    ),
+}
+
+#[cfg(feature = "netcdf")]
+#[test]
+fn trial_to_decode_non_latlon_grid_as_netcdf() -> Result<(), Box<dyn std::error::Error>> {
+    let input = utils::testdata::grib2::multi_message_data(1)?;
+
+    let dir = TempDir::new()?;
+    let out_path = dir.path().join("out.nc");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("decode")
+        .arg(input.path())
+        .arg("0.0")
+        .arg("--format")
+        .arg("netcdf")
+        .arg("--out")
+        .arg(&out_path);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "NetCDF output is supported only for regular lat/lon grids",
+        ));
+
+    Ok(())
+}
@@ -47,14 +47,14 @@ Sections:
    31 │ 000000000000284d - 0000000000002851 │ Section 8
 
 SubMessages:
-      id │    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5
-     0.0 │     -     2     3     4     5     6 │ 3.0     4.0     5.200  
-     0.1 │     -     2     7     8     9    10 │ 3.0     4.0     5.200  
-     0.2 │     -     2    11    12    13    14 │ 3.0     4.0     5.200  
-     0.3 │     -     2    15    16    17    18 │ 3.0     4.0     5.200  
-     0.4 │     -     2    19    20    21    22 │ 3.0     4.0     5.200  
-     0.5 │     -     2    23    24    25    26 │ 3.0     4.0     5.200  
-     0.6 │     -     2    27    28    29    30 │ 3.0     4.0     5.200  
+      id │    S1    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5   │ Centre Master Local
+     0.0 │     1     -     2     3     4     5     6 │ 3.0     4.0     5.200   │     34      5     1
+     0.1 │     1     -     2     7     8     9    10 │ 3.0     4.0     5.200   │     34      5     1
+     0.2 │     1     -     2    11    12    13    14 │ 3.0     4.0     5.200   │     34      5     1
+     0.3 │     1     -     2    15    16    17    18 │ 3.0     4.0     5.200   │     34      5     1
+     0.4 │     1     -     2    19    20    21    22 │ 3.0     4.0     5.200   │     34      5     1
+     0.5 │     1     -     2    23    24    25    26 │ 3.0     4.0     5.200   │     34      5     1
+     0.6 │     1     -     2    27    28    29    30 │ 3.0     4.0     5.200   │     34      5     1
 
 Templates:
 3.0      - Latitude/longitude
@@ -98,10 +98,10 @@ Sections:
    26 │ 000000000000023f - 0000000000000243 │ Section 8
 
 SubMessages:
-      id │    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5
-     0.0 │     2     3     4     5     6     7 │ 3.101   4.8     5.0    
-     1.0 │    11    12    13    14    15    16 │ 3.101   4.8     5.0    
-     2.0 │    20    21    22    23    24    25 │ 3.101   4.8     5.0    
+      id │    S1    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5   │ Centre Master Local
+     0.0 │     1     2     3     4     5     6     7 │ 3.101   4.8     5.0     │     78     19     1
+     1.0 │    10    11    12    13    14    15    16 │ 3.101   4.8     5.0     │     78     19     1
+     2.0 │    19    20    21    22    23    24    25 │ 3.101   4.8     5.0     │     78     19     1
 
 Templates:
 3.101    - General unstructured grid
@@ -134,9 +134,9 @@ Sections:
    15 │ 000000000017aeb3 - 000000000017aeb7 │ Section 8
 
 SubMessages:
-      id │    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5
-     0.0 │     -     2     3     4     5     6 │ 3.30    4.8     5.3    
-     1.0 │     -    10    11    12    13    14 │ 3.30    4.8     5.3    
+      id │    S1    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5   │ Centre Master Local
+     0.0 │     1     -     2     3     4     5     6 │ 3.30    4.8     5.3     │      8      1     0
+     1.0 │     9     -    10    11    12    13    14 │ 3.30    4.8     5.3     │      8      1     0
 
 Templates:
 3.30     - Lambert conformal
@@ -188,14 +188,24 @@ Templates:
         "inspect",
         utils::testdata::grib2::jma_tornado_nowcast()?,
         vec!["-m"],
-        "      id │    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5
-     0.0 │     -     2     3     4     5     6 │ 3.0     4.0     5.200  
-     0.1 │     -     2     7     8     9    10 │ 3.0     4.0     5.200  
-     0.2 │     -     2    11    12    13    14 │ 3.0     4.0     5.200  
-     0.3 │     -     2    15    16    17    18 │ 3.0     4.0     5.200  
-     0.4 │     -     2    19    20    21    22 │ 3.0     4.0     5.200  
-     0.5 │     -     2    23    24    25    26 │ 3.0     4.0     5.200  
-     0.6 │     -     2    27    28    29    30 │ 3.0     4.0     5.200  
+        "      id │    S1    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5   │ Centre Master Local
+     0.0 │     1     -     2     3     4     5     6 │ 3.0     4.0     5.200   │     34      5     1
+     0.1 │     1     -     2     7     8     9    10 │ 3.0     4.0     5.200   │     34      5     1
+     0.2 │     1     -     2    11    12    13    14 │ 3.0     4.0     5.200   │     34      5     1
+     0.3 │     1     -     2    15    16    17    18 │ 3.0     4.0     5.200   │     34      5     1
+     0.4 │     1     -     2    19    20    21    22 │ 3.0     4.0     5.200   │     34      5     1
+     0.5 │     1     -     2    23    24    25    26 │ 3.0     4.0     5.200   │     34      5     1
+     0.6 │     1     -     2    27    28    29    30 │ 3.0     4.0     5.200   │     34      5     1
+"
+    ),
+    (
+        display_with_opt_m_for_data_with_section_2,
+        "inspect",
+        utils::testdata::grib2::multi_message_data(2)?,
+        vec!["-m"],
+        "      id │    S1    S2    S3    S4    S5    S6    S7 │ Tmpl3   Tmpl4   Tmpl5   │ Centre Master Local
+     0.0 │     1     2     3     4     5     6     7 │ 3.101   4.8     5.0     │     78     19     1
+     1.0 │    10    11    12    13    14    15    16 │ 3.101   4.8     5.0     │     78     19     1
 "
     ),
     (
@@ -207,6 +217,34 @@ Templates:
 3.0      - Latitude/longitude
 4.0      - Analysis or forecast at a horizontal level or in a horizontal layer at a point in time
 5.200    - Run length packing with level values
+"
+    ),
+    (
+        display_with_opt_r,
+        "inspect",
+        utils::testdata::grib2::jma_meps()?,
+        vec!["-r"],
+        "      id │ Tmpl5   │ Nbits              R     E     D │       NG SpDiff
+     0.0 │ 5.3     │    14     -14.655413    -6     0 │     1906      2
+     0.1 │ 5.3     │    14     -17.375841    -6     0 │     1906      2
+     0.2 │ 5.3     │    14      275.89325    -7     0 │     1906      2
+     0.3 │ 5.3     │    14     -14.383656    -6     0 │     1906      2
+     0.4 │ 5.3     │    14     -15.979205    -6     0 │     1906      2
+     0.5 │ 5.3     │    14      274.84537    -7     0 │     1906      2
+     0.6 │ 5.3     │    14     -13.452219    -6     0 │     1906      2
+     0.7 │ 5.3     │    14     -16.698019    -6     0 │     1906      2
+     0.8 │ 5.3     │    14      274.47662    -7     0 │     1906      2
+     0.9 │ 5.3     │    14        5.38845    -5     0 │     1906      2
+    0.10 │ 5.3     │    14     -10.740026    -7     0 │     1906      2
+    0.11 │ 5.3     │    14     -18.829784    -6     0 │     1906      2
+    0.12 │ 5.3     │    14      274.69788    -7     0 │     1906      2
+    0.13 │ 5.3     │    14        3.48229    -5     0 │     1906      2
+    0.14 │ 5.3     │    14         5472.7    -3     0 │     1906      2
+    0.15 │ 5.3     │    14      249.55132    -7     0 │     1906      2
+    0.16 │ 5.3     │    14      1.0537826    -5     0 │     1906      2
+    0.17 │ 5.3     │    14       9029.614    -2     0 │     1906      2
+    0.18 │ 5.3     │    14     -12.488269    -6     0 │     1906      2
+    0.19 │ 5.3     │    14      -29.81222    -6     0 │     1906      2
 "
     ),
 }
@@ -234,3 +272,37 @@ fn display_with_all_options() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn display_as_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tempfile = utils::testdata::grib2::jma_tornado_nowcast()?;
+    let arg_path = tempfile.path();
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("inspect").arg("--json").arg(arg_path);
+    let output = cmd.assert().success().stderr(predicate::str::is_empty());
+    let json: serde_json::Value = serde_json::from_slice(&output.get_output().stdout)?;
+
+    let sections = json["sections"].as_array().ok_or("no sections")?;
+    assert_eq!(sections.len(), 32);
+    assert_eq!(sections[0]["section"], 0);
+    assert_eq!(sections[0]["offset"], 0);
+    assert_eq!(sections[0]["end"], 16);
+
+    let submessages = json["submessages"].as_array().ok_or("no submessages")?;
+    assert_eq!(submessages.len(), 7);
+    assert_eq!(submessages[1]["id"], "0.1");
+    assert_eq!(submessages[1]["s1"], 1);
+    assert_eq!(submessages[1]["s2"], serde_json::Value::Null);
+    assert_eq!(submessages[1]["s4"], 7);
+    assert_eq!(submessages[1]["tmpl5"], "5.200");
+    assert_eq!(submessages[1]["centre"], 34);
+    assert_eq!(submessages[1]["master_table_version"], 5);
+    assert_eq!(submessages[1]["local_table_version"], 1);
+
+    let templates = json["templates"].as_array().ok_or("no templates")?;
+    assert_eq!(templates[0]["template"], "3.0");
+    assert_eq!(templates[0]["description"], "Latitude/longitude");
+
+    Ok(())
+}
@@ -19,6 +19,24 @@ crate::commands::test_simple_display! {
      0.4 │ code '0' is not implemented     Forecast                   40 [m]                               NaN                               NaN │          0/     86016 regular_ll           
      0.5 │ code '0' is not implemented     Forecast                   50 [m]                               NaN                               NaN │          0/     86016 regular_ll           
      0.6 │ code '0' is not implemented     Forecast                   60 [m]                               NaN                               NaN │          0/     86016 regular_ll           
+"
+    ),
+    (
+        counting_submessages_in_grib2_with_multiple_submessages,
+        "list",
+        utils::testdata::grib2::jma_tornado_nowcast()?,
+        vec!["--count"],
+        "submessages: 7
+messages: 1
+"
+    ),
+    (
+        counting_submessages_in_grib2_with_multiple_messages,
+        "list",
+        utils::testdata::grib2::multi_message_data(3)?,
+        vec!["-c"],
+        "submessages: 3
+messages: 3
 "
     ),
     (
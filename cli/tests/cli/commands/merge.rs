@@ -0,0 +1,84 @@
+use std::{
+    fs::File,
+    io::{BufReader, Write},
+    process::Command,
+};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use tempfile::{NamedTempFile, TempDir};
+
+use crate::{utils, CMD_NAME};
+
+#[test]
+fn merging_single_message_files() -> Result<(), Box<dyn std::error::Error>> {
+    let first = utils::testdata::grib2::multi_message_data(1)?;
+    let second = utils::testdata::grib2::multi_message_data(1)?;
+    let dir = TempDir::new()?;
+    let out_path = dir.path().join("out.grib2");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("merge")
+        .arg(first.path())
+        .arg(second.path())
+        .arg("-o")
+        .arg(&out_path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    let f = BufReader::new(File::open(&out_path)?);
+    let grib2 = grib::from_reader(f)?;
+    assert_eq!(grib2.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn merging_files_with_trailing_junk() -> Result<(), Box<dyn std::error::Error>> {
+    let mut first = utils::testdata::grib2::multi_message_data(2)?;
+    first.write_all(b"TRAILING JUNK")?;
+    let second = utils::testdata::grib2::multi_message_data(1)?;
+    let dir = TempDir::new()?;
+    let out_path = dir.path().join("out.grib2");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("merge")
+        .arg(first.path())
+        .arg(second.path())
+        .arg("-o")
+        .arg(&out_path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+
+    let expected = utils::get_uncompressed(utils::testdata::grib2::multi_message_data(3)?.path())?;
+    let actual = std::fs::read(&out_path)?;
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[test]
+fn merging_with_invalid_file() -> Result<(), Box<dyn std::error::Error>> {
+    let first = utils::testdata::grib2::multi_message_data(1)?;
+    let second = NamedTempFile::new()?;
+    let dir = TempDir::new()?;
+    let out_path = dir.path().join("out.grib2");
+
+    let mut cmd = Command::cargo_bin(CMD_NAME)?;
+    cmd.arg("merge")
+        .arg(first.path())
+        .arg(second.path())
+        .arg("-o")
+        .arg(&out_path);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::ends_with(": empty GRIB2 data\n"));
+    assert!(!out_path.exists());
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use std::{fs::File, io::BufReader, process::Command};
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+use crate::{utils, CMD_NAME};
+
+macro_rules! test_splitting_multi_message_data {
+    ($(($name:ident, $options:expr, $expected_file_names:expr),)*) => ($(
+        #[test]
+        fn $name() -> Result<(), Box<dyn std::error::Error>> {
+            let input = utils::testdata::grib2::multi_message_data(3)?;
+            let dir = TempDir::new()?;
+            let out_dir = dir.path().join("out");
+
+            let mut cmd = Command::cargo_bin(CMD_NAME)?;
+            cmd.arg("split")
+                .args($options)
+                .arg(input.path())
+                .arg(&out_dir);
+            cmd.assert()
+                .success()
+                .stdout(predicate::str::is_empty())
+                .stderr(predicate::str::is_empty());
+
+            let mut actual = std::fs::read_dir(&out_dir)?
+                .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+                .collect::<Result<Vec<_>, _>>()?;
+            actual.sort();
+            assert_eq!(actual, $expected_file_names);
+
+            for file_name in actual {
+                let f = BufReader::new(File::open(out_dir.join(file_name))?);
+                let grib2 = grib::from_reader(f)?;
+                assert_eq!(grib2.len(), 1);
+            }
+
+            Ok(())
+        }
+    )*);
+}
+
+test_splitting_multi_message_data! {
+    (
+        splitting_multi_message_data,
+        Vec::<&str>::new(),
+        vec!["0.grib2", "1.grib2", "2.grib2"]
+    ),
+    (
+        splitting_multi_message_data_with_param_names,
+        vec!["--with-param"],
+        vec!["0_0_1_52.grib2", "1_0_1_52.grib2", "2_0_1_52.grib2"]
+    ),
+}
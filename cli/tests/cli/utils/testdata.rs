@@ -31,6 +31,15 @@ pub(crate) fn non_grib_file() -> Result<NamedTempFile, io::Error> {
     Ok(out)
 }
 
+pub(crate) fn grib1_file() -> Result<NamedTempFile, io::Error> {
+    let mut out = NamedTempFile::new()?;
+    // "GRIB", 3-octet total length, and edition number 1, followed by dummy data
+    out.write_all(b"GRIB\x00\x00\x28\x01")?;
+    out.write_all(&[0; 32])?;
+
+    Ok(out)
+}
+
 pub(crate) mod grib2 {
     use super::*;
 
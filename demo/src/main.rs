@@ -97,10 +97,13 @@ fn app() -> Html {
                 let grid_def = submessage.grid_def();
                 let num_grid_points = grid_def.num_points();
                 let num_points_represented = submessage.repr_def().num_points();
-                let grid_type = grib::GridDefinitionTemplateValues::try_from(grid_def)
+                let grid_type = submessage.grid_definition_values()
                     .map(|def| Cow::from(def.short_name()))
                     .unwrap_or_else(|_| {
-                        Cow::from(format!("unknown (template {})", grid_def.grid_tmpl_num()))
+                        Cow::from(format!(
+                            "unknown (template {})",
+                            submessage.grid_definition_template_number()
+                        ))
                     });
 
                 let grib_context_ = grib_context.clone();
@@ -113,21 +116,26 @@ fn app() -> Html {
                                 grib.submessages().nth(index)
                             {
                                 if let Ok((w, h)) = submessage.grid_shape() {
-                                    let decoder =
-                                        grib::Grib2SubmessageDecoder::from(submessage).unwrap(); // FIXME
-                                    let values = decoder.dispatch().unwrap(); // FIXME
-                                    let pixel_bytes = values
-                                        .flat_map(palette::jma_amedas_temperature)
-                                        .collect::<Vec<_>>();
-                                    let pixel_bytes: &[u8] = &pixel_bytes;
-                                    let pixel_bytes = wasm_bindgen::Clamped(pixel_bytes);
-                                    let image_data = ImageData::new_with_u8_clamped_array_and_sh(
-                                        pixel_bytes,
-                                        w as u32,
-                                        h as u32,
-                                    )
-                                    .unwrap(); // FIXME
-                                    Some(image_data)
+                                    let values = grib::Grib2SubmessageDecoder::from(submessage)
+                                        .and_then(|decoder| {
+                                            decoder.dispatch().map(|values| values.collect())
+                                        });
+                                    match values {
+                                        Ok(values) => Some(create_image_data(values, w, h)),
+                                        Err(grib::GribError::UnsupportedTemplate {
+                                            section: 5,
+                                            ..
+                                        }) => {
+                                            utils::alert(
+                                                "Decoding of this data representation template is not supported",
+                                            );
+                                            None
+                                        }
+                                        Err(e) => {
+                                            utils::alert(&format!("Failed to decode values: {e}"));
+                                            None
+                                        }
+                                    }
                                 } else {
                                     None
                                 }
@@ -203,6 +211,18 @@ fn app() -> Html {
     }
 }
 
+fn create_image_data(values: Vec<f32>, width: usize, height: usize) -> ImageData {
+    let pixel_bytes = values
+        .into_iter()
+        .flat_map(palette::jma_amedas_temperature)
+        .collect::<Vec<_>>();
+    let pixel_bytes: &[u8] = &pixel_bytes;
+    let pixel_bytes = wasm_bindgen::Clamped(pixel_bytes);
+    let image_data =
+        ImageData::new_with_u8_clamped_array_and_sh(pixel_bytes, width as u32, height as u32);
+    image_data.unwrap() // FIXME
+}
+
 fn format_surface(surface: &grib::FixedSurface) -> String {
     let value = surface.value();
     let unit = surface
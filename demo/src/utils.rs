@@ -5,3 +5,9 @@ pub(crate) fn get_classes(element_id: &str) -> Option<web_sys::DomTokenList> {
     let class_list = element.class_list();
     Some(class_list)
 }
+
+pub(crate) fn alert(message: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
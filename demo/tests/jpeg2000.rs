@@ -0,0 +1,25 @@
+#![cfg(target_arch = "wasm32")]
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn decoding_jpeg2000_code_stream() -> Result<(), Box<dyn std::error::Error>> {
+    let buf =
+        include_bytes!("../../testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2");
+    let grib2 = grib::from_reader(std::io::Cursor::new(buf))?;
+    let (_index, first_submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+    let decoder = grib::Grib2SubmessageDecoder::from(first_submessage)?;
+    let mut decoded = decoder.dispatch()?;
+    assert_eq!(decoded.size_hint(), (1126500, Some(1126500)));
+
+    let first_value = decoded.next();
+    assert_eq!(first_value.map(|f| f.round()), Some(236.0_f32));
+
+    let last_value = decoded.nth(1126498);
+    assert_eq!(last_value.map(|f| f.round()), Some(286.0_f32));
+
+    let next_to_last_value = decoded.next();
+    assert_eq!(next_to_last_value, None);
+    Ok(())
+}
@@ -9,14 +9,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     // forecast time in hours.
     let mut args = env::args().skip(1);
     if let (Some(file_path), Some(forecast_time)) = (args.next(), args.next()) {
-        let forecast_time = forecast_time.parse::<u32>()?;
+        let forecast_time = forecast_time.parse::<i32>()?;
         find_layers(file_path, forecast_time)
     } else {
         panic!("Usage: find_layers <path> <forecast_time>");
     }
 }
 
-fn find_layers<P>(path: P, forecast_time_hours: u32) -> Result<(), Box<dyn Error>>
+fn find_layers<P>(path: P, forecast_time_hours: i32) -> Result<(), Box<dyn Error>>
 where
     P: AsRef<Path>,
 {
@@ -20,7 +20,6 @@ struct Record {
     #[allow(dead_code)]
     #[serde(rename = "Note_en")]
     note: String,
-    #[allow(dead_code)]
     #[serde(rename = "UnitComments_en")]
     unit: String,
     #[allow(dead_code)]
@@ -84,12 +83,9 @@ impl CodeDB {
                 out_tables.push((category, new_codetable));
             }
 
-            out_tables
-                .last_mut()
-                .unwrap()
-                .1
-                .data
-                .push((record.code_flag, record.description));
+            let table = &mut out_tables.last_mut().unwrap().1;
+            table.data.push((record.code_flag, record.description));
+            table.units.push(record.unit);
         }
 
         Ok(out_tables)
@@ -134,6 +130,10 @@ impl fmt::Display for CodeDB {
 
             let variable_name = self.get_variable_name(*id);
             write!(f, "{}", code_table.export(&variable_name))?;
+            // Units are meaningful only for parameters.
+            if let (4, 2, _) = id {
+                write!(f, "\n\n{}", code_table.export_units(&variable_name))?;
+            }
         }
         Ok(())
     }
@@ -236,6 +236,7 @@ mod tests {
                             .iter()
                             .map(|(a, b)| (a.to_string(), b.to_string()))
                             .collect::<Vec<_>>(),
+                        units: vec![String::new(); table.len()],
                     },
                 )
             })
@@ -294,6 +295,7 @@ mod tests {
                             .iter()
                             .map(|(a, b)| (a.to_string(), b.to_string()))
                             .collect::<Vec<_>>(),
+                        units: vec![String::new(); table.len()],
                     },
                 )
             })
@@ -311,12 +313,12 @@ mod tests {
             (
                 OptArg::L2(0, 0),
                 vec![
-                    ("0", "Temperature"),
-                    ("1-9", "Reserved"),
-                    ("10", "Latent heat net flux"),
-                    ("11-191", "Reserved"),
-                    ("192-254", "Reserved for local use"),
-                    ("255", "Missing"),
+                    ("0", "Temperature", "K"),
+                    ("1-9", "Reserved", ""),
+                    ("10", "Latent heat net flux", "W m-2"),
+                    ("11-191", "Reserved", ""),
+                    ("192-254", "Reserved for local use", ""),
+                    ("255", "Missing", ""),
                 ],
             ),
             (
@@ -325,23 +327,27 @@ mod tests {
                     (
                         "0",
                         "Seconds prior to initial reference time (defined in Section 1)",
+                        "s",
                     ),
-                    ("1-191", "Reserved"),
-                    ("192-254", "Reserved for local use"),
-                    ("255", "Missing"),
+                    ("1-191", "Reserved", ""),
+                    ("192-254", "Reserved for local use", ""),
+                    ("255", "Missing", ""),
                 ],
             ),
             (
                 OptArg::L2(3, 2),
-                vec![("0", "Clear sky probability"), ("30", "Measurement cost")],
+                vec![
+                    ("0", "Clear sky probability", "%"),
+                    ("30", "Measurement cost", "Numeric"),
+                ],
             ),
             (
                 OptArg::L2(20, 0),
                 vec![
-                    ("0", "Universal thermal climate index"),
-                    ("1-191", "Reserved"),
-                    ("192-254", "Reserved for local use"),
-                    ("255", "Missing"),
+                    ("0", "Universal thermal climate index", "K"),
+                    ("1-191", "Reserved", ""),
+                    ("192-254", "Reserved for local use", ""),
+                    ("255", "Missing", ""),
                 ],
             ),
         ];
@@ -355,7 +361,11 @@ mod tests {
                         desc: expected_title.clone(),
                         data: table
                             .iter()
-                            .map(|(a, b)| (a.to_string(), b.to_string()))
+                            .map(|(a, b, _)| (a.to_string(), b.to_string()))
+                            .collect::<Vec<_>>(),
+                        units: table
+                            .iter()
+                            .map(|(_, _, c)| c.to_string())
                             .collect::<Vec<_>>(),
                     },
                 )
@@ -437,17 +447,45 @@ const CODE_TABLE_4_2_0_0: &[& str] = &[
     \"Latent heat net flux\",
 ];
 
+/// Baz (units)
+const CODE_TABLE_4_2_0_0_UNITS: &[& str] = &[
+    \"K\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"\",
+    \"W m-2\",
+];
+
 /// Baz
 const CODE_TABLE_4_2_0_191: &[& str] = &[
     \"Seconds prior to initial reference time (defined in Section 1)\",
 ];
 
+/// Baz (units)
+const CODE_TABLE_4_2_0_191_UNITS: &[& str] = &[
+    \"s\",
+];
+
 /// Baz
 const CODE_TABLE_4_2_3_2: &[& str] = &[];
 
+/// Baz (units)
+const CODE_TABLE_4_2_3_2_UNITS: &[& str] = &[];
+
 /// Baz
 const CODE_TABLE_4_2_20_0: &[& str] = &[
     \"Universal thermal climate index\",
+];
+
+/// Baz (units)
+const CODE_TABLE_4_2_20_0_UNITS: &[& str] = &[
+    \"K\",
 ];"
         );
     }
@@ -461,4 +499,14 @@ const CODE_TABLE_4_2_20_0: &[& str] = &[
             vec!["0A", "0B",]
         );
     }
+
+    #[test]
+    fn codetable_units_to_vec() {
+        let mut db = CodeDB::new();
+        db.load(PATH_STR_3).unwrap();
+        let table = db.get((4, 2, OptArg::L2(0, 0))).unwrap();
+        assert_eq!(table.units_to_vec().len(), table.to_vec().len());
+        assert_eq!(table.units_to_vec()[0], "K");
+        assert_eq!(table.units_to_vec()[10], "W m-2");
+    }
 }
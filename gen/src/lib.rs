@@ -65,6 +65,7 @@ pub enum CodeRangeParseError {
 pub struct CodeTable {
     desc: String,
     data: Vec<(String, String)>,
+    units: Vec<String>,
 }
 
 impl CodeTable {
@@ -72,6 +73,7 @@ impl CodeTable {
         Self {
             desc,
             data: Vec::new(),
+            units: Vec::new(),
         }
     }
 
@@ -86,13 +88,39 @@ const {}: &[& str] = &{:#?};",
         )
     }
 
+    fn export_units(&self, name: &str) -> String {
+        format!(
+            "\
+/// {} (units)
+const {}_UNITS: &[& str] = &{:#?};",
+            self.desc,
+            name,
+            self.units_to_vec(),
+        )
+    }
+
     fn to_vec(&self) -> Vec<String> {
+        self.expand(|i| &self.data[i].1)
+    }
+
+    fn units_to_vec(&self) -> Vec<String> {
+        self.expand(|i| self.units.get(i).map(String::as_str).unwrap_or_default())
+    }
+
+    /// Expands entries into a vector indexed by code, taking the value for
+    /// each entry from `value`. Whether an entry is meaningful is determined
+    /// by its description, so that all vectors from the same table share the
+    /// same indices.
+    fn expand<'a, F>(&'a self, value: F) -> Vec<String>
+    where
+        F: Fn(usize) -> &'a str,
+    {
         let mut output = Vec::new();
 
         let mut count = 0;
         let mut empty_count = 0;
 
-        for entry in self.data.iter() {
+        for (i, entry) in self.data.iter().enumerate() {
             let (id, string) = entry;
             let string = match string.as_str() {
                 "Future versions" => None,
@@ -102,7 +130,7 @@ const {}: &[& str] = &{:#?};",
                 "Missing" => None,
                 "Missing value" => None,
                 ")" => None,
-                _ => Some(string),
+                _ => Some(value(i)),
             };
 
             if let Ok(range) = id.parse::<CodeRange>() {
@@ -117,12 +145,8 @@ const {}: &[& str] = &{:#?};",
                         return Vec::new(); // Sparse code tables are not
                                            // supported at the moment.
                     }
-                    if range.size() == 1 {
-                        output.push(string.to_string());
-                    } else {
-                        for _i in range.start..=range.end {
-                            output.push(string.clone());
-                        }
+                    for _i in range.start..=range.end {
+                        output.push(string.to_owned());
                     }
                     count += range.size();
                 } else {
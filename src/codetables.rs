@@ -1,7 +1,17 @@
+#[cfg(feature = "std")]
+mod canonical;
+#[cfg(feature = "std")]
+pub(crate) use canonical::lookup as canonical_lookup;
+#[cfg(feature = "std")]
+pub use canonical::CanonicalParameter;
 mod core;
 pub use self::core::Code::{self, Name, Num};
+#[cfg(feature = "std")]
 mod external;
+#[cfg(feature = "std")]
 pub use external::*;
 pub mod grib2;
+#[cfg(feature = "std")]
 mod old;
+#[cfg(feature = "std")]
 pub use old::*;
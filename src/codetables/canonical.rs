@@ -0,0 +1,69 @@
+use crate::Parameter;
+
+/// Physical quantity of a parameter, normalized across originating centres.
+///
+/// This `enum` is returned by [`Parameter::canonical`] and allows parameters
+/// encoded with different codes, such as WMO codes and centre-specific local
+/// codes, to be compared with each other.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum CanonicalParameter {
+    /// Temperature.
+    Temperature,
+    /// Pressure.
+    Pressure,
+    /// Pressure reduced to mean sea level.
+    PressureReducedToMsl,
+    /// Total precipitation accumulated over a period.
+    TotalPrecipitation,
+    /// Precipitation rate.
+    PrecipitationRate,
+    /// Total precipitation rate, i.e. the sum of large-scale and convective
+    /// precipitation rates.
+    TotalPrecipitationRate,
+}
+
+// Codes are `(discipline << 16) + (category << 8) + number`, as used in
+// [`crate::codetables::NCEP`].
+const WMO: &[(u32, CanonicalParameter)] = &[
+    (0x_00_00_00, CanonicalParameter::Temperature),
+    (0x_00_01_07, CanonicalParameter::PrecipitationRate),
+    (0x_00_01_08, CanonicalParameter::TotalPrecipitation),
+    (0x_00_01_34, CanonicalParameter::TotalPrecipitationRate),
+    (0x_00_03_00, CanonicalParameter::Pressure),
+    (0x_00_03_01, CanonicalParameter::PressureReducedToMsl),
+];
+
+// Local codes are only meaningful together with the originating centre.
+//
+// JMA encodes temperature and pressure with WMO codes, so only its local
+// precipitation codes are listed here.
+const LOCAL: &[(u16, u32, CanonicalParameter)] = &[
+    // JMA: 1-hour precipitation (radar/rain gauge-analyzed precipitation and
+    // precipitation short-range forecasts)
+    (34, 0x_00_01_c8, CanonicalParameter::TotalPrecipitation),
+    // JMA: precipitation intensity (precipitation nowcasts)
+    (34, 0x_00_c1_00, CanonicalParameter::PrecipitationRate),
+    // NCEP: MSLET (MSLP (Eta model reduction))
+    (7, 0x_00_03_c0, CanonicalParameter::PressureReducedToMsl),
+    // NCEP: MSLMA (MSLP (MAPS system reduction))
+    (7, 0x_00_03_c6, CanonicalParameter::PressureReducedToMsl),
+];
+
+pub(crate) fn lookup(param: &Parameter) -> Option<CanonicalParameter> {
+    let code = param.as_u32();
+    if param.is_local() {
+        // Local codes are meaningless if local tables are not used.
+        if param.local_ver == 255 {
+            return None;
+        }
+        LOCAL
+            .iter()
+            .find(|(centre, c, _)| *centre == param.centre && *c == code)
+            .map(|(_, _, canonical)| *canonical)
+    } else {
+        WMO.iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, canonical)| *canonical)
+    }
+}
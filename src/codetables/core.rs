@@ -1,6 +1,7 @@
 use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Code<Enum, N> {
     Name(Enum),
     Num(N),
@@ -1,7 +1,43 @@
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
+#[derive(Debug, Clone, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Table1_3 {
+    OperationalProducts = 0,
+    OperationalTestProducts,
+    ResearchProducts,
+    ReAnalysisProducts,
+    Tigge,
+    TiggeTest,
+    S2sOperationalProducts,
+    S2sTestProducts,
+    Uerra,
+    UerraTest,
+    CopernicusRegionalReanalysis,
+    CopernicusRegionalReanalysisTest,
+    Missing = 255,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Table1_4 {
+    AnalysisProducts = 0,
+    ForecastProducts,
+    AnalysisAndForecastProducts,
+    ControlForecastProducts,
+    PerturbedForecastProducts,
+    ControlAndPerturbedForecastProducts,
+    ProcessedSatelliteObservations,
+    ProcessedRadarObservations,
+    EventProbability,
+    Missing = 255,
+}
+
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Table4_4 {
     Minute = 0,
     Hour,
@@ -36,10 +72,26 @@ impl Table4_4 {
             Self::Missing => None,
         }
     }
+
+    /// Returns the length of the unit in seconds, or `None` if the length is
+    /// not constant (e.g. months and years) or the unit is missing.
+    pub fn seconds(&self) -> Option<u32> {
+        match self {
+            Self::Minute => Some(60),
+            Self::Hour => Some(3600),
+            Self::Day => Some(86400),
+            Self::ThreeHours => Some(10800),
+            Self::SixHours => Some(21600),
+            Self::TwelveHours => Some(43200),
+            Self::Second => Some(1),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Table5_6 {
     FirstOrderSpatialDifferencing = 1,
     SecondOrderSpatialDifferencing,
@@ -63,6 +115,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unit_length_in_seconds() {
+        assert_eq!(Table4_4::Hour.seconds(), Some(3600));
+        assert_eq!(Table4_4::SixHours.seconds(), Some(21600));
+        assert_eq!(Table4_4::Month.seconds(), None);
+        assert_eq!(Table4_4::Missing.seconds(), None);
+    }
+
     #[test]
     fn num_lookup_result_conversion() {
         assert_eq!(Code::from(Table4_4::try_from(1u8)), Name(Table4_4::Hour));
@@ -45,6 +45,26 @@ impl ArrayLookup for CommonCodeTable00 {
     }
 }
 
+/// Returns the date on which the given version of the GRIB master tables was
+/// implemented, as described in Common Code Table C-0.
+///
+/// `None` is returned for the experimental version 0 and for versions not
+/// known to the library.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(grib::codetables::master_table_date(5), Some("4 November 2009"));
+/// assert_eq!(grib::codetables::master_table_date(0), None);
+/// assert_eq!(grib::codetables::master_table_date(255), None);
+/// ```
+pub fn master_table_date(version: u8) -> Option<&'static str> {
+    match version {
+        0 => None,
+        v => COMMON_CODE_TABLE_00.get(usize::from(v)).copied(),
+    }
+}
+
 pub struct CommonCodeTable11;
 
 impl ArrayLookup for CommonCodeTable11 {
@@ -146,65 +166,74 @@ impl CodeTable4_2 {
             parameter,
         }
     }
+
+    /// Looks up the unit of the parameter `code`, as given in the WMO table.
+    pub fn unit(&self, code: usize) -> Option<&'static str> {
+        self.tables().1.get(code).copied().filter(|s| !s.is_empty())
+    }
+
+    fn tables(&self) -> (&'static [&'static str], &'static [&'static str]) {
+        match (self.discipline, self.parameter) {
+            (0, 0) => (CODE_TABLE_4_2_0_0, CODE_TABLE_4_2_0_0_UNITS),
+            (0, 1) => (CODE_TABLE_4_2_0_1, CODE_TABLE_4_2_0_1_UNITS),
+            (0, 2) => (CODE_TABLE_4_2_0_2, CODE_TABLE_4_2_0_2_UNITS),
+            (0, 3) => (CODE_TABLE_4_2_0_3, CODE_TABLE_4_2_0_3_UNITS),
+            (0, 4) => (CODE_TABLE_4_2_0_4, CODE_TABLE_4_2_0_4_UNITS),
+            (0, 5) => (CODE_TABLE_4_2_0_5, CODE_TABLE_4_2_0_5_UNITS),
+            (0, 6) => (CODE_TABLE_4_2_0_6, CODE_TABLE_4_2_0_6_UNITS),
+            (0, 7) => (CODE_TABLE_4_2_0_7, CODE_TABLE_4_2_0_7_UNITS),
+            (0, 13) => (CODE_TABLE_4_2_0_13, CODE_TABLE_4_2_0_13_UNITS),
+            (0, 14) => (CODE_TABLE_4_2_0_14, CODE_TABLE_4_2_0_14_UNITS),
+            (0, 15) => (CODE_TABLE_4_2_0_15, CODE_TABLE_4_2_0_15_UNITS),
+            (0, 16) => (CODE_TABLE_4_2_0_16, CODE_TABLE_4_2_0_16_UNITS),
+            (0, 17) => (CODE_TABLE_4_2_0_17, CODE_TABLE_4_2_0_17_UNITS),
+            (0, 18) => (CODE_TABLE_4_2_0_18, CODE_TABLE_4_2_0_18_UNITS),
+            (0, 19) => (CODE_TABLE_4_2_0_19, CODE_TABLE_4_2_0_19_UNITS),
+            (0, 20) => (CODE_TABLE_4_2_0_20, CODE_TABLE_4_2_0_20_UNITS),
+            (0, 190) => (CODE_TABLE_4_2_0_190, CODE_TABLE_4_2_0_190_UNITS),
+            (0, 191) => (CODE_TABLE_4_2_0_191, CODE_TABLE_4_2_0_191_UNITS),
+            (1, 0) => (CODE_TABLE_4_2_1_0, CODE_TABLE_4_2_1_0_UNITS),
+            (1, 1) => (CODE_TABLE_4_2_1_1, CODE_TABLE_4_2_1_1_UNITS),
+            (1, 2) => (CODE_TABLE_4_2_1_2, CODE_TABLE_4_2_1_2_UNITS),
+            (2, 0) => (CODE_TABLE_4_2_2_0, CODE_TABLE_4_2_2_0_UNITS),
+            (2, 3) => (CODE_TABLE_4_2_2_3, CODE_TABLE_4_2_2_3_UNITS),
+            (2, 4) => (CODE_TABLE_4_2_2_4, CODE_TABLE_4_2_2_4_UNITS),
+            (2, 5) => (CODE_TABLE_4_2_2_5, CODE_TABLE_4_2_2_5_UNITS),
+            (3, 0) => (CODE_TABLE_4_2_3_0, CODE_TABLE_4_2_3_0_UNITS),
+            (3, 1) => (CODE_TABLE_4_2_3_1, CODE_TABLE_4_2_3_1_UNITS),
+            (3, 2) => (CODE_TABLE_4_2_3_2, CODE_TABLE_4_2_3_2_UNITS),
+            (3, 3) => (CODE_TABLE_4_2_3_3, CODE_TABLE_4_2_3_3_UNITS),
+            (3, 4) => (CODE_TABLE_4_2_3_4, CODE_TABLE_4_2_3_4_UNITS),
+            (3, 5) => (CODE_TABLE_4_2_3_5, CODE_TABLE_4_2_3_5_UNITS),
+            (3, 6) => (CODE_TABLE_4_2_3_6, CODE_TABLE_4_2_3_6_UNITS),
+            (4, 0) => (CODE_TABLE_4_2_4_0, CODE_TABLE_4_2_4_0_UNITS),
+            (4, 1) => (CODE_TABLE_4_2_4_1, CODE_TABLE_4_2_4_1_UNITS),
+            (4, 2) => (CODE_TABLE_4_2_4_2, CODE_TABLE_4_2_4_2_UNITS),
+            (4, 3) => (CODE_TABLE_4_2_4_3, CODE_TABLE_4_2_4_3_UNITS),
+            (4, 4) => (CODE_TABLE_4_2_4_4, CODE_TABLE_4_2_4_4_UNITS),
+            (4, 5) => (CODE_TABLE_4_2_4_5, CODE_TABLE_4_2_4_5_UNITS),
+            (4, 6) => (CODE_TABLE_4_2_4_6, CODE_TABLE_4_2_4_6_UNITS),
+            (4, 7) => (CODE_TABLE_4_2_4_7, CODE_TABLE_4_2_4_7_UNITS),
+            (4, 8) => (CODE_TABLE_4_2_4_8, CODE_TABLE_4_2_4_8_UNITS),
+            (4, 9) => (CODE_TABLE_4_2_4_9, CODE_TABLE_4_2_4_9_UNITS),
+            (4, 10) => (CODE_TABLE_4_2_4_10, CODE_TABLE_4_2_4_10_UNITS),
+            (10, 0) => (CODE_TABLE_4_2_10_0, CODE_TABLE_4_2_10_0_UNITS),
+            (10, 1) => (CODE_TABLE_4_2_10_1, CODE_TABLE_4_2_10_1_UNITS),
+            (10, 2) => (CODE_TABLE_4_2_10_2, CODE_TABLE_4_2_10_2_UNITS),
+            (10, 3) => (CODE_TABLE_4_2_10_3, CODE_TABLE_4_2_10_3_UNITS),
+            (10, 4) => (CODE_TABLE_4_2_10_4, CODE_TABLE_4_2_10_4_UNITS),
+            (10, 191) => (CODE_TABLE_4_2_10_191, CODE_TABLE_4_2_10_191_UNITS),
+            (20, 0) => (CODE_TABLE_4_2_20_0, CODE_TABLE_4_2_20_0_UNITS),
+            (20, 1) => (CODE_TABLE_4_2_20_1, CODE_TABLE_4_2_20_1_UNITS),
+            (20, 2) => (CODE_TABLE_4_2_20_2, CODE_TABLE_4_2_20_2_UNITS),
+            _ => (CODE_TABLE_UNSUPPORTED, CODE_TABLE_UNSUPPORTED),
+        }
+    }
 }
 
 impl ArrayLookup for CodeTable4_2 {
     fn data(&self) -> &'static [&'static str] {
-        match (self.discipline, self.parameter) {
-            (0, 0) => CODE_TABLE_4_2_0_0,
-            (0, 1) => CODE_TABLE_4_2_0_1,
-            (0, 2) => CODE_TABLE_4_2_0_2,
-            (0, 3) => CODE_TABLE_4_2_0_3,
-            (0, 4) => CODE_TABLE_4_2_0_4,
-            (0, 5) => CODE_TABLE_4_2_0_5,
-            (0, 6) => CODE_TABLE_4_2_0_6,
-            (0, 7) => CODE_TABLE_4_2_0_7,
-            (0, 13) => CODE_TABLE_4_2_0_13,
-            (0, 14) => CODE_TABLE_4_2_0_14,
-            (0, 15) => CODE_TABLE_4_2_0_15,
-            (0, 16) => CODE_TABLE_4_2_0_16,
-            (0, 17) => CODE_TABLE_4_2_0_17,
-            (0, 18) => CODE_TABLE_4_2_0_18,
-            (0, 19) => CODE_TABLE_4_2_0_19,
-            (0, 20) => CODE_TABLE_4_2_0_20,
-            (0, 190) => CODE_TABLE_4_2_0_190,
-            (0, 191) => CODE_TABLE_4_2_0_191,
-            (1, 0) => CODE_TABLE_4_2_1_0,
-            (1, 1) => CODE_TABLE_4_2_1_1,
-            (1, 2) => CODE_TABLE_4_2_1_2,
-            (2, 0) => CODE_TABLE_4_2_2_0,
-            (2, 3) => CODE_TABLE_4_2_2_3,
-            (2, 4) => CODE_TABLE_4_2_2_4,
-            (2, 5) => CODE_TABLE_4_2_2_5,
-            (3, 0) => CODE_TABLE_4_2_3_0,
-            (3, 1) => CODE_TABLE_4_2_3_1,
-            (3, 2) => CODE_TABLE_4_2_3_2,
-            (3, 3) => CODE_TABLE_4_2_3_3,
-            (3, 4) => CODE_TABLE_4_2_3_4,
-            (3, 5) => CODE_TABLE_4_2_3_5,
-            (3, 6) => CODE_TABLE_4_2_3_6,
-            (4, 0) => CODE_TABLE_4_2_4_0,
-            (4, 1) => CODE_TABLE_4_2_4_1,
-            (4, 2) => CODE_TABLE_4_2_4_2,
-            (4, 3) => CODE_TABLE_4_2_4_3,
-            (4, 4) => CODE_TABLE_4_2_4_4,
-            (4, 5) => CODE_TABLE_4_2_4_5,
-            (4, 6) => CODE_TABLE_4_2_4_6,
-            (4, 7) => CODE_TABLE_4_2_4_7,
-            (4, 8) => CODE_TABLE_4_2_4_8,
-            (4, 9) => CODE_TABLE_4_2_4_9,
-            (4, 10) => CODE_TABLE_4_2_4_10,
-            (10, 0) => CODE_TABLE_4_2_10_0,
-            (10, 1) => CODE_TABLE_4_2_10_1,
-            (10, 2) => CODE_TABLE_4_2_10_2,
-            (10, 3) => CODE_TABLE_4_2_10_3,
-            (10, 4) => CODE_TABLE_4_2_10_4,
-            (10, 191) => CODE_TABLE_4_2_10_191,
-            (20, 0) => CODE_TABLE_4_2_20_0,
-            (20, 1) => CODE_TABLE_4_2_20_1,
-            (20, 2) => CODE_TABLE_4_2_20_2,
-            _ => CODE_TABLE_UNSUPPORTED,
-        }
+        self.tables().0
     }
 }
 
@@ -241,6 +270,11 @@ impl ArrayLookup for CodeTable5_0 {
 }
 
 pub trait Lookup {
+    /// Looks up the code in the table.
+    ///
+    /// Codes not defined in the table, including reserved entries without
+    /// descriptions, are uniformly treated as unimplemented and displayed as
+    /// "code 'N' is not implemented".
     fn lookup(&self, code: usize) -> LookupResult;
 }
 
@@ -253,6 +287,7 @@ impl<T: ArrayLookup> Lookup for T {
         let result = self
             .data()
             .get(code)
+            .filter(|s| !s.is_empty())
             .ok_or(ConversionError::Unimplemented(code));
         LookupResult(result)
     }
@@ -265,3 +300,43 @@ pub(crate) const SUPPORTED_PROD_DEF_TEMPLATE_NUMBERS: [u16; 71] = [
     43, 44, 45, 46, 47, 48, 49, 51, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 67, 68, 70, 71, 72,
     73, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 91, 254, 1000, 1001, 1002, 1100, 1101,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_lookup_of_unknown_code {
+        ($(($name:ident, $table:expr, $code:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let result = $table.lookup($code);
+                assert_eq!(result.description(), None);
+                assert_eq!(
+                    result.to_string(),
+                    format!("code '{}' is not implemented", $code)
+                );
+            }
+        )*);
+    }
+
+    test_lookup_of_unknown_code! {
+        (lookup_of_reserved_code_in_table_4_1, CodeTable4_1::new(0), 21),
+        (lookup_of_code_beyond_table_4_1, CodeTable4_1::new(0), 192),
+        (lookup_of_code_in_unsupported_table_4_1, CodeTable4_1::new(255), 0),
+        (lookup_of_code_beyond_table_4_2, CodeTable4_2::new(0, 0), 192),
+        (lookup_of_code_in_unsupported_table_4_2, CodeTable4_2::new(0, 255), 0),
+        (lookup_of_code_beyond_table_4_3, CodeTable4_3, 192),
+        (lookup_of_reserved_code_in_table_4_5, CodeTable4_5, 0),
+        (lookup_of_code_beyond_table_4_5, CodeTable4_5, 255),
+    }
+
+    #[test]
+    fn lookup_of_known_code() {
+        let result = CodeTable4_5.lookup(1);
+        assert_eq!(
+            result.description(),
+            Some("Ground or water surface".to_owned())
+        );
+        assert_eq!(result.to_string(), "Ground or water surface");
+    }
+}
@@ -1,20 +1,26 @@
 use std::{
-    cell::{RefCell, RefMut},
-    collections::HashSet,
+    cell::{OnceCell, RefCell, RefMut},
+    collections::{HashMap, HashSet},
     fmt::{self, Display, Formatter},
-    io::{Cursor, Read, Seek},
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    rc::Rc,
 };
 
+#[cfg(jpeg2000)]
+use crate::PackingKind;
 use crate::{
     codetables::{
         CodeTable3_1, CodeTable4_0, CodeTable4_1, CodeTable4_2, CodeTable4_3, CodeTable5_0, Lookup,
     },
     datatypes::*,
     error::*,
-    grid::GridPointIterator,
+    grid::{resample_nearest, GridPointIterator, LatLonGridDefinition},
     parser::Grib2SubmessageIndexStream,
-    reader::{Grib2Read, Grib2SectionStream, SeekableGrib2Reader, SECT8_ES_SIZE},
-    GridPointIndexIterator,
+    reader::{
+        Grib2Read, Grib2SectionStream, ReadOptions, SeekableGrib2Reader, SECT0_IS_MAGIC,
+        SECT0_IS_SIZE, SECT8_ES_SIZE,
+    },
+    DecodeError, Grib2SubmessageDecoder, GridPointIndexIterator,
 };
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -65,6 +71,7 @@ impl SectionBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TemplateInfo(pub u8, pub u16);
 
 impl TemplateInfo {
@@ -108,6 +115,42 @@ pub fn from_reader<SR: Read + Seek>(
     Grib2::<SeekableGrib2Reader<SR>>::read_with_seekable(reader)
 }
 
+/// Reads a [`Grib2`] instance from an I/O stream of GRIB2 with options.
+///
+/// [`from_reader`] is the same as this function with the default options,
+/// under which reading is lenient. See [`ReadOptions`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use grib::{GribError, ParseError, ReadOptions};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut buf = std::fs::read(
+///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+///     )?;
+///     let len = buf.len();
+///     buf[len - 1] = b'0';
+///
+///     let options = ReadOptions::default().strict(true);
+///     let result = grib::from_reader_with_options(std::io::Cursor::new(&buf), &options);
+///     assert!(matches!(
+///         result,
+///         Err(GribError::ParseError(ParseError::EndSectionMismatch))
+///     ));
+///
+///     let grib2 = grib::from_reader(std::io::Cursor::new(&buf))?;
+///     assert_eq!(grib2.len(), 1);
+///     Ok(())
+/// }
+/// ```
+pub fn from_reader_with_options<SR: Read + Seek>(
+    reader: SR,
+    options: &ReadOptions,
+) -> Result<Grib2<SeekableGrib2Reader<SR>>, GribError> {
+    Grib2::read_with_options(SeekableGrib2Reader::new(reader), options)
+}
+
 /// Reads a [`Grib2`] instance from bytes of GRIB2.
 ///
 /// # Examples
@@ -135,12 +178,196 @@ pub fn from_slice(bytes: &[u8]) -> Result<Grib2<SeekableGrib2Reader<Cursor<&[u8]
     Grib2::<SeekableGrib2Reader<Cursor<&[u8]>>>::read_with_seekable(reader)
 }
 
+/// Reads GRIB2 data message by message from a reader, which does not need to
+/// be seekable, and returns an iterator over submessages in the data.
+///
+/// Unlike [`from_reader`], which indexes the whole data before returning,
+/// this yields submessages in a message as soon as the message is read, so
+/// the first submessage is available without reading the rest of the data.
+/// Each item owns the bytes of the message containing it.
+///
+/// Since the beginning of each message is searched byte by byte, it is
+/// recommended to wrap unbuffered readers in [`std::io::BufReader`].
+///
+/// # Examples
+///
+/// ```
+/// use std::io::BufReader;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let f = std::fs::File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+///     let f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+///
+///     let mut stream = grib::stream_submessages(f);
+///     let mut first = stream.next().ok_or("first submessage is not found")??;
+///     assert_eq!(first.index(), (0, 0));
+///     assert_eq!(first.submessage().grid_shape()?, (1440, 721));
+///     assert_eq!(stream.count(), 9);
+///     Ok(())
+/// }
+/// ```
+pub fn stream_submessages<R: Read>(reader: R) -> SubmessageStream<R> {
+    SubmessageStream {
+        reader,
+        num_messages: 0,
+        current: None,
+        finished: false,
+    }
+}
+
+type OwnedMessage = Grib2<SeekableGrib2Reader<Cursor<Rc<[u8]>>>>;
+
+/// An iterator over submessages returned by [`stream_submessages`].
+pub struct SubmessageStream<R> {
+    reader: R,
+    num_messages: usize,
+    // the message being iterated, its bytes and the index of the next
+    // submessage in it
+    current: Option<(OwnedMessage, Rc<[u8]>, usize)>,
+    finished: bool,
+}
+
+impl<R: Read> SubmessageStream<R> {
+    // Reads the next message as bytes, skipping any data before Section 0.
+    fn read_message(&mut self) -> Result<Option<Vec<u8>>, GribError> {
+        let mut matched = 0;
+        while matched < SECT0_IS_MAGIC.len() {
+            let mut byte = [0];
+            match self.reader.read_exact(&mut byte) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(ParseError::from(e).into()),
+            }
+            matched = if byte[0] == SECT0_IS_MAGIC[matched] {
+                matched + 1
+            } else if byte[0] == SECT0_IS_MAGIC[0] {
+                1
+            } else {
+                0
+            };
+        }
+
+        let mut sect0 = [0; SECT0_IS_SIZE];
+        sect0[..SECT0_IS_MAGIC.len()].copy_from_slice(SECT0_IS_MAGIC);
+        self.reader
+            .read_exact(&mut sect0[SECT0_IS_MAGIC.len()..])
+            .map_err(ParseError::from)?;
+        let indicator = Indicator::from_slice(&sect0)?;
+        let total_length = usize::try_from(indicator.total_length)
+            .ok()
+            .filter(|len| *len >= SECT0_IS_SIZE)
+            .ok_or_else(|| {
+                GribError::InvalidValueError(format!(
+                    "total length of message: {}",
+                    indicator.total_length
+                ))
+            })?;
+
+        // The buffer grows only as bytes are actually read so that a corrupted
+        // total length does not cause a huge allocation.
+        let mut buf = sect0.to_vec();
+        (&mut self.reader)
+            .take((total_length - SECT0_IS_SIZE) as u64)
+            .read_to_end(&mut buf)
+            .map_err(ParseError::from)?;
+        if buf.len() != total_length {
+            return Err(ParseError::ReadError(format!(
+                "message {} is truncated: {} of {total_length} bytes read",
+                self.num_messages,
+                buf.len()
+            ))
+            .into());
+        }
+        Ok(Some(buf))
+    }
+
+    fn next_submessage(&mut self) -> Result<Option<OwnedSubMessage>, GribError> {
+        loop {
+            if let Some((message, bytes, next)) = &mut self.current {
+                if *next < message.len() {
+                    let reader = SeekableGrib2Reader::new(Cursor::new(Rc::clone(bytes)));
+                    let item = OwnedSubMessage {
+                        index: (self.num_messages - 1, *next),
+                        message: message.with_reader(reader),
+                    };
+                    *next += 1;
+                    return Ok(Some(item));
+                }
+            }
+
+            let Some(bytes) = self.read_message()? else {
+                return Ok(None);
+            };
+            let bytes = Rc::<[u8]>::from(bytes);
+            let message = from_reader(Cursor::new(Rc::clone(&bytes)))?;
+            self.current = Some((message, bytes, 0));
+            self.num_messages += 1;
+        }
+    }
+}
+
+impl<R: Read> Iterator for SubmessageStream<R> {
+    type Item = Result<OwnedSubMessage, GribError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_submessage().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+        result
+    }
+}
+
+/// A submessage yielded by [`stream_submessages`], which owns the bytes of
+/// the message containing it.
+///
+/// Submessages in the same message share the bytes, but each of them has its
+/// own reader and its own cache of decoded values. Unlike submessages from
+/// [`Grib2::iter`], they can therefore be kept and used at the same time.
+pub struct OwnedSubMessage {
+    index: MessageIndex,
+    message: OwnedMessage,
+}
+
+impl OwnedSubMessage {
+    /// Returns the index of the submessage in the whole data.
+    pub fn index(&self) -> MessageIndex {
+        self.index
+    }
+
+    /// Returns the submessage, which provides access to its sections and
+    /// values.
+    ///
+    /// Note that offsets available from the returned submessage are counted
+    /// from the beginning of the message and not from the beginning of the
+    /// whole data, and that its message index is always 0. Use
+    /// [`index`](Self::index) to get the index in the whole data.
+    ///
+    /// The returned submessage borrows the reader of `self` mutably, which is
+    /// why this method takes `&mut self`.
+    pub fn submessage(&mut self) -> SubMessage<'_, SeekableGrib2Reader<Cursor<Rc<[u8]>>>> {
+        let (_, submessage) = self
+            .message
+            .iter()
+            .nth(self.index.1)
+            .expect("submessage index should be within the message");
+        submessage
+    }
+}
+
 pub struct Grib2<R> {
     reader: RefCell<R>,
     sections: Box<[SectionInfo]>,
     submessages: Vec<Grib2SubmessageIndex>,
+    decoded_values: Box<[OnceCell<Box<[f32]>>]>,
+    grid_latlons: Box<[OnceCell<LatLons>]>,
 }
 
+type LatLons = Box<[(f32, f32)]>;
+
 impl<R> Grib2<R> {
     /// Returns the length of submessages in the data.
     ///
@@ -162,6 +389,38 @@ impl<R> Grib2<R> {
         self.submessages.len()
     }
 
+    /// Returns the total number of grid points over all submessages in the
+    /// data, i.e. the number of values obtained by decoding all submessages.
+    ///
+    /// This is computed from Section 3 of each submessage without decoding, and
+    /// can be used to report progress of decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     assert_eq!(grib2.total_points(), 7 * 86016);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn total_points(&self) -> usize {
+        self.submessages
+            .iter()
+            .filter_map(
+                |submessage| match self.sections[submessage.3].body.as_ref() {
+                    Some(SectionBody::Section3(grid_def)) => Some(grid_def.num_points() as usize),
+                    _ => None,
+                },
+            )
+            .sum()
+    }
+
     /// Returns `true` if `self` has zero submessages.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -205,6 +464,228 @@ impl<R> Grib2<R> {
         self.into_iter()
     }
 
+    /// Returns an iterator over submessages in the data for which `pred`
+    /// returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let indices = grib2
+    ///         .iter_filtered(|submessage| {
+    ///             submessage
+    ///                 .prod_def()
+    ///                 .forecast_time()
+    ///                 .is_some_and(|ft| ft.value > 0)
+    ///         })
+    ///         .map(|(index, _)| index)
+    ///         .collect::<Vec<_>>();
+    ///     assert_eq!(
+    ///         indices,
+    ///         vec![(0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (0, 6)]
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn iter_filtered<F>(&self, pred: F) -> impl Iterator<Item = (MessageIndex, SubMessage<R>)>
+    where
+        F: Fn(&SubMessage<R>) -> bool,
+    {
+        self.iter().filter(move |(_, submessage)| pred(submessage))
+    }
+
+    /// Returns the first submessage in the data for which `pred` returns
+    /// `true`, or `None` if there is no such submessage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let mut f = xz2::bufread::XzDecoder::new(f);
+    ///     let mut buf = Vec::new();
+    ///     f.read_to_end(&mut buf)?;
+    ///     let grib2 = grib::from_slice(&buf)?;
+    ///
+    ///     let (index, submessage) = grib2
+    ///         .find(|submessage| {
+    ///             submessage
+    ///                 .parameter()
+    ///                 .and_then(|param| param.description())
+    ///                 .is_some_and(|desc| desc == "Total precipitation rate")
+    ///         })
+    ///         .ok_or("precipitation rate not found")?;
+    ///     assert_eq!(index, (0, 1));
+    ///     assert_eq!(submessage.prod_def().parameter_number(), Some(52));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find<F>(&self, pred: F) -> Option<(MessageIndex, SubMessage<R>)>
+    where
+        F: Fn(&SubMessage<R>) -> bool,
+    {
+        self.iter().find(|(_, submessage)| pred(submessage))
+    }
+
+    /// Groups indices of submessages by their parameters.
+    ///
+    /// Indices in each group are in the order of submessages in the data.
+    /// Submessages whose parameters are not available are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let groups = grib2.group_by_parameter();
+    ///     assert_eq!(groups.len(), 1);
+    ///     assert_eq!(groups.values().next().map(Vec::len), Some(7));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn group_by_parameter(&self) -> HashMap<Parameter, Vec<MessageIndex>> {
+        let mut groups = HashMap::<_, Vec<_>>::new();
+        for (index, submessage) in self.iter() {
+            if let Some(param) = submessage.parameter() {
+                groups.entry(param).or_default().push(index);
+            }
+        }
+        groups
+    }
+
+    /// Collects forecast times and indices of submessages with the parameter
+    /// `param` and the first fixed surface `level`, sorted by forecast time.
+    ///
+    /// Forecast times are compared after conversion to a common unit. Those
+    /// with units whose length is not constant, such as months, are placed
+    /// at the end in order of their values.
+    pub fn time_series(
+        &self,
+        param: &Parameter,
+        level: &FixedSurface,
+    ) -> Vec<(ForecastTime, MessageIndex)> {
+        let mut series = self
+            .iter()
+            .filter_map(|(index, submessage)| {
+                if submessage.parameter().as_ref() != Some(param) {
+                    return None;
+                }
+                let prod_def = submessage.prod_def();
+                let (first_surface, _) = prod_def.fixed_surfaces()?;
+                if &first_surface != level {
+                    return None;
+                }
+                let forecast_time = prod_def.forecast_time()?;
+                Some((forecast_time, index))
+            })
+            .collect::<Vec<_>>();
+        series.sort_by_key(|(forecast_time, _)| {
+            let seconds = forecast_time.seconds();
+            (seconds.is_none(), seconds, forecast_time.value)
+        });
+        series
+    }
+
+    /// Returns latitudes and longitudes of grid points defined in Section 3
+    /// starting at `section3_offset`, i.e. `submessage.3.body.offset` of a
+    /// submessage.
+    ///
+    /// Coordinates are identical across submessages sharing the same Section
+    /// 3, so they are computed only at the first call for each Section 3 and
+    /// cached in the [`Grib2`] instance. The order of items is the same as
+    /// that of [`SubMessage::latlons`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+    ///     let offset = submessage.3.body.offset;
+    ///     drop(submessage);
+    ///
+    ///     let latlons = grib2.latlons_for_grid(offset)?;
+    ///     assert_eq!(latlons.len(), 86016);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn latlons_for_grid(&self, section3_offset: usize) -> Result<&[(f32, f32)], GribError> {
+        let pos = self
+            .sections
+            .binary_search_by_key(&section3_offset, |sect| sect.offset)
+            .ok()
+            .filter(|pos| self.sections[*pos].num == 3)
+            .ok_or_else(|| {
+                GribError::InvalidValueError(format!(
+                    "Section 3 not found at offset {section3_offset}"
+                ))
+            })?;
+        let cache = &self.grid_latlons[pos];
+        if let Some(latlons) = cache.get() {
+            return Ok(latlons);
+        }
+        let grid_def = match self.sections[pos].body.as_ref() {
+            Some(SectionBody::Section3(grid_def)) => grid_def,
+            _ => return Err(GribError::InternalDataError),
+        };
+        let latlons = grid_latlons(grid_def)?.collect::<Box<[_]>>();
+        Ok(cache.get_or_init(|| latlons))
+    }
+
+    /// Drops decoded values cached by [`SubMessage::values`] and coordinates
+    /// cached by [`latlons_for_grid`](Self::latlons_for_grid), releasing the
+    /// memory held for them.
+    ///
+    /// Cached items are otherwise kept as long as the [`Grib2`] instance
+    /// lives. Values and coordinates are computed again when they are
+    /// requested after clearing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let mut grib2 = grib::from_reader(f)?;
+    ///     for (_, mut submessage) in grib2.iter() {
+    ///         let _values = submessage.values()?;
+    ///     }
+    ///
+    ///     grib2.clear_cache();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn clear_cache(&mut self) {
+        self.decoded_values.iter_mut().for_each(|cache| {
+            cache.take();
+        });
+        self.grid_latlons.iter_mut().for_each(|cache| {
+            cache.take();
+        });
+    }
+
     /// Returns an iterator over sections in the data.
     ///
     /// # Examples
@@ -236,15 +717,99 @@ impl<R> Grib2<R> {
 
 impl<R: Grib2Read> Grib2<R> {
     pub fn read(r: R) -> Result<Self, GribError> {
-        let mut sect_stream = Grib2SectionStream::new(r);
+        Self::read_with_progress(r, |_| {})
+    }
+
+    /// Reads and indexes the data with options. See [`ReadOptions`] for
+    /// details.
+    pub fn read_with_options(r: R, options: &ReadOptions) -> Result<Self, GribError> {
+        Self::read_sections(Grib2SectionStream::new(r).with_options(options), |_| {})
+    }
+
+    /// Reads and indexes the data, calling `callback` with the byte offset of
+    /// the end of each message after the message is read.
+    ///
+    /// This is useful to report progress of indexing large files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{BufReader, Read};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+    ///     let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+    ///     let mut buf = Vec::new();
+    ///     f.read_to_end(&mut buf)?;
+    ///
+    ///     let mut offsets = Vec::new();
+    ///     let reader = grib::SeekableGrib2Reader::new(std::io::Cursor::new(&buf));
+    ///     let _grib2 = grib::Grib2::read_with_progress(reader, |offset| offsets.push(offset))?;
+    ///     assert_eq!(offsets.last(), Some(&buf.len()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_with_progress<F>(r: R, callback: F) -> Result<Self, GribError>
+    where
+        F: FnMut(usize),
+    {
+        Self::read_sections(Grib2SectionStream::new(r), callback)
+    }
+
+    fn read_sections<F>(
+        mut sect_stream: Grib2SectionStream<R>,
+        mut callback: F,
+    ) -> Result<Self, GribError>
+    where
+        F: FnMut(usize),
+    {
+        let sects = sect_stream.by_ref().inspect(|sect| {
+            if let Ok(SectionInfo { num: 8, offset, .. }) = sect {
+                callback(offset + SECT8_ES_SIZE);
+            }
+        });
+        let (sections, submessages) = Self::index_sections(sects)?;
+        Ok(Self::from_parts(
+            sect_stream.into_reader(),
+            sections,
+            submessages,
+        ))
+    }
+
+    // Validates the sequence of sections and returns the sections with the
+    // indices of submessages.
+    pub(crate) fn index_sections<I>(
+        sects: I,
+    ) -> Result<(Vec<SectionInfo>, Vec<Grib2SubmessageIndex>), GribError>
+    where
+        I: Iterator<Item = Result<SectionInfo, ParseError>>,
+    {
         let mut cacher = Vec::new();
-        let parser = Grib2SubmessageIndexStream::new(sect_stream.by_ref()).with_cacher(&mut cacher);
+        let parser = Grib2SubmessageIndexStream::new(sects).with_cacher(&mut cacher);
         let submessages = parser.collect::<Result<Vec<_>, _>>()?;
-        Ok(Self {
-            reader: RefCell::new(sect_stream.into_reader()),
-            sections: cacher.into_boxed_slice(),
+        Ok((cacher, submessages))
+    }
+
+    // Returns a context with the same sections read from `reader`, which has its
+    // own cache of decoded values.
+    fn with_reader<S: Grib2Read>(&self, reader: S) -> Grib2<S> {
+        Grib2::from_parts(reader, self.sections.to_vec(), self.submessages.clone())
+    }
+
+    pub(crate) fn from_parts(
+        reader: R,
+        sections: Vec<SectionInfo>,
+        submessages: Vec<Grib2SubmessageIndex>,
+    ) -> Self {
+        let decoded_values = submessages.iter().map(|_| OnceCell::new()).collect();
+        let grid_latlons = sections.iter().map(|_| OnceCell::new()).collect();
+        Self {
+            reader: RefCell::new(reader),
+            sections: sections.into_boxed_slice(),
             submessages,
-        })
+            decoded_values,
+            grid_latlons,
+        }
     }
 
     pub fn read_with_seekable<SR: Read + Seek>(
@@ -254,9 +819,97 @@ impl<R: Grib2Read> Grib2<R> {
         Grib2::<SeekableGrib2Reader<SR>>::read(r)
     }
 
+    /// Returns the current position of the underlying reader from the start
+    /// of the data.
+    ///
+    /// This is provided for diagnostic purposes. Since section payloads are
+    /// always read after seeking to their absolute offsets, the position left
+    /// by previous operations does not affect subsequent reads.
+    ///
+    /// An error is returned if the reader is in use by a [`SubMessage`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let (_index, mut submessage) = grib2.iter().next().unwrap();
+    ///     let _ = submessage.values()?;
+    ///     drop(submessage);
+    ///     assert!(grib2.reader_position()? > 0);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn reader_position(&self) -> Result<u64, GribError> {
+        let mut reader = self.reader.try_borrow_mut().map_err(|_| {
+            GribError::InvalidValueError("reader is in use by a submessage".to_owned())
+        })?;
+        let pos = reader
+            .stream_position()
+            .map_err(|e| GribError::ParseError(ParseError::from(e)))?;
+        Ok(pos)
+    }
+
     pub fn list_templates(&self) -> Vec<TemplateInfo> {
         get_templates(&self.sections)
     }
+
+    /// Reads and returns the raw bytes of the message at `message_index`,
+    /// from the beginning of Section 0 to the end of Section 8.
+    ///
+    /// The returned bytes form a standalone GRIB2 message, which can be read
+    /// again with [`from_slice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{
+    ///     fs::File,
+    ///     io::{BufReader, Read},
+    /// };
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut buf = Vec::new();
+    ///
+    ///     let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+    ///     let f = BufReader::new(f);
+    ///     let mut f = xz2::bufread::XzDecoder::new(f);
+    ///     f.read_to_end(&mut buf)?;
+    ///
+    ///     let grib2 = grib::from_slice(&buf)?;
+    ///     let message = grib2.extract_message(1)?;
+    ///
+    ///     let extracted = grib::from_slice(&message)?;
+    ///     assert_eq!(extracted.len(), 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn extract_message(&self, message_index: usize) -> Result<Vec<u8>, GribError> {
+        let last_submessage = self
+            .submessages
+            .iter()
+            .rev()
+            .find(|submessage| submessage.message_index().0 == message_index)
+            .ok_or_else(|| {
+                GribError::InvalidValueError(format!("message {message_index} not found"))
+            })?;
+        let sect0 = &self.sections[last_submessage.0];
+        let sect8 = &self.sections[last_submessage.8];
+        let start = sect0.offset;
+        let end = sect8.offset + sect8.size;
+
+        let mut reader = self.reader.borrow_mut();
+        reader
+            .seek(SeekFrom::Start(start as u64))
+            .map_err(ParseError::from)?;
+        let buf = reader.read_slice_without_offset_check(end - start)?;
+        Ok(buf.into_vec())
+    }
 }
 
 impl<'a, R: 'a> IntoIterator for &'a Grib2<R> {
@@ -285,11 +938,16 @@ fn get_templates(sects: &[SectionInfo]) -> Vec<TemplateInfo> {
 pub struct SubmessageIterator<'a, R> {
     context: &'a Grib2<R>,
     pos: usize,
+    end: usize,
 }
 
 impl<'a, R> SubmessageIterator<'a, R> {
     fn new(context: &'a Grib2<R>) -> Self {
-        Self { context, pos: 0 }
+        Self {
+            context,
+            pos: 0,
+            end: context.submessages.len(),
+        }
     }
 
     fn new_submessage_section(&self, index: usize) -> Option<SubMessageSection<'a>> {
@@ -298,14 +956,10 @@ impl<'a, R> SubmessageIterator<'a, R> {
             self.context.sections.get(index)?,
         ))
     }
-}
-
-impl<'a, R> Iterator for SubmessageIterator<'a, R> {
-    type Item = (MessageIndex, SubMessage<'a, R>);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let submessage_index = self.context.submessages.get(self.pos)?;
-        self.pos += 1;
+    fn item(&self, pos: usize) -> Option<(MessageIndex, SubMessage<'a, R>)> {
+        let submessage_index = self.context.submessages.get(pos)?;
+        let decoded_values = self.context.decoded_values.get(pos)?;
 
         Some((
             submessage_index.message_index(),
@@ -322,21 +976,74 @@ impl<'a, R> Iterator for SubmessageIterator<'a, R> {
                 self.new_submessage_section(submessage_index.7)?,
                 self.new_submessage_section(submessage_index.8)?,
                 self.context.reader.borrow_mut(),
+                decoded_values,
+                self.previous_bitmap_section(submessage_index),
             ),
         ))
     }
 
+    // Finds the Section 6 defining the bitmap most recently in the same
+    // message, which is referred to by Section 6 of the submessage if its bitmap
+    // indicator is 254 ("a bitmap previously defined in the same message
+    // applies").
+    fn previous_bitmap_section(
+        &self,
+        submessage_index: &Grib2SubmessageIndex,
+    ) -> Option<SubMessageSection<'a>> {
+        let sections = &self.context.sections;
+        let bitmap_indicator = |sect: &SectionInfo| match &sect.body {
+            Some(SectionBody::Section6(body)) => Some(body.bitmap_indicator),
+            _ => None,
+        };
+        if bitmap_indicator(sections.get(submessage_index.6)?) != Some(254) {
+            return None;
+        }
+        (submessage_index.0..submessage_index.6)
+            .rev()
+            .find(|index| bitmap_indicator(&sections[*index]) == Some(0))
+            .and_then(|index| self.new_submessage_section(index))
+    }
+}
+
+impl<'a, R> Iterator for SubmessageIterator<'a, R> {
+    type Item = (MessageIndex, SubMessage<'a, R>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        let item = self.item(self.pos)?;
+        self.pos += 1;
+        Some(item)
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.context.submessages.len() - self.pos;
+        let size = self.end - self.pos;
         (size, Some(size))
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        self.pos = n;
+        self.pos = self.pos.saturating_add(n).min(self.end);
         self.next()
     }
 }
 
+impl<R> DoubleEndedIterator for SubmessageIterator<'_, R> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.end {
+            return None;
+        }
+        let item = self.item(self.end - 1)?;
+        self.end -= 1;
+        Some(item)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.end = self.end.saturating_sub(n).max(self.pos);
+        self.next_back()
+    }
+}
+
 impl<'a, R> IntoIterator for &'a SubmessageIterator<'a, R> {
     type Item = (MessageIndex, SubMessage<'a, R>);
     type IntoIter = SubmessageIterator<'a, R>;
@@ -345,6 +1052,7 @@ impl<'a, R> IntoIterator for &'a SubmessageIterator<'a, R> {
         SubmessageIterator {
             context: self.context,
             pos: self.pos,
+            end: self.end,
         }
     }
 }
@@ -360,8 +1068,104 @@ pub struct SubMessage<'a, R>(
     pub SubMessageSection<'a>,
     pub SubMessageSection<'a>,
     pub(crate) RefMut<'a, R>,
+    pub(crate) &'a OnceCell<Box<[f32]>>,
+    // Section 6 with the bitmap referred to by bitmap indicator 254
+    pub(crate) Option<SubMessageSection<'a>>,
 );
 
+/// Reference time and forecast target time of a submessage, returned by
+/// [`SubMessage::temporal_info`].
+#[cfg(feature = "time-calculation")]
+#[derive(Debug, PartialEq, Eq)]
+pub struct TemporalInfo {
+    pub ref_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Reference time plus the forecast time, which is before the reference
+    /// time if the forecast time is negative.
+    pub forecast_time_target: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Structured summary of a submessage, returned by
+/// [`SubMessage::description`].
+///
+/// Textual fields hold descriptions of templates and code table entries.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubMessageDescription {
+    /// Description of the grid definition template.
+    pub grid_type: Option<String>,
+    /// Number of grid points defined in Section 3.
+    pub num_points: u32,
+    /// Description of the product definition template.
+    pub product_type: Option<String>,
+    pub parameter_category: Option<String>,
+    pub parameter_name: Option<String>,
+    pub generating_process: Option<String>,
+    pub forecast_time: Option<ForecastTime>,
+    pub fixed_surfaces: Option<(FixedSurface, FixedSurface)>,
+    /// Description of the data representation template.
+    pub data_representation: Option<String>,
+    /// Number of values represented in Section 7.
+    pub num_represented_values: u32,
+}
+
+impl Display for SubMessageDescription {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (forecast_time_unit, forecast_time) = self
+            .forecast_time
+            .as_ref()
+            .map(|ft| ft.describe())
+            .unwrap_or_default();
+        let ((first_type, first_factor, first_value), (second_type, second_factor, second_value)) =
+            self.fixed_surfaces
+                .as_ref()
+                .map(|(first, second)| (first.describe(), second.describe()))
+                .unwrap_or_default();
+
+        write!(
+            f,
+            "\
+Grid:                                   {}
+  Number of points:                     {}
+Product:                                {}
+  Parameter Category:                   {}
+  Parameter:                            {}
+  Generating Proceess:                  {}
+  Forecast Time:                        {}
+  Forecast Time Unit:                   {}
+  1st Fixed Surface Type:               {}
+  1st Scale Factor:                     {}
+  1st Scaled Value:                     {}
+  2nd Fixed Surface Type:               {}
+  2nd Scale Factor:                     {}
+  2nd Scaled Value:                     {}
+Data Representation:                    {}
+  Number of represented values:         {}
+",
+            self.grid_type.as_deref().unwrap_or_default(),
+            self.num_points,
+            self.product_type.as_deref().unwrap_or_default(),
+            self.parameter_category.as_deref().unwrap_or_default(),
+            self.parameter_name.as_deref().unwrap_or_default(),
+            self.generating_process.as_deref().unwrap_or_default(),
+            forecast_time,
+            forecast_time_unit,
+            first_type,
+            first_factor,
+            first_value,
+            second_type,
+            second_factor,
+            second_value,
+            self.data_representation.as_deref().unwrap_or_default(),
+            self.num_represented_values,
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DataKind {
+    Analysis,
+    Forecast,
+}
+
 impl<R> SubMessage<'_, R> {
     /// Returns the product's parameter.
     ///
@@ -441,6 +1245,81 @@ impl<R> SubMessage<'_, R> {
         }
     }
 
+    /// Returns the range of bytes of the message containing this submessage,
+    /// as a tuple of the absolute start offset and the absolute end offset
+    /// (exclusive) from the beginning of the input.
+    ///
+    /// The offsets are counted from the beginning of the input even if the
+    /// message is preceded by other data or reading started in the middle of
+    /// the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Cursor, Seek, SeekFrom};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let message = std::fs::read(
+    ///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+    ///     )?;
+    ///     let mut buf = b"HEADER".to_vec();
+    ///     buf.extend_from_slice(&message);
+    ///
+    ///     let mut f = Cursor::new(buf);
+    ///     f.seek(SeekFrom::Start(2))?;
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().unwrap();
+    ///     assert_eq!(submessage.byte_range(), (6, 6 + message.len()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn byte_range(&self) -> (usize, usize) {
+        let sect0 = self.0.body;
+        let sect8 = self.8.body;
+        (sect0.offset, sect8.offset + sect8.size)
+    }
+
+    /// Returns the reference time and the time targeted by the forecast.
+    ///
+    /// The forecast target time is not available if the forecast time is not
+    /// available or its unit does not have a constant length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+    ///
+    ///     let info = submessage.temporal_info();
+    ///     let ref_time = info.ref_time.ok_or("no reference time")?;
+    ///     assert_eq!(
+    ///         info.forecast_time_target,
+    ///         Some(ref_time + chrono::Duration::minutes(10))
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "time-calculation")]
+    pub fn temporal_info(&self) -> TemporalInfo {
+        let ref_time = self.identification().ref_time().ok();
+        let forecast_time_target = ref_time
+            .zip(
+                self.prod_def()
+                    .forecast_time()
+                    .and_then(|ft| ft.to_duration()),
+            )
+            .and_then(|(ref_time, duration)| ref_time.checked_add_signed(duration));
+        TemporalInfo {
+            ref_time,
+            forecast_time_target,
+        }
+    }
+
     fn identification(&self) -> &Identification {
         // panics should not happen if data is correct
         match self.1.body.body.as_ref().unwrap() {
@@ -449,6 +1328,104 @@ impl<R> SubMessage<'_, R> {
         }
     }
 
+    /// Returns ECMWF local use data in Section 2.
+    ///
+    /// `None` is returned if the originating centre is not ECMWF (98), if
+    /// Section 2 is not present, or if its content cannot be parsed as a
+    /// supported local definition.
+    pub fn ecmwf_local_use(&self) -> Option<EcmwfLocalUse> {
+        if self.identification().centre_id() != 98 {
+            return None;
+        }
+        match self.2.as_ref()?.body.body.as_ref()? {
+            SectionBody::Section2(data) => EcmwfLocalUse::from_bytes(data.payload()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns NCEP local use data in Section 2.
+    ///
+    /// `None` is returned if the originating centre is not NCEP (7), if
+    /// Section 2 is not present, or if its content cannot be parsed as a
+    /// supported local definition.
+    pub fn ncep_local_use(&self) -> Option<NcepLocalUse> {
+        if self.identification().centre_id() != 7 {
+            return None;
+        }
+        match self.2.as_ref()?.body.body.as_ref()? {
+            SectionBody::Section2(data) => NcepLocalUse::from_bytes(data.payload()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the submessage is considered to contain forecast
+    /// data.
+    ///
+    /// This is a heuristic. The type of generating process in Section 4 (Code
+    /// Table 4.3) is checked first: forecast-like processes such as "Forecast",
+    /// "Ensemble forecast", "Nowcast" and "Hindcast" are treated as forecasts.
+    /// If the template has no generating process or the process is neither
+    /// forecast-like nor analysis-like (e.g. "Observation" or locally defined
+    /// values), the significance of reference time in Section 1 (Code Table
+    /// 1.2) is used instead, where only "Verifying time of forecast" is
+    /// treated as a forecast.
+    ///
+    /// Both [`is_forecast`](Self::is_forecast) and
+    /// [`is_analysis`](Self::is_analysis) can be `false` for the same
+    /// submessage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let mut iter = grib2.iter();
+    ///     let (_, first) = iter.next().ok_or("first submessage is not found")?;
+    ///     assert!(!first.is_forecast());
+    ///     drop(first);
+    ///     let (_, second) = iter.next().ok_or("second submessage is not found")?;
+    ///     assert!(second.is_forecast());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn is_forecast(&self) -> bool {
+        self.data_kind() == Some(DataKind::Forecast)
+    }
+
+    /// Returns `true` if the submessage is considered to contain analysis
+    /// data.
+    ///
+    /// This is a heuristic. The type of generating process in Section 4 (Code
+    /// Table 4.3) is checked first: analysis-like processes such as
+    /// "Analysis", "Initialization" and "Analysis error" are treated as
+    /// analyses. If the template has no generating process or the process is
+    /// neither forecast-like nor analysis-like, the significance of reference
+    /// time in Section 1 (Code Table 1.2) is used instead, where only
+    /// "Analysis" is treated as an analysis.
+    ///
+    /// See also [`is_forecast`](Self::is_forecast).
+    pub fn is_analysis(&self) -> bool {
+        self.data_kind() == Some(DataKind::Analysis)
+    }
+
+    fn data_kind(&self) -> Option<DataKind> {
+        let from_process = match self.prod_def().generating_process() {
+            Some(0 | 1 | 7 | 12 | 17 | 20 | 21) => Some(DataKind::Analysis),
+            Some(2..=6 | 10 | 11 | 13..=15 | 18 | 19) => Some(DataKind::Forecast),
+            _ => None,
+        };
+        from_process.or_else(|| match self.identification().ref_time_significance() {
+            0 => Some(DataKind::Analysis),
+            2 => Some(DataKind::Forecast),
+            _ => None,
+        })
+    }
+
     pub fn grid_def(&self) -> &GridDefinition {
         // panics should not happen if data is correct
         match self.3.body.body.as_ref().unwrap() {
@@ -457,6 +1434,32 @@ impl<R> SubMessage<'_, R> {
         }
     }
 
+    /// Returns the grid definition parsed into a typed enum.
+    ///
+    /// This is a shorthand for
+    /// `GridDefinitionTemplateValues::try_from(submessage.grid_def())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+    ///
+    ///     let def = submessage.grid_definition_values()?;
+    ///     assert_eq!(def.short_name(), "regular_ll");
+    ///     assert_eq!(def.grid_shape(), (256, 336));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn grid_definition_values(&self) -> Result<GridDefinitionTemplateValues, GribError> {
+        GridDefinitionTemplateValues::try_from(self.grid_def())
+    }
+
     pub fn prod_def(&self) -> &ProdDefinition {
         // panics should not happen if data is correct
         match self.4.body.body.as_ref().unwrap() {
@@ -473,76 +1476,106 @@ impl<R> SubMessage<'_, R> {
         }
     }
 
-    pub fn describe(&self) -> String {
-        let category = self.prod_def().parameter_category();
-        let forecast_time = self
-            .prod_def()
-            .forecast_time()
-            .map(|ft| ft.describe())
-            .unwrap_or((String::new(), String::new()));
-        let fixed_surfaces_info = self
-            .prod_def()
-            .fixed_surfaces()
-            .map(|(first, second)| (first.describe(), second.describe()))
-            .map(|(first, second)| (first.0, first.1, first.2, second.0, second.1, second.2))
-            .unwrap_or((
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-                String::new(),
-            ));
+    /// Returns the Grid Definition Template number in Section 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+    ///
+    ///     assert_eq!(submessage.grid_definition_template_number(), 0);
+    ///     assert_eq!(submessage.product_definition_template_number(), 0);
+    ///     assert_eq!(submessage.data_representation_template_number(), 200);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn grid_definition_template_number(&self) -> u16 {
+        self.grid_def().grid_tmpl_num()
+    }
 
-        format!(
-            "\
-Grid:                                   {}
-  Number of points:                     {}
-Product:                                {}
-  Parameter Category:                   {}
-  Parameter:                            {}
-  Generating Proceess:                  {}
-  Forecast Time:                        {}
-  Forecast Time Unit:                   {}
-  1st Fixed Surface Type:               {}
-  1st Scale Factor:                     {}
-  1st Scaled Value:                     {}
-  2nd Fixed Surface Type:               {}
-  2nd Scale Factor:                     {}
-  2nd Scaled Value:                     {}
-Data Representation:                    {}
-  Number of represented values:         {}
-",
-            self.3.describe().unwrap_or_default(),
-            self.grid_def().num_points(),
-            self.4.describe().unwrap_or_default(),
-            category
-                .map(|v| CodeTable4_1::new(self.indicator().discipline)
+    /// Returns the Product Definition Template number in Section 4.
+    pub fn product_definition_template_number(&self) -> u16 {
+        self.prod_def().prod_tmpl_num()
+    }
+
+    /// Returns the Data Representation Template number in Section 5.
+    pub fn data_representation_template_number(&self) -> u16 {
+        self.repr_def().repr_tmpl_num()
+    }
+
+    /// Returns a structured summary of the submessage, which is also used by
+    /// [`describe`](Self::describe).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+    ///
+    ///     let description = submessage.description();
+    ///     assert_eq!(
+    ///         description.grid_type.as_deref(),
+    ///         Some("Latitude/longitude")
+    ///     );
+    ///     assert_eq!(description.num_points, 86016);
+    ///     assert_eq!(
+    ///         description.generating_process.as_deref(),
+    ///         Some("Analysis")
+    ///     );
+    ///     assert_eq!(
+    ///         description.forecast_time,
+    ///         Some(grib::ForecastTime::from_numbers(0, 0))
+    ///     );
+    ///     let (first_surface, _) = description.fixed_surfaces.ok_or("no fixed surfaces")?;
+    ///     assert_eq!(first_surface.surface_type, 1);
+    ///     assert_eq!(
+    ///         description.data_representation.as_deref(),
+    ///         Some("Run length packing with level values")
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn description(&self) -> SubMessageDescription {
+        let discipline = self.indicator().discipline;
+        let prod_def = self.prod_def();
+        let category = prod_def.parameter_category();
+        SubMessageDescription {
+            grid_type: self.3.describe(),
+            num_points: self.grid_def().num_points(),
+            product_type: self.4.describe(),
+            parameter_category: category.map(|v| {
+                CodeTable4_1::new(discipline)
                     .lookup(usize::from(v))
-                    .to_string())
-                .unwrap_or_default(),
-            self.prod_def()
-                .parameter_number()
-                .zip(category)
-                .map(|(n, c)| CodeTable4_2::new(self.indicator().discipline, c)
+                    .to_string()
+            }),
+            parameter_name: prod_def.parameter_number().zip(category).map(|(n, c)| {
+                CodeTable4_2::new(discipline, c)
                     .lookup(usize::from(n))
-                    .to_string())
-                .unwrap_or_default(),
-            self.prod_def()
+                    .to_string()
+            }),
+            generating_process: prod_def
                 .generating_process()
-                .map(|v| CodeTable4_3.lookup(usize::from(v)).to_string())
-                .unwrap_or_default(),
-            forecast_time.1,
-            forecast_time.0,
-            fixed_surfaces_info.0,
-            fixed_surfaces_info.1,
-            fixed_surfaces_info.2,
-            fixed_surfaces_info.3,
-            fixed_surfaces_info.4,
-            fixed_surfaces_info.5,
-            self.5.describe().unwrap_or_default(),
-            self.repr_def().num_points(),
-        )
+                .map(|v| CodeTable4_3.lookup(usize::from(v)).to_string()),
+            forecast_time: prod_def.forecast_time(),
+            fixed_surfaces: prod_def.fixed_surfaces(),
+            data_representation: self.5.describe(),
+            num_represented_values: self.repr_def().num_points(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        self.description().to_string()
     }
 
     /// Returns the shape of the grid, i.e. a tuple of the number of grids in
@@ -576,8 +1609,7 @@ Data Representation:                    {}
     /// }
     /// ```
     pub fn grid_shape(&self) -> Result<(usize, usize), GribError> {
-        let grid_def = self.grid_def();
-        let shape = GridDefinitionTemplateValues::try_from(grid_def)?.grid_shape();
+        let shape = self.grid_definition_values()?.grid_shape();
         Ok(shape)
     }
 
@@ -619,9 +1651,8 @@ Data Representation:                    {}
     /// }
     /// ```
     pub fn ij(&self) -> Result<GridPointIndexIterator, GribError> {
-        let grid_def = self.grid_def();
-        let num_defined = grid_def.num_points() as usize;
-        let ij = GridDefinitionTemplateValues::try_from(grid_def)?.ij()?;
+        let num_defined = self.grid_def().effective_num_points()? as usize;
+        let ij = self.grid_definition_values()?.ij()?;
         let (num_decoded, _) = ij.size_hint();
         if num_defined == num_decoded {
             Ok(ij)
@@ -668,38 +1699,569 @@ Data Representation:                    {}
     /// }
     /// ```
     pub fn latlons(&self) -> Result<GridPointIterator, GribError> {
-        let grid_def = self.grid_def();
-        let num_defined = grid_def.num_points() as usize;
-        let latlons = GridDefinitionTemplateValues::try_from(grid_def)?.latlons()?;
-        let (num_decoded, _) = latlons.size_hint();
-        if num_defined == num_decoded {
-            Ok(latlons)
-        } else {
-            Err(GribError::InvalidValueError(format!(
-                "number of grid points does not match: {num_defined} (defined) vs {num_decoded} (decoded)"
-            )))
-        }
+        grid_latlons(self.grid_def())
     }
 }
 
-pub struct SubMessageSection<'a> {
-    pub index: usize,
-    pub body: &'a SectionInfo,
-}
-
-impl<'a> SubMessageSection<'a> {
-    pub fn new(index: usize, body: &'a SectionInfo) -> Self {
-        Self { index, body }
+fn grid_latlons(grid_def: &GridDefinition) -> Result<GridPointIterator, GribError> {
+    let num_defined = grid_def.effective_num_points()? as usize;
+    let latlons = GridDefinitionTemplateValues::try_from(grid_def)?.latlons()?;
+    let (num_decoded, _) = latlons.size_hint();
+    if num_defined == num_decoded {
+        Ok(latlons)
+    } else {
+        Err(GribError::InvalidValueError(format!(
+            "number of grid points does not match: {num_defined} (defined) vs {num_decoded} (decoded)"
+        )))
     }
+}
 
-    pub fn template_code(&self) -> Option<TemplateInfo> {
-        self.body.get_tmpl_code()
+impl<'a, R: Grib2Read> SubMessage<'a, R> {
+    /// Decodes and returns grid point values.
+    ///
+    /// Decoded values are cached in the [`Grib2`] instance, so Section 7 is
+    /// read and decoded only at the first call for each submessage, even when
+    /// the submessage is obtained again from another iterator. The cache is
+    /// not evicted automatically; it can be dropped with
+    /// [`Grib2::clear_cache`]. To process values without caching them, use
+    /// [`for_each_value`](Self::for_each_value),
+    /// [`present_values`](Self::present_values), or
+    /// [`Grib2SubmessageDecoder::decode_into`] with a reused buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let values = first_submessage.values()?;
+    ///     assert_eq!(values.len(), 1126500);
+    ///     assert_eq!(values[0].round(), 236.0_f32);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn values(&mut self) -> Result<&'a [f32], GribError> {
+        let cache = self.10;
+        if let Some(values) = cache.get() {
+            return Ok(values);
+        }
+        let decoder = Grib2SubmessageDecoder::from_submessage(self)?;
+        let values = decoder.dispatch()?.collect::<Box<[f32]>>();
+        Ok(cache.get_or_init(|| values))
     }
 
-    pub fn describe(&self) -> Option<String> {
-        self.template_code().and_then(|code| code.describe())
+    /// Reads and returns the raw payload of Section 7, i.e. octets after the
+    /// section header, without decoding them.
+    ///
+    /// This is useful for implementing custom decoders for packing methods not
+    /// supported by this crate. The payload can also be passed to
+    /// [`decode_values`](crate::decode_values) together with other sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let bytes = first_submessage.section7_bytes()?;
+    ///     assert_eq!(bytes.len(), first_submessage.7.body.size - 5);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn section7_bytes(&mut self) -> Result<Box<[u8]>, GribError> {
+        let sect7 = self.7.body;
+        self.9
+            .read_sect_payload_as_slice(sect7)
+            .map_err(|e| GribError::DecodeError(DecodeError::SectionReadError(sect7.num, e)))
     }
-}
+
+    /// Decodes values and returns an iterator over pairs of the index and the
+    /// value of grid points present in the bitmap.
+    ///
+    /// Indices are those in the order of [`values`](Self::values). Grid points
+    /// masked by the bitmap are skipped without being expanded to NaN, so
+    /// that sparse fields can be processed efficiently. Values are neither
+    /// read from nor stored in the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let mut f = xz2::bufread::XzDecoder::new(f);
+    ///     let mut buf = Vec::new();
+    ///     std::io::Read::read_to_end(&mut f, &mut buf)?;
+    ///     let grib2 = grib::from_slice(&buf)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let num_present = first_submessage.present_values()?.count();
+    ///     assert_eq!(num_present, 162225);
+    ///     assert_eq!(first_submessage.grid_def().num_points(), 268800);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn present_values(&mut self) -> Result<impl Iterator<Item = (usize, f32)>, GribError> {
+        let decoder = Grib2SubmessageDecoder::from_submessage(self)?;
+        Ok(decoder.present_values()?.into_iter())
+    }
+
+    /// Returns a parallel iterator over latitudes, longitudes, and values of
+    /// grid points.
+    ///
+    /// Values are decoded and latitudes and longitudes are computed before
+    /// the iteration, so that georeferencing of large fields can be
+    /// parallelized with [rayon](https://docs.rs/rayon).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let num_points_in_north = first_submessage
+    ///         .par_points()?
+    ///         .filter(|(lat, _lon, _value)| *lat > 0.)
+    ///         .count();
+    ///     assert!(num_points_in_north > 0);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_points(
+        &mut self,
+    ) -> Result<impl rayon::iter::IndexedParallelIterator<Item = (f32, f32, f32)> + 'a, GribError>
+    {
+        use rayon::prelude::*;
+
+        let latlons = self.latlons()?.collect::<Vec<_>>();
+        let values = self.values()?;
+        let iter = latlons
+            .into_par_iter()
+            .zip(values.par_iter())
+            .map(|((lat, lon), value)| (lat, lon, *value));
+        Ok(iter)
+    }
+
+    /// Decodes grid point values and passes them to `f` one by one.
+    ///
+    /// Unlike [`values`](Self::values), decoded values are neither collected
+    /// nor cached, so that large fields can be processed without holding all
+    /// the values in memory. If the values have already been cached, they are
+    /// read from the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let mut count = 0;
+    ///     let mut max = f32::MIN;
+    ///     first_submessage.for_each_value(|value| {
+    ///         count += 1;
+    ///         max = max.max(value);
+    ///     })?;
+    ///     assert_eq!(count, 1126500);
+    ///     assert_eq!(max.round(), 286.0_f32);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn for_each_value<F: FnMut(f32)>(&mut self, f: F) -> Result<(), GribError> {
+        if let Some(values) = self.10.get() {
+            values.iter().copied().for_each(f);
+            return Ok(());
+        }
+        let decoder = Grib2SubmessageDecoder::from_submessage(self)?;
+        decoder.dispatch()?.for_each(f);
+        Ok(())
+    }
+
+    /// Decodes and returns values in a rectangular window of the grid.
+    ///
+    /// The window starts at the `x`-th point in the i direction and the `y`-th
+    /// point in the j direction, counted in the order the values are stored,
+    /// and has `width` × `height` points. Values are arranged row by row.
+    ///
+    /// This is available only for grids with the same number of points in
+    /// every row and with points consecutive in the i direction in
+    /// non-alternating rows. For data packed with the JPEG 2000 code stream
+    /// format (template 5.40) without missing grid points, only the window is
+    /// decoded unless the values have already been cached; otherwise the
+    /// window is taken from the values returned by [`values`](Self::values).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let values = first_submessage.subgrid(0, 0, 10, 5)?;
+    ///     assert_eq!(values.len(), 50);
+    ///     assert_eq!(values[0].round(), 236.0_f32);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn subgrid(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<f32>, GribError> {
+        let def = self.grid_definition_values()?;
+        let scanning_mode = match &def {
+            GridDefinitionTemplateValues::Template0(def) if def.points_per_row.is_none() => {
+                Some(def.scanning_mode)
+            }
+            GridDefinitionTemplateValues::Template20(def) => Some(def.scanning_mode),
+            GridDefinitionTemplateValues::Template30(def) => Some(def.scanning_mode),
+            GridDefinitionTemplateValues::Template40(def) => Some(def.scanning_mode),
+            GridDefinitionTemplateValues::Template90(def) => Some(def.scanning_mode),
+            GridDefinitionTemplateValues::Template140(def) => Some(def.scanning_mode),
+            _ => None,
+        };
+        if !scanning_mode
+            .is_some_and(|mode| mode.is_consecutive_for_i() && !mode.scans_alternating_rows())
+        {
+            return Err(GribError::NotSupported(
+                "subgrid of grids not stored in rows of the same length along the i direction"
+                    .to_owned(),
+            ));
+        }
+
+        let (ni, nj) = def.grid_shape();
+        let fits = |start: usize, len: usize, max: usize| {
+            start.checked_add(len).is_some_and(|end| end <= max)
+        };
+        if !fits(x, width, ni) || !fits(y, height, nj) {
+            return Err(GribError::InvalidValueError(format!(
+                "window of {width}x{height} points at ({x}, {y}) exceeds the grid of {ni}x{nj} points"
+            )));
+        }
+
+        #[cfg(jpeg2000)]
+        if self.10.get().is_none() {
+            let decoder = Grib2SubmessageDecoder::from_submessage(self)?;
+            if decoder.packing_kind() == PackingKind::Jpeg2000
+                && decoder.num_points_encoded == decoder.num_points_total
+            {
+                return decoder.decode_region(x, y, width, height);
+            }
+        }
+
+        let values = self.values()?;
+        if ni.checked_mul(nj) != Some(values.len()) {
+            return Err(GribError::DecodeError(DecodeError::LengthMismatch));
+        }
+        let window = (y..y + height)
+            .flat_map(|j| &values[j * ni + x..j * ni + x + width])
+            .copied()
+            .collect();
+        Ok(window)
+    }
+
+    /// Resamples values of the field to a regular latitude/longitude grid with
+    /// the nearest-neighbor method.
+    ///
+    /// Each point of `target` gets the value at the nearest grid point of the
+    /// field, taking the wrap-around of longitude into account. Points of
+    /// `target` outside the domain of the field get NaN. Values are returned
+    /// in the order of points of `target`, i.e. following its scanning mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let target = grib::LatLonGridDefinition {
+    ///         ni: 10,
+    ///         nj: 10,
+    ///         first_point_lat: 40_000_000,
+    ///         first_point_lon: 130_000_000,
+    ///         last_point_lat: 31_000_000,
+    ///         last_point_lon: 139_000_000,
+    ///         scanning_mode: grib::ScanningMode(0b00000000),
+    ///         points_per_row: None,
+    ///     };
+    ///     let values = first_submessage.resample_to(&target)?;
+    ///     assert_eq!(values.len(), 100);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn resample_to(&mut self, target: &LatLonGridDefinition) -> Result<Vec<f32>, GribError> {
+        let src_latlons = self.latlons()?.collect::<Vec<_>>();
+        let src_values = self.values()?;
+        if src_latlons.len() != src_values.len() {
+            return Err(GribError::DecodeError(DecodeError::LengthMismatch));
+        }
+        let target_latlons = target.latlons()?;
+        Ok(resample_nearest(&src_latlons, src_values, target_latlons))
+    }
+
+    /// Returns latitudes and longitudes of a regular grid as sorted axes,
+    /// together with grid point values rearranged accordingly.
+    ///
+    /// Latitudes are sorted in descending order and longitudes in ascending
+    /// order, regardless of the scanning mode. Values are arranged in the
+    /// row-major order, i.e. the value at the `r`-th latitude and the `c`-th
+    /// longitude is at `r * lons.len() + c`.
+    ///
+    /// This is available only for regular lat/lon grids (template 3.0) and
+    /// regular Gaussian grids (template 3.40).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let (lats, lons, values) = first_submessage.to_sorted_grid()?;
+    ///     assert_eq!((lats.len(), lons.len()), (751, 1500));
+    ///     assert_eq!(lats[0], 90.0);
+    ///     assert_eq!(lons[0], 0.0);
+    ///     assert_eq!(values.len(), 751 * 1500);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn to_sorted_grid(&mut self) -> Result<SortedGrid, GribError> {
+        let def = self.grid_definition_values()?;
+        let (ni, nj) =
+            match &def {
+                GridDefinitionTemplateValues::Template0(def) if def.points_per_row.is_none() => {
+                    (def.ni as usize, def.nj as usize)
+                }
+                GridDefinitionTemplateValues::Template40(def) => (def.ni as usize, def.nj as usize),
+                _ => return Err(GribError::NotSupported(
+                    "sorting grid points of grids other than regular lat/lon and Gaussian grids"
+                        .to_owned(),
+                )),
+            };
+
+        let ij = def.ij()?;
+        let latlons = self.latlons()?;
+        let values = self.values()?;
+        if ni.checked_mul(nj) != Some(values.len()) {
+            return Err(GribError::InvalidValueError(format!(
+                "number of values does not match: {} (decoded) vs {ni}x{nj} (grid)",
+                values.len()
+            )));
+        }
+
+        let mut lat_axis = vec![f32::NAN; nj];
+        let mut lon_axis = vec![f32::NAN; ni];
+        let mut grid = vec![f32::NAN; values.len()];
+        for (((i, j), (lat, lon)), value) in ij.zip(latlons).zip(values) {
+            lat_axis[j] = lat;
+            lon_axis[i] = lon;
+            grid[j * ni + i] = *value;
+        }
+
+        let mut j_order = (0..nj).collect::<Vec<_>>();
+        j_order.sort_by(|a, b| lat_axis[*b].total_cmp(&lat_axis[*a]));
+        let mut i_order = (0..ni).collect::<Vec<_>>();
+        i_order.sort_by(|a, b| lon_axis[*a].total_cmp(&lon_axis[*b]));
+
+        let lats = j_order.iter().map(|j| lat_axis[*j]).collect();
+        let lons = i_order.iter().map(|i| lon_axis[*i]).collect();
+        let grid = &grid;
+        let i_order = &i_order;
+        let values = j_order
+            .iter()
+            .flat_map(|j| i_order.iter().map(move |i| grid[j * ni + i]))
+            .collect();
+        Ok((lats, lons, values))
+    }
+
+    /// Computes statistics of grid point values in a single decoding pass.
+    ///
+    /// NaN values, including those of grid points masked by the bitmap, are
+    /// ignored. As with [`for_each_value`](Self::for_each_value), decoded
+    /// values are not cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let stats = first_submessage.statistics()?;
+    ///     assert_eq!(stats.count, 1126500);
+    ///     assert_eq!(stats.max.round(), 286.0_f32);
+    ///     assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn statistics(&mut self) -> Result<FieldStats, GribError> {
+        let mut count = 0;
+        let mut min = f32::INFINITY;
+        let mut max = f32::NEG_INFINITY;
+        let mut sum = 0_f64;
+        self.for_each_value(|value| {
+            if value.is_nan() {
+                return;
+            }
+            count += 1;
+            min = min.min(value);
+            max = max.max(value);
+            sum += f64::from(value);
+        })?;
+
+        let stats = if count == 0 {
+            FieldStats {
+                count,
+                min: f32::NAN,
+                max: f32::NAN,
+                mean: f32::NAN,
+            }
+        } else {
+            FieldStats {
+                count,
+                min,
+                max,
+                mean: (sum / count as f64) as f32,
+            }
+        };
+        Ok(stats)
+    }
+
+    /// Decodes and returns level values of run-length packing (Data
+    /// Representation Template 5.200) without mapping them to representative
+    /// values.
+    ///
+    /// Levels are indices into the list of representative values in Section
+    /// 5, ranging from 0 to the maximum level, where 0 denotes missing values.
+    /// Grid points masked by the bitmap are also given level 0. This is useful
+    /// for categorical products such as radar echo intensity categories.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, mut first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let levels = first_submessage.level_values()?;
+    ///     assert_eq!(levels.len(), 86016);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn level_values(&mut self) -> Result<Vec<u16>, GribError> {
+        let decoder = Grib2SubmessageDecoder::from_submessage(self)?;
+        decoder.level_values()
+    }
+
+    /// Decodes and returns spherical harmonic coefficients as pairs of the real
+    /// and imaginary parts.
+    ///
+    /// This is available only for submessages with Grid Definition Template
+    /// 3.50 (spherical harmonic coefficients). The coefficients are ordered as
+    /// stored in the data. Decoded values are cached in the same way as
+    /// [`values`](Self::values).
+    pub fn spectral_coefficients(&mut self) -> Result<Vec<(f32, f32)>, GribError> {
+        if !matches!(
+            self.grid_definition_values(),
+            Ok(GridDefinitionTemplateValues::Template50(_))
+        ) {
+            return Err(GribError::InvalidValueError(
+                "not spherical harmonic coefficients".to_owned(),
+            ));
+        }
+        let values = self.values()?;
+        if values.len() % 2 != 0 {
+            return Err(GribError::InvalidValueError(format!(
+                "odd number of values for complex coefficients: {}",
+                values.len()
+            )));
+        }
+        let coefficients = values
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+        Ok(coefficients)
+    }
+}
+
+/// Latitudes, longitudes, and values of grid points, returned by
+/// [`SubMessage::to_sorted_grid`].
+pub type SortedGrid = (Vec<f32>, Vec<f32>, Vec<f32>);
+
+/// Statistics of grid point values of a submessage.
+///
+/// This `struct` is created by the [`statistics`] method on [`SubMessage`].
+/// All the values other than `count` are NaN if there are no non-NaN values.
+///
+/// [`statistics`]: SubMessage::statistics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldStats {
+    /// Number of non-NaN values.
+    pub count: usize,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+}
+
+pub struct SubMessageSection<'a> {
+    pub index: usize,
+    pub body: &'a SectionInfo,
+}
+
+impl<'a> SubMessageSection<'a> {
+    pub fn new(index: usize, body: &'a SectionInfo) -> Self {
+        Self { index, body }
+    }
+
+    pub fn template_code(&self) -> Option<TemplateInfo> {
+        self.body.get_tmpl_code()
+    }
+
+    pub fn describe(&self) -> Option<String> {
+        self.template_code().and_then(|code| code.describe())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -729,6 +2291,50 @@ mod tests {
         assert!(result.is_ok())
     }
 
+    #[test]
+    fn byte_ranges_of_messages_following_leading_data() -> Result<(), Box<dyn std::error::Error>> {
+        let message = std::fs::read(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let header = b"HEADER TO BE SKIPPED\n";
+        let junk = b"JUNK";
+        let mut buf = header.to_vec();
+        buf.extend_from_slice(&message);
+        buf.extend_from_slice(junk);
+        buf.extend_from_slice(&message);
+
+        let first_start = header.len();
+        let first_end = first_start + message.len();
+        let second_start = first_end + junk.len();
+        let second_end = second_start + message.len();
+
+        let grib2 = from_reader(std::io::Cursor::new(&buf))?;
+        let actual = grib2
+            .iter()
+            .map(|(_, submessage)| submessage.byte_range())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![(first_start, first_end), (second_start, second_end)]
+        );
+        let (start, end) = actual[1];
+        assert_eq!(&buf[start..end], &message[..]);
+
+        // starting reading in the middle of the leading data
+        let mut f = std::io::Cursor::new(&buf);
+        std::io::Seek::seek(&mut f, std::io::SeekFrom::Start(7))?;
+        let grib2 = from_reader(f)?;
+        let actual = grib2
+            .iter()
+            .map(|(_, submessage)| submessage.byte_range())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            vec![(first_start, first_end), (second_start, second_end)]
+        );
+        Ok(())
+    }
+
     #[test]
     fn from_bytes() {
         let f = File::open(
@@ -896,6 +2502,986 @@ mod tests {
         ),
     }
 
+    #[test]
+    fn values_with_previously_defined_bitmap() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+        f.read_to_end(&mut buf)?;
+        let grib2 = crate::from_slice(&buf)?;
+        let bitmap_indicator = |submessage: &SubMessage<_>| match &submessage.6.body.body {
+            Some(SectionBody::Section6(body)) => Some(body.bitmap_indicator),
+            _ => None,
+        };
+
+        let (first_sect6, first) = {
+            let (_, mut first) = grib2.iter().next().ok_or("first submessage not found")?;
+            assert_eq!(bitmap_indicator(&first), Some(0));
+            (first.6.index, first.values()?.to_vec())
+        };
+
+        let (_, mut second) = grib2.iter().nth(1).ok_or("second submessage not found")?;
+        assert_eq!(bitmap_indicator(&second), Some(254));
+        assert_eq!(second.11.as_ref().map(|sect| sect.index), Some(first_sect6));
+        let second = second.values()?;
+        assert_eq!(second.len(), 268800);
+        assert!(first
+            .iter()
+            .zip(second)
+            .all(|(a, b)| a.is_nan() == b.is_nan()));
+        assert_eq!(second.iter().filter(|v| !v.is_nan()).count(), 162225);
+        Ok(())
+    }
+
+    #[test]
+    fn time_series_for_precipitation_rate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let f = Cursor::new(buf);
+        let grib2 = crate::from_reader(f)?;
+
+        let param = Parameter {
+            discipline: 0,
+            centre: 34,
+            master_ver: 5,
+            local_ver: 1,
+            category: 1,
+            num: 52,
+        };
+        let level = FixedSurface::new(1, -127, -2147483647);
+        let series = grib2.time_series(&param, &level);
+
+        assert_eq!(series.len(), 19);
+        assert_eq!(series[0], (ForecastTime::from_numbers(1, 0), (0, 1)));
+        assert!(series
+            .windows(2)
+            .all(|pair| pair[0].0.value <= pair[1].0.value));
+
+        Ok(())
+    }
+
+    #[test]
+    fn submessages_grouped_by_parameter() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let groups = grib2.group_by_parameter();
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), grib2.len());
+
+        let param = Parameter {
+            discipline: 0,
+            centre: 34,
+            master_ver: 5,
+            local_ver: 1,
+            category: 1,
+            num: 52,
+        };
+        let group = groups.get(&param).ok_or("precipitation rate not found")?;
+        assert_eq!(group.len(), 19);
+        assert_eq!(group[0], (0, 1));
+        assert!(group.windows(2).all(|pair| pair[0] < pair[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn analysis_and_forecast_submessages() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+
+        let actual = grib2
+            .iter()
+            .map(|(index, submessage)| (index, submessage.is_analysis(), submessage.is_forecast()))
+            .collect::<Vec<_>>();
+        assert_eq!(actual[0], ((0, 0), true, false));
+        assert_eq!(actual[1], ((0, 1), false, true));
+        assert!(actual[1..]
+            .iter()
+            .all(|(_, is_analysis, is_forecast)| !is_analysis && *is_forecast));
+        Ok(())
+    }
+
+    #[test]
+    fn production_status_and_type_of_data() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::codetables::{
+            grib2::{Table1_3, Table1_4},
+            Code,
+        };
+
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+
+        let ident = submessage.identification();
+        assert_eq!(
+            ident.prod_status_code(),
+            Code::Name(Table1_3::OperationalProducts)
+        );
+        assert_eq!(
+            ident.data_type_code(),
+            Code::Name(Table1_4::AnalysisAndForecastProducts)
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn points_from_parallel_iterator() -> Result<(), Box<dyn std::error::Error>> {
+        use rayon::prelude::*;
+
+        let f = File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+
+        let expected = submessage
+            .latlons()?
+            .zip(submessage.values()?)
+            .map(|((lat, lon), value)| (lat, lon, *value))
+            .collect::<Vec<_>>();
+        let actual = submessage.par_points()?.collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn decoding_submessages_out_of_order() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190605000000_MEPS_GPV_Rjp_L-pall_FH00-15_grib2.bin.0-20.xz",
+        )?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        // `Grib2SubmessageDecoder` is used to bypass the cache of decoded values.
+        let decode = |grib2: &Grib2<SeekableGrib2Reader<Cursor<&[u8]>>>, n: usize| {
+            let (_, submessage) = grib2.iter().nth(n).ok_or("submessage not found")?;
+            let decoder = Grib2SubmessageDecoder::from(submessage)?;
+            let values = decoder.dispatch()?.collect::<Vec<_>>();
+            Ok::<_, Box<dyn std::error::Error>>(values)
+        };
+
+        let grib2 = crate::from_slice(&buf)?;
+        let expected = (0..3)
+            .map(|n| decode(&grib2, n))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let mut actual = vec![Vec::new(); 3];
+        for n in [2, 0, 1] {
+            let (_, submessage) = grib2.iter().nth(n).ok_or("submessage not found")?;
+            let _ = submessage.latlons()?.count();
+            let sect7 = submessage.7.body;
+            let sect7_end = (sect7.offset + sect7.size) as u64;
+            drop(submessage);
+            actual[n] = decode(&grib2, n)?;
+            assert_eq!(grib2.reader_position()?, sect7_end);
+        }
+
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            assert!(actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| a == e || (a.is_nan() && e.is_nan())));
+            assert_eq!(actual.len(), expected.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn progress_of_reading_multi_message_data() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let mut offsets = Vec::new();
+        let reader = SeekableGrib2Reader::new(Cursor::new(&buf));
+        let grib2 = Grib2::read_with_progress(reader, |offset| offsets.push(offset))?;
+
+        let num_messages = grib2.sections().filter(|sect| sect.num == 0).count();
+        assert_eq!(offsets.len(), num_messages);
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(offsets.last(), Some(&buf.len()));
+        Ok(())
+    }
+
+    // Reader returning at most 7 bytes at a time and recording the number of
+    // bytes read so far.
+    struct SlowReader<'a> {
+        inner: &'a [u8],
+        num_read: Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Read for SlowReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(7);
+            let size = self.inner.read(&mut buf[..len])?;
+            self.num_read.set(self.num_read.get() + size);
+            Ok(size)
+        }
+    }
+
+    #[test]
+    fn streaming_submessages_from_slow_reader() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let first_message_size = grib2.extract_message(0)?.len();
+        let expected_indices = grib2.iter().map(|(index, _)| index).collect::<Vec<_>>();
+
+        let num_read = Rc::new(std::cell::Cell::new(0));
+        let reader = SlowReader {
+            inner: &buf,
+            num_read: Rc::clone(&num_read),
+        };
+        let mut stream = crate::stream_submessages(reader);
+
+        let mut first = stream.next().ok_or("first submessage not found")??;
+        assert_eq!(first.index(), (0, 0));
+        assert_eq!(num_read.get(), first_message_size);
+        let (_, expected) = grib2.iter().next().ok_or("first submessage not found")?;
+        assert_eq!(first.submessage().parameter(), expected.parameter());
+        drop(expected);
+
+        let mut actual_indices = vec![first.index()];
+        for item in stream {
+            actual_indices.push(item?.index());
+        }
+        assert_eq!(actual_indices, expected_indices);
+        assert_eq!(num_read.get(), buf.len());
+        Ok(())
+    }
+
+    #[test]
+    fn using_streamed_submessages_in_same_message_at_same_time(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let buf = std::fs::read(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let grib2 = crate::from_slice(&buf)?;
+        let expected = grib2
+            .iter()
+            .map(|(_, mut submessage)| {
+                submessage
+                    .values()
+                    .map(|values| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut items = crate::stream_submessages(buf.as_slice()).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(items.len(), 7);
+        assert!(items.iter().all(|item| item.index().0 == 0));
+        let mut submessages = items
+            .iter_mut()
+            .map(|item| item.submessage())
+            .collect::<Vec<_>>();
+        let actual = submessages
+            .iter_mut()
+            .map(|submessage| {
+                submessage
+                    .values()
+                    .map(|values| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn streaming_submessages_with_huge_total_length() {
+        let mut buf = b"GRIB\xff\xff\x00\x02".to_vec();
+        buf.extend_from_slice(&(1_u64 << 60).to_be_bytes());
+        buf.extend_from_slice(&[0; 100]);
+
+        let results = crate::stream_submessages(buf.as_slice()).collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0],
+            Err(GribError::ParseError(ParseError::ReadError(_)))
+        ));
+    }
+
+    #[test]
+    fn streaming_submessages_from_truncated_data() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+        buf.truncate(buf.len() - 1);
+
+        let results = crate::stream_submessages(buf.as_slice()).collect::<Vec<_>>();
+        assert_eq!(results.len(), 10);
+        assert!(results[..9].iter().all(|result| result.is_ok()));
+        assert!(matches!(
+            results[9],
+            Err(GribError::ParseError(ParseError::ReadError(_)))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_of_values() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/Z__C_RJTD_20170221120000_MSG_GPV_Gll0p5deg_Pys_B20170221120000_F2017022115-2017022212_grib2.bin.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+        let num_points_encoded = match submessage.5.body.body.as_ref() {
+            Some(SectionBody::Section5(repr_def)) => repr_def.num_points() as usize,
+            _ => return Err("Section 5 not found".into()),
+        };
+
+        let stats = submessage.statistics()?;
+        assert_eq!(stats.count, num_points_encoded);
+        assert!(stats.min.is_finite() && stats.max.is_finite() && stats.mean.is_finite());
+        assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+
+        let values = submessage.values()?;
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        assert_eq!(stats.max, max);
+        Ok(())
+    }
+
+    #[test]
+    fn sorted_grid_of_global_field() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+        let (lats, lons, values) = submessage.to_sorted_grid()?;
+
+        assert_eq!(lats.first(), Some(&90.0));
+        assert!(lats.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(lons.first(), Some(&0.0));
+        assert!(lons.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(values.len(), lats.len() * lons.len());
+        // The grid scans from the north pole eastwards, which is already sorted.
+        let original = submessage.values()?;
+        assert!(values
+            .iter()
+            .zip(original)
+            .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())));
+        Ok(())
+    }
+
+    #[test]
+    fn sorted_grid_of_field_scanning_westwards() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+        let (start, end) = submessage.byte_range();
+        let sect3_offset = submessage.3.body.offset - start;
+        let original = submessage.values()?.to_vec();
+        drop(submessage);
+
+        // Turns the grid into one scanning in the -i direction from 359.75 to 0
+        // degrees, with the same sequence of values.
+        let mut message = buf[start..end].to_vec();
+        let sect3 = &mut message[sect3_offset..];
+        let first_point_lon = sect3[50..54].to_vec();
+        sect3.copy_within(59..63, 50);
+        sect3[59..63].copy_from_slice(&first_point_lon);
+        sect3[71] |= 0b10000000;
+
+        let grib2 = crate::from_slice(&message)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+        let mut latlons = submessage.latlons()?;
+        let (_, first_lon) = latlons.next().ok_or("first point not found")?;
+        let (_, second_lon) = latlons.next().ok_or("second point not found")?;
+        assert!(first_lon > second_lon);
+
+        let (lats, lons, values) = submessage.to_sorted_grid()?;
+        let (ni, nj) = (1440, 721);
+        assert_eq!((lats.len(), lons.len()), (nj, ni));
+        assert_eq!(lons.first(), Some(&0.0));
+        assert!(lons.windows(2).all(|w| w[0] < w[1]));
+        // Each row comes out west-to-east, i.e. in the reverse order of scanning.
+        let reversed = original
+            .chunks(ni)
+            .flat_map(|row| row.iter().rev())
+            .copied()
+            .collect::<Vec<_>>();
+        assert!(values
+            .iter()
+            .zip(&reversed)
+            .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())));
+        Ok(())
+    }
+
+    #[test]
+    fn sorted_grid_of_lambert_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/ds.critfireo.bin.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+        assert!(matches!(
+            submessage.to_sorted_grid(),
+            Err(GribError::NotSupported(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn level_values_of_run_length_packed_data() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+
+        let levels = submessage.level_values()?;
+        let distinct = levels
+            .iter()
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(distinct.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+        let values = submessage.values()?;
+        assert_eq!(levels.len(), values.len());
+        assert!(levels
+            .iter()
+            .zip(values)
+            .all(|(level, value)| (*level == 0) == value.is_nan()));
+        Ok(())
+    }
+
+    #[test]
+    fn level_values_of_data_not_packed_with_run_length_packing(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+
+        assert!(matches!(
+            submessage.level_values(),
+            Err(GribError::InvalidValueError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn submessage_iteration_from_back() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let len = grib2.len();
+        let (expected_index, expected) = grib2.iter().nth(len - 1).ok_or("item not available")?;
+        let expected_param = expected.parameter();
+        drop(expected);
+
+        let mut iter = grib2.iter();
+        let (actual_index, actual) = iter.next_back().ok_or("item not available")?;
+        assert_eq!(actual_index, expected_index);
+        assert_eq!(actual.parameter(), expected_param);
+        drop(actual);
+        assert_eq!(iter.size_hint(), (len - 1, Some(len - 1)));
+
+        let forward = grib2.iter().map(|(index, _)| index).collect::<Vec<_>>();
+        let mut backward = grib2
+            .iter()
+            .rev()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        backward.reverse();
+        assert_eq!(backward, forward);
+
+        let mut iter = grib2.iter();
+        let _ = iter.next_back();
+        assert_eq!(
+            iter.by_ref().map(|(index, _)| index).last(),
+            Some(forward[len - 2])
+        );
+        assert!(iter.next_back().is_none());
+        Ok(())
+    }
+
+    // Creates a message with a bitmap marking all the points as missing and Section
+    // 7 without any packed values from a message using simple packing.
+    fn message_with_all_missing_values() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let original = std::fs::read(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let grib2 = crate::from_slice(&original)?;
+        let num_points = grib2.total_points();
+
+        let mut message = Vec::new();
+        for sect in grib2.sections() {
+            let bytes = &original[sect.offset..sect.offset + sect.size];
+            match sect.num {
+                5 => {
+                    let mut bytes = bytes.to_vec();
+                    bytes[5..9].copy_from_slice(&0_u32.to_be_bytes());
+                    message.extend_from_slice(&bytes);
+                }
+                6 => {
+                    let bitmap_size = num_points.div_ceil(8);
+                    message.extend_from_slice(&(6 + bitmap_size as u32).to_be_bytes());
+                    message.extend_from_slice(&[6, 0]);
+                    message.extend(std::iter::repeat_n(0, bitmap_size));
+                }
+                7 => message.extend_from_slice(&[0, 0, 0, 5, 7]),
+                _ => message.extend_from_slice(bytes),
+            }
+        }
+        let total_length = message.len() as u64;
+        message[8..16].copy_from_slice(&total_length.to_be_bytes());
+        Ok(message)
+    }
+
+    // Creates a message with NCEP local use data in Section 2 from a message
+    // without Section 2.
+    fn message_with_ncep_local_use(payload: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.12.xz")?;
+        let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+        f.read_to_end(&mut buf)?;
+        let grib2 = crate::from_slice(&buf)?;
+
+        let mut message = Vec::new();
+        for sect in grib2.sections() {
+            message.extend_from_slice(&buf[sect.offset..sect.offset + sect.size]);
+            if sect.num == 1 {
+                message.extend_from_slice(&(5 + payload.len() as u32).to_be_bytes());
+                message.push(2);
+                message.extend_from_slice(payload);
+            }
+        }
+        let total_length = message.len() as u64;
+        message[8..16].copy_from_slice(&total_length.to_be_bytes());
+        Ok(message)
+    }
+
+    #[test]
+    fn ncep_local_use_in_section_2() -> Result<(), Box<dyn std::error::Error>> {
+        let message = message_with_ncep_local_use(&[0x01, 0x01, 0x01, 0x00, 0x01, 0xff])?;
+        let grib2 = crate::from_slice(&message)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let local_use = submessage
+            .ncep_local_use()
+            .ok_or("local use data is not found")?;
+        assert_eq!(local_use.ensemble_type, 1);
+        assert!(local_use.is_control_forecast());
+        assert_eq!(submessage.ecmwf_local_use(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn ncep_local_use_with_unsupported_identifier() -> Result<(), Box<dyn std::error::Error>> {
+        let message = message_with_ncep_local_use(&[0x02, 0x01])?;
+        let grib2 = crate::from_slice(&message)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        assert_eq!(submessage.ncep_local_use(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn section7_bytes_of_submessages() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+        f.read_to_end(&mut buf)?;
+        let grib2 = crate::from_slice(&buf)?;
+
+        for (_, mut submessage) in grib2.iter() {
+            let sect7 = submessage.7.body;
+            let bytes = submessage.section7_bytes()?;
+            assert_eq!(bytes.len(), sect7.size - 5);
+            assert_eq!(
+                &bytes[..],
+                &buf[sect7.offset + 5..sect7.offset + sect7.size]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn section7_bytes_of_empty_section_7() -> Result<(), Box<dyn std::error::Error>> {
+        let message = message_with_all_missing_values()?;
+        let grib2 = crate::from_slice(&message)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        assert!(submessage.section7_bytes()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn decoding_message_with_empty_section_7() -> Result<(), Box<dyn std::error::Error>> {
+        let message = message_with_all_missing_values()?;
+        let grib2 = crate::from_slice(&message)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let values = submessage.values()?;
+        assert_eq!(values.len(), 2949120);
+        assert!(values.iter().all(|v| v.is_nan()));
+        Ok(())
+    }
+
+    #[test]
+    fn message_extraction_from_multi_message_data() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, original) = grib2
+            .iter()
+            .find(|(index, _)| *index == (1, 0))
+            .ok_or("message 1 not found")?;
+        let expected_length = original.indicator().total_length as usize;
+        let expected_param = original.parameter();
+        drop(original);
+
+        let message = grib2.extract_message(1)?;
+        assert_eq!(message.len(), expected_length);
+        assert_eq!(&message[..4], b"GRIB");
+        assert_eq!(&message[message.len() - 4..], b"7777");
+
+        let extracted = crate::from_slice(&message)?;
+        assert_eq!(extracted.len(), 1);
+        let (index, submessage) = extracted.iter().next().ok_or("no submessage")?;
+        assert_eq!(index, (0, 0));
+        assert_eq!(submessage.parameter(), expected_param);
+
+        assert!(grib2.extract_message(grib2.len()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn values_passed_to_callback_without_caching() -> Result<(), Box<dyn std::error::Error>> {
+        for file_name in [
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+            "testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2",
+        ] {
+            let f = File::open(file_name)?;
+            let f = BufReader::new(f);
+            let grib2 = crate::from_reader(f)?;
+
+            for (_, mut submessage) in grib2.iter() {
+                let mut actual = Vec::new();
+                submessage.for_each_value(|value| actual.push(value))?;
+                assert!(submessage.10.get().is_none());
+
+                let expected = submessage.values()?;
+                assert_eq!(actual.len(), expected.len());
+                assert!(actual
+                    .iter()
+                    .zip(expected.iter())
+                    .all(|(a, e)| a == e || (a.is_nan() && e.is_nan())));
+            }
+        }
+        Ok(())
+    }
+
+    struct CountingReader<R> {
+        inner: R,
+        num_bytes_read: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let size = self.inner.read(buf)?;
+            self.num_bytes_read.set(self.num_bytes_read.get() + size);
+            Ok(size)
+        }
+    }
+
+    impl<R: Seek> Seek for CountingReader<R> {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn decoded_values_are_cached() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let num_bytes_read = std::rc::Rc::new(std::cell::Cell::new(0));
+        let f = CountingReader {
+            inner: BufReader::new(f),
+            num_bytes_read: num_bytes_read.clone(),
+        };
+        let grib2 = crate::from_reader(f)?;
+
+        let (_, mut submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        let num_bytes_before_decoding = num_bytes_read.get();
+        let first = submessage.values()?;
+        let num_bytes_after_decoding = num_bytes_read.get();
+        assert!(num_bytes_after_decoding > num_bytes_before_decoding);
+        let second = submessage.values()?;
+        drop(submessage);
+
+        let (_, mut submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        let third = submessage.values()?;
+
+        assert_eq!(num_bytes_read.get(), num_bytes_after_decoding);
+        assert!(std::ptr::eq(first, second));
+        assert!(std::ptr::eq(first, third));
+        Ok(())
+    }
+
+    #[test]
+    fn decoded_values_decoded_again_after_clearing_cache() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let num_bytes_read = std::rc::Rc::new(std::cell::Cell::new(0));
+        let f = CountingReader {
+            inner: BufReader::new(f),
+            num_bytes_read: num_bytes_read.clone(),
+        };
+        let mut grib2 = crate::from_reader(f)?;
+
+        let (_, mut submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        let expected = submessage
+            .values()?
+            .iter()
+            .map(|v| v.to_bits())
+            .collect::<Vec<_>>();
+        drop(submessage);
+
+        grib2.clear_cache();
+        assert!(grib2
+            .decoded_values
+            .iter()
+            .all(|cache| cache.get().is_none()));
+
+        let num_bytes_before_decoding = num_bytes_read.get();
+        let (_, mut submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        let actual = submessage
+            .values()?
+            .iter()
+            .map(|v| v.to_bits())
+            .collect::<Vec<_>>();
+        assert!(num_bytes_read.get() > num_bytes_before_decoding);
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn subgrid_of_jpeg2000_packed_field() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+        let grib2 = crate::from_reader(BufReader::new(f))?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+        let (x, y, width, height) = (100, 50, 30, 20);
+        let actual = submessage.subgrid(x, y, width, height)?;
+        let (ni, _) = submessage.grid_shape()?;
+        let values = submessage.values()?;
+        let expected = (y..y + height)
+            .flat_map(|j| &values[j * ni + x..j * ni + x + width])
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        // taken from the cached values
+        assert_eq!(submessage.subgrid(x, y, width, height)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn subgrid_exceeding_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+        let grib2 = crate::from_reader(BufReader::new(f))?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+        assert!(matches!(
+            submessage.subgrid(1490, 0, 11, 1),
+            Err(GribError::InvalidValueError(_))
+        ));
+        assert!(matches!(
+            submessage.subgrid(0, 751, 1, 1),
+            Err(GribError::InvalidValueError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn resampling_to_coarser_latlon_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+        f.read_to_end(&mut buf)?;
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+        let target = LatLonGridDefinition {
+            ni: 180,
+            nj: 91,
+            first_point_lat: 90_000_000,
+            first_point_lon: 0,
+            last_point_lat: -90_000_000,
+            last_point_lon: 358_000_000,
+            scanning_mode: crate::ScanningMode(0b00000000),
+            points_per_row: None,
+        };
+        let actual = submessage.resample_to(&target)?;
+        assert_eq!(actual.len(), 180 * 91);
+
+        let values = submessage.values()?;
+        for (row, col) in [(0, 0), (10, 25), (45, 90), (60, 179), (90, 179)] {
+            assert_eq!(actual[row * 180 + col], values[row * 8 * 1440 + col * 8]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn resampling_to_grid_beyond_domain() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let grib2 = crate::from_reader(BufReader::new(f))?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+        let target = LatLonGridDefinition {
+            ni: 2,
+            nj: 1,
+            first_point_lat: 35_000_000,
+            first_point_lon: 0,
+            last_point_lat: 35_000_000,
+            last_point_lon: 180_000_000,
+            scanning_mode: crate::ScanningMode(0b00000000),
+            points_per_row: None,
+        };
+        let actual = submessage.resample_to(&target)?;
+        assert!(actual.iter().all(|value| value.is_nan()));
+        Ok(())
+    }
+
+    #[cfg(feature = "time-calculation")]
+    #[test]
+    fn temporal_info_with_negative_forecast_time() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = std::fs::read(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let sect4_offset = {
+            let grib2 = crate::from_slice(&buf)?;
+            let (_, submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+            submessage.4.body.offset
+        };
+        // 10 minutes before the reference time, with the sign bit set
+        buf[sect4_offset + 18..sect4_offset + 22].copy_from_slice(&[0x80, 0x00, 0x00, 0x0a]);
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        assert_eq!(
+            submessage.prod_def().forecast_time(),
+            Some(ForecastTime::from_numbers(0, -10))
+        );
+        let info = submessage.temporal_info();
+        let ref_time = info.ref_time.ok_or("no reference time")?;
+        let target = info.forecast_time_target.ok_or("no target time")?;
+        assert!(target < ref_time);
+        assert_eq!(ref_time - target, chrono::Duration::minutes(10));
+        Ok(())
+    }
+
+    #[test]
+    fn latlons_shared_between_submessages_with_same_grid() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let grib2 = crate::from_reader(BufReader::new(f))?;
+        let offsets = grib2
+            .iter()
+            .map(|(_, submessage)| submessage.3.body.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets[0], offsets[1]);
+
+        let first = grib2.latlons_for_grid(offsets[0])?;
+        let second = grib2.latlons_for_grid(offsets[1])?;
+        assert!(std::ptr::eq(first, second));
+
+        let (_, submessage) = grib2.iter().nth(1).ok_or("submessage not found")?;
+        let expected = submessage.latlons()?.collect::<Vec<_>>();
+        assert_eq!(second, &expected[..]);
+
+        let sect4_offset = submessage.4.body.offset;
+        drop(submessage);
+        assert!(grib2.latlons_for_grid(sect4_offset).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn present_values_of_field_with_bitmap() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let mut f = xz2::bufread::XzDecoder::new(BufReader::new(f));
+        f.read_to_end(&mut buf)?;
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, mut submessage) = grib2.iter().next().ok_or("submessage not found")?;
+
+        let present = submessage.present_values()?.collect::<Vec<_>>();
+        assert_eq!(present.len(), submessage.repr_def().num_points() as usize);
+
+        let values = submessage.values()?;
+        assert!(present
+            .iter()
+            .all(|(i, value)| values[*i].to_bits() == value.to_bits()));
+        let num_non_nan = values.iter().filter(|value| !value.is_nan()).count();
+        assert_eq!(num_non_nan, present.len());
+        Ok(())
+    }
+
     fn get_section_indices<R>(
         submessage: SubMessage<'_, R>,
     ) -> (
@@ -122,7 +122,7 @@
 //!
 //! use grib::{codetables::grib2::*, ForecastTime, Name};
 //!
-//! fn find_submessages<P>(path: P, forecast_time_hours: u32)
+//! fn find_submessages<P>(path: P, forecast_time_hours: i32)
 //! where
 //!     P: AsRef<Path>,
 //! {
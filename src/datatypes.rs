@@ -1,3 +1,5 @@
+mod local;
+pub use local::*;
 mod product_attributes;
 pub use product_attributes::*;
 mod sections;
@@ -5,6 +7,7 @@ pub use sections::*;
 
 pub type MessageIndex = (usize, usize);
 
+#[derive(Clone)]
 pub(crate) struct Grib2SubmessageIndex(
     pub(crate) usize,
     pub(crate) usize,
@@ -0,0 +1,4 @@
+mod ecmwf;
+pub use ecmwf::*;
+mod ncep;
+pub use ncep::*;
@@ -0,0 +1,113 @@
+use crate::{error::*, helpers::read_as};
+
+/// Local use data defined by ECMWF in Section 2.
+///
+/// Only local definition number 1 (MARS labelling or ensemble forecast data)
+/// is supported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EcmwfLocalUse {
+    /// Local definition number.
+    pub local_def_num: u16,
+    /// MARS class (e.g. 1 for operational archive).
+    pub class: u8,
+    /// MARS type (e.g. 10 for control forecast and 11 for perturbed
+    /// forecast).
+    pub data_type: u8,
+    /// MARS stream (e.g. 1035 for ensemble forecasts).
+    pub stream: u16,
+    /// Experiment version, consisting of 4 ASCII characters.
+    pub experiment_version: String,
+    /// Ensemble member number.
+    pub perturbation_number: u8,
+    /// Number of forecasts in the ensemble.
+    pub num_forecasts_in_ensemble: u8,
+}
+
+impl EcmwfLocalUse {
+    /// Parses the payload of Section 2, i.e. octets after the section header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let payload = [
+    ///     0x00, 0x01, 0x01, 0x0b, 0x04, 0x0b, 0x30, 0x30, 0x30, 0x31, 0x05, 0x33, 0x00,
+    /// ];
+    /// let local_use = grib::EcmwfLocalUse::from_bytes(&payload).unwrap();
+    /// assert_eq!(local_use.experiment_version, "0001");
+    /// assert!(local_use.is_perturbed_forecast());
+    /// ```
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, GribError> {
+        if buf.len() < 12 {
+            return Err(GribError::InvalidValueError(format!(
+                "ECMWF local use data is too short: {}",
+                buf.len()
+            )));
+        }
+
+        let local_def_num = read_as!(u16, buf, 0);
+        if local_def_num != 1 {
+            return Err(GribError::NotSupported(format!(
+                "ECMWF local definition number {local_def_num}"
+            )));
+        }
+
+        Ok(Self {
+            local_def_num,
+            class: buf[2],
+            data_type: buf[3],
+            stream: read_as!(u16, buf, 4),
+            experiment_version: String::from_utf8_lossy(&buf[6..10]).into_owned(),
+            perturbation_number: buf[10],
+            num_forecasts_in_ensemble: buf[11],
+        })
+    }
+
+    /// Returns `true` if the data is a control forecast of an ensemble.
+    pub fn is_control_forecast(&self) -> bool {
+        self.data_type == 10
+    }
+
+    /// Returns `true` if the data is a perturbed forecast of an ensemble.
+    pub fn is_perturbed_forecast(&self) -> bool {
+        self.data_type == 11
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ecmwf_local_use_of_ensemble_member() {
+        // Section 2 payload of a perturbed member of ECMWF ENS
+        let payload = [
+            0x00, 0x01, 0x01, 0x0b, 0x04, 0x0b, 0x30, 0x30, 0x30, 0x31, 0x05, 0x33, 0x00,
+        ];
+        let actual = EcmwfLocalUse::from_bytes(&payload).unwrap();
+        let expected = EcmwfLocalUse {
+            local_def_num: 1,
+            class: 1,
+            data_type: 11,
+            stream: 1035,
+            experiment_version: "0001".to_owned(),
+            perturbation_number: 5,
+            num_forecasts_in_ensemble: 51,
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_control_forecast());
+        assert!(actual.is_perturbed_forecast());
+    }
+
+    #[test]
+    fn parse_ecmwf_local_use_with_unsupported_definition() {
+        let payload = [
+            0x00, 0x24, 0x01, 0x0b, 0x04, 0x0b, 0x30, 0x30, 0x30, 0x31, 0x05, 0x33, 0x00,
+        ];
+        assert_eq!(
+            EcmwfLocalUse::from_bytes(&payload),
+            Err(GribError::NotSupported(
+                "ECMWF local definition number 36".to_owned()
+            ))
+        );
+    }
+}
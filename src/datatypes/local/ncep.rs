@@ -0,0 +1,117 @@
+use crate::error::*;
+
+/// Local use data defined by NCEP in Section 2.
+///
+/// Only the ensemble extension (local section identifier 1), which carries
+/// the ensemble information formerly stored in octets 41-45 of the GRIB1 PDS
+/// extension, is supported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NcepLocalUse {
+    /// Local section identifier.
+    pub local_section_id: u8,
+    /// Application identifier (1 for ensemble).
+    pub application_id: u8,
+    /// Type of ensemble forecast (1 for unperturbed control forecast, 2 and 3
+    /// for individual negatively and positively perturbed forecasts, 4 for
+    /// cluster and 5 for whole ensemble).
+    pub ensemble_type: u8,
+    /// Identification number of the ensemble member or cluster.
+    pub identification_num: u8,
+    /// Product identifier (1 for full field individual forecast, 2 for
+    /// weighted mean, etc.).
+    pub product_id: u8,
+    /// Spatial smoothing of the product (255 for original resolution).
+    pub spatial_smoothing: u8,
+}
+
+impl NcepLocalUse {
+    /// Parses the payload of Section 2, i.e. octets after the section header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let payload = [0x01, 0x01, 0x03, 0x05, 0x01, 0xff];
+    /// let local_use = grib::NcepLocalUse::from_bytes(&payload).unwrap();
+    /// assert_eq!(local_use.identification_num, 5);
+    /// assert!(local_use.is_perturbed_forecast());
+    /// ```
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, GribError> {
+        if buf.len() < 6 {
+            return Err(GribError::InvalidValueError(format!(
+                "NCEP local use data is too short: {}",
+                buf.len()
+            )));
+        }
+
+        let local_section_id = buf[0];
+        if local_section_id != 1 {
+            return Err(GribError::NotSupported(format!(
+                "NCEP local section identifier {local_section_id}"
+            )));
+        }
+
+        Ok(Self {
+            local_section_id,
+            application_id: buf[1],
+            ensemble_type: buf[2],
+            identification_num: buf[3],
+            product_id: buf[4],
+            spatial_smoothing: buf[5],
+        })
+    }
+
+    /// Returns `true` if the data is a control forecast of an ensemble.
+    pub fn is_control_forecast(&self) -> bool {
+        self.ensemble_type == 1
+    }
+
+    /// Returns `true` if the data is a perturbed forecast of an ensemble.
+    pub fn is_perturbed_forecast(&self) -> bool {
+        matches!(self.ensemble_type, 2 | 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ncep_local_use_of_ensemble_member() {
+        // Section 2 payload of a positively perturbed member of GEFS
+        let payload = [0x01, 0x01, 0x03, 0x0a, 0x01, 0xff];
+        let actual = NcepLocalUse::from_bytes(&payload).unwrap();
+        let expected = NcepLocalUse {
+            local_section_id: 1,
+            application_id: 1,
+            ensemble_type: 3,
+            identification_num: 10,
+            product_id: 1,
+            spatial_smoothing: 255,
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_control_forecast());
+        assert!(actual.is_perturbed_forecast());
+    }
+
+    #[test]
+    fn parse_ncep_local_use_with_unsupported_identifier() {
+        let payload = [0x02, 0x01, 0x03, 0x0a, 0x01, 0xff];
+        assert_eq!(
+            NcepLocalUse::from_bytes(&payload),
+            Err(GribError::NotSupported(
+                "NCEP local section identifier 2".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_too_short_ncep_local_use() {
+        let payload = [0x01, 0x01, 0x03];
+        assert_eq!(
+            NcepLocalUse::from_bytes(&payload),
+            Err(GribError::InvalidValueError(
+                "NCEP local use data is too short: 3".to_owned()
+            ))
+        );
+    }
+}
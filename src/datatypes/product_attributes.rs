@@ -12,7 +12,8 @@ use crate::codetables::{grib2::*, *};
 /// third-party code, such as [`NCEP`].
 ///
 /// [`is_identical_to`]: Parameter::is_identical_to
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parameter {
     /// Discipline of processed data in the GRIB message.
     pub discipline: u8,
@@ -51,6 +52,26 @@ impl Parameter {
             .description()
     }
 
+    /// Looks up the unit of the parameter given in the WMO table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Extracted from the first submessage of JMA MSM GRIB2 data.
+    /// let param = grib::Parameter {
+    ///     discipline: 0,
+    ///     centre: 34,
+    ///     master_ver: 2,
+    ///     local_ver: 1,
+    ///     category: 3,
+    ///     num: 5,
+    /// };
+    /// assert_eq!(param.unit(), Some("gpm"))
+    /// ```
+    pub fn unit(&self) -> Option<&'static str> {
+        CodeTable4_2::new(self.discipline, self.category).unit(usize::from(self.num))
+    }
+
     /// Checks if the parameter is identical to a third-party `code`, such as
     /// [`NCEP`].
     ///
@@ -79,23 +100,72 @@ impl Parameter {
         self_.is_ok_and(|v| v == code)
     }
 
+    /// Returns the physical quantity of the parameter normalized across
+    /// originating centres, or `None` if the code is not known.
+    ///
+    /// WMO codes are mapped regardless of the originating centre, while local
+    /// codes (category or number 192 and above) are mapped only for the centre
+    /// that defines them and only if local tables are used, i.e. the local
+    /// tables version number is not 255.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::codetables::CanonicalParameter;
+    ///
+    /// // NCEP's local code for MSLET (MSLP (Eta model reduction)).
+    /// let mslet = grib::Parameter {
+    ///     discipline: 0,
+    ///     centre: 7,
+    ///     master_ver: 2,
+    ///     local_ver: 1,
+    ///     category: 3,
+    ///     num: 192,
+    /// };
+    /// // WMO's code for pressure reduced to MSL.
+    /// let prmsl = grib::Parameter {
+    ///     discipline: 0,
+    ///     centre: 34,
+    ///     master_ver: 2,
+    ///     local_ver: 1,
+    ///     category: 3,
+    ///     num: 1,
+    /// };
+    /// assert_eq!(mslet.canonical(), prmsl.canonical());
+    /// assert_eq!(
+    ///     mslet.canonical(),
+    ///     Some(CanonicalParameter::PressureReducedToMsl)
+    /// );
+    /// ```
+    pub fn canonical(&self) -> Option<CanonicalParameter> {
+        crate::codetables::canonical_lookup(self)
+    }
+
+    // Category or number 192 and above is reserved for local use.
+    pub(crate) fn is_local(&self) -> bool {
+        self.category >= 192 || self.num >= 192
+    }
+
     pub(crate) fn as_u32(&self) -> u32 {
         (u32::from(self.discipline) << 16) + (u32::from(self.category) << 8) + u32::from(self.num)
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ForecastTime {
     pub unit: Code<grib2::Table4_4, u8>,
-    pub value: u32,
+    /// Forecast time in the unit, which is negative for data valid before
+    /// the reference time.
+    pub value: i32,
 }
 
 impl ForecastTime {
-    pub fn new(unit: Code<grib2::Table4_4, u8>, value: u32) -> Self {
+    pub fn new(unit: Code<grib2::Table4_4, u8>, value: i32) -> Self {
         Self { unit, value }
     }
 
-    pub fn from_numbers(unit: u8, value: u32) -> Self {
+    pub fn from_numbers(unit: u8, value: i32) -> Self {
         let unit = Table4_4::try_from(unit).into();
         Self { unit, value }
     }
@@ -108,21 +178,68 @@ impl ForecastTime {
         let value = self.value.to_string();
         (unit, value)
     }
+
+    /// Converts the forecast time to [`chrono::Duration`].
+    ///
+    /// Returns `None` if the unit does not have a constant length, such as
+    /// months and years, or is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ft = grib::ForecastTime::from_numbers(11, 2);
+    /// assert_eq!(ft.to_duration(), Some(chrono::Duration::hours(12)));
+    /// ```
+    #[cfg(feature = "time-calculation")]
+    pub fn to_duration(&self) -> Option<chrono::Duration> {
+        chrono::Duration::try_seconds(self.seconds()?)
+    }
+
+    /// Returns the forecast time in seconds if the unit has a constant length.
+    pub(crate) fn seconds(&self) -> Option<i64> {
+        match &self.unit {
+            Name(unit) => unit
+                .seconds()
+                .map(|seconds| i64::from(seconds) * i64::from(self.value)),
+            Num(_) => None,
+        }
+    }
 }
 
+/// Formats the forecast time with the unit abbreviated as in
+/// [`Table4_4::short_expr`].
+///
+/// The unit is enclosed in brackets by default. With the alternate flag
+/// (`{:#}`), the unit follows the value without brackets.
+///
+/// # Examples
+///
+/// ```
+/// let ft = grib::ForecastTime::from_numbers(1, 3);
+/// assert_eq!(ft.to_string(), "3 [h]");
+/// assert_eq!(format!("{ft:#}"), "3 h");
+/// ```
 impl Display for ForecastTime {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}", self.value)?;
 
-        match &self.unit {
-            Name(unit) => {
+        match (&self.unit, f.alternate()) {
+            (Name(unit), false) => {
                 if let Some(expr) = unit.short_expr() {
                     write!(f, " [{expr}]")?;
                 }
             }
-            Num(num) => {
+            (Name(unit), true) => {
+                if let Some(expr) = unit.short_expr() {
+                    write!(f, " {expr}")?;
+                }
+            }
+            (Num(num), false) => {
                 write!(f, " [unit: {num}]")?;
             }
+            (Num(num), true) => {
+                write!(f, " (unit: {num})")?;
+            }
         }
 
         Ok(())
@@ -130,6 +247,7 @@ impl Display for ForecastTime {
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedSurface {
     /// Use [CodeTable4_5] to get textual representation.
     pub surface_type: u8,
@@ -146,13 +264,27 @@ impl FixedSurface {
         }
     }
 
+    /// Returns the value of the surface, or NaN if either the scale factor or
+    /// the scaled value is missing.
     pub fn value(&self) -> f64 {
-        if self.value_is_nan() {
-            f64::NAN
-        } else {
-            let factor: f64 = 10_f64.powi(-i32::from(self.scale_factor));
-            f64::from(self.scaled_value) * factor
-        }
+        scaled_value_to_f64(self.scale_factor, self.scaled_value)
+    }
+
+    /// Returns the value of the surface, or `None` if either the scale factor
+    /// or the scaled value is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(grib::FixedSurface::new(103, 0, 2).checked_value(), Some(2.));
+    /// assert_eq!(grib::FixedSurface::new(103, 0, 0).checked_value(), Some(0.));
+    /// assert_eq!(
+    ///     grib::FixedSurface::new(103, i8::MIN + 1, 0).checked_value(),
+    ///     None
+    /// );
+    /// ```
+    pub fn checked_value(&self) -> Option<f64> {
+        (!self.is_missing()).then(|| self.value())
     }
 
     /// Returns the unit string defined for the type of the surface, if any.
@@ -216,6 +348,12 @@ impl FixedSurface {
         self.scaled_value == i32::MIN + 1
     }
 
+    /// Checks if the surface value should be treated as missing, i.e. if either
+    /// the scale factor or the scaled value is missing.
+    pub fn is_missing(&self) -> bool {
+        self.scale_factor_is_nan() || self.value_is_nan()
+    }
+
     pub fn describe(&self) -> (String, String, String) {
         let stype = CodeTable4_5
             .lookup(usize::from(self.surface_type))
@@ -233,3 +371,352 @@ impl FixedSurface {
         (stype, scale_factor, scaled_value)
     }
 }
+
+/// Probability information of products defined with Product Definition
+/// Templates 4.5 and 4.9.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProbabilitySpec {
+    pub forecast_probability_number: u8,
+    pub total_num_forecast_probabilities: u8,
+    /// Probability type defined in Code Table 4.9.
+    pub probability_type: u8,
+    pub lower_limit_scale_factor: i8,
+    pub lower_limit_scaled_value: i32,
+    pub upper_limit_scale_factor: i8,
+    pub upper_limit_scaled_value: i32,
+}
+
+impl ProbabilitySpec {
+    /// Returns the lower limit, or NaN if it is missing.
+    pub fn lower_limit(&self) -> f64 {
+        scaled_value_to_f64(self.lower_limit_scale_factor, self.lower_limit_scaled_value)
+    }
+
+    /// Returns the upper limit, or NaN if it is missing.
+    pub fn upper_limit(&self) -> f64 {
+        scaled_value_to_f64(self.upper_limit_scale_factor, self.upper_limit_scaled_value)
+    }
+}
+
+fn scaled_value_to_f64(scale_factor: i8, scaled_value: i32) -> f64 {
+    // Handle as NaN if all bits are 1, as in `FixedSurface`.
+    if scale_factor == i8::MIN + 1 || scaled_value == i32::MIN + 1 {
+        f64::NAN
+    } else {
+        let factor: f64 = 10_f64.powi(-i32::from(scale_factor));
+        f64::from(scaled_value) * factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "time-calculation")]
+    macro_rules! test_forecast_time_conversion_to_duration {
+        ($(($name:ident, $unit:expr, $value:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let ft = ForecastTime::from_numbers($unit, $value);
+                assert_eq!(ft.to_duration(), $expected);
+            }
+        )*);
+    }
+
+    #[cfg(feature = "time-calculation")]
+    test_forecast_time_conversion_to_duration! {
+        (
+            forecast_time_conversion_to_duration_for_minutes,
+            0, 30, Some(chrono::Duration::minutes(30))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_hours,
+            1, 3, Some(chrono::Duration::hours(3))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_days,
+            2, 2, Some(chrono::Duration::days(2))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_months,
+            3, 1, None
+        ),
+        (
+            forecast_time_conversion_to_duration_for_3_hours,
+            10, 2, Some(chrono::Duration::hours(6))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_6_hours,
+            11, 2, Some(chrono::Duration::hours(12))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_12_hours,
+            12, 2, Some(chrono::Duration::hours(24))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_seconds,
+            13, 90, Some(chrono::Duration::seconds(90))
+        ),
+        (
+            forecast_time_conversion_to_duration_for_missing_unit,
+            255, 0, None
+        ),
+        (
+            forecast_time_conversion_to_duration_for_unknown_unit,
+            254, 0, None
+        ),
+    }
+
+    macro_rules! test_forecast_time_formatting {
+        ($(($name:ident, $ft:expr, $expected:expr, $expected_alternate:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let ft = $ft;
+                assert_eq!(ft.to_string(), $expected);
+                assert_eq!(format!("{ft:#}"), $expected_alternate);
+            }
+        )*);
+    }
+
+    test_forecast_time_formatting! {
+        (
+            forecast_time_formatting_for_hours,
+            ForecastTime::new(Name(Table4_4::Hour), 3),
+            "3 [h]",
+            "3 h"
+        ),
+        (
+            forecast_time_formatting_for_minutes,
+            ForecastTime::new(Name(Table4_4::Minute), 10),
+            "10 [m]",
+            "10 m"
+        ),
+        (
+            forecast_time_formatting_for_missing_unit,
+            ForecastTime::new(Name(Table4_4::Missing), 0),
+            "0",
+            "0"
+        ),
+        (
+            forecast_time_formatting_for_unknown_unit,
+            ForecastTime::new(Num(200), 1),
+            "1 [unit: 200]",
+            "1 (unit: 200)"
+        ),
+    }
+
+    macro_rules! test_fixed_surface_value {
+        ($(($name:ident, $surface:expr, $expected_value:expr, $expected_description:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let surface = $surface;
+                let expected_value: Option<f64> = $expected_value;
+                assert_eq!(surface.checked_value(), expected_value);
+                assert_eq!(surface.is_missing(), expected_value.is_none());
+                assert_eq!(surface.value().is_nan(), expected_value.is_none());
+                let (_, scale_factor, scaled_value) = surface.describe();
+                assert_eq!(
+                    (scale_factor.as_str(), scaled_value.as_str()),
+                    $expected_description
+                );
+            }
+        )*);
+    }
+
+    test_fixed_surface_value! {
+        (
+            fixed_surface_value_for_zero,
+            FixedSurface::new(103, 0, 0),
+            Some(0.),
+            ("0", "0")
+        ),
+        (
+            fixed_surface_value_with_scale_factor,
+            FixedSurface::new(103, 1, 15),
+            Some(1.5),
+            ("1", "15")
+        ),
+        (
+            fixed_surface_value_for_missing_surface,
+            FixedSurface::new(255, i8::MIN + 1, i32::MIN + 1),
+            None,
+            ("Missing", "Missing")
+        ),
+        (
+            fixed_surface_value_with_missing_scale_factor,
+            FixedSurface::new(103, i8::MIN + 1, 0),
+            None,
+            ("Missing", "0")
+        ),
+        (
+            fixed_surface_value_with_missing_scaled_value,
+            FixedSurface::new(103, 0, i32::MIN + 1),
+            None,
+            ("0", "Missing")
+        ),
+    }
+
+    #[test]
+    fn forecast_time_in_seconds() {
+        assert_eq!(ForecastTime::from_numbers(1, 3).seconds(), Some(10800));
+        assert_eq!(ForecastTime::from_numbers(4, 1).seconds(), None);
+    }
+
+    macro_rules! test_canonical_parameter {
+        ($(($name:ident, $centre:expr, $category:expr, $num:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let param = Parameter {
+                    discipline: 0,
+                    centre: $centre,
+                    master_ver: 2,
+                    local_ver: 1,
+                    category: $category,
+                    num: $num,
+                };
+                assert_eq!(param.canonical(), $expected);
+            }
+        )*);
+    }
+
+    test_canonical_parameter! {
+        (
+            canonical_parameter_for_wmo_total_precipitation_rate,
+            34, 1, 52, Some(CanonicalParameter::TotalPrecipitationRate)
+        ),
+        (
+            canonical_parameter_for_jma_local_precipitation_intensity,
+            34, 193, 0, Some(CanonicalParameter::PrecipitationRate)
+        ),
+        (
+            canonical_parameter_for_jma_local_hourly_precipitation,
+            34, 1, 200, Some(CanonicalParameter::TotalPrecipitation)
+        ),
+        (
+            canonical_parameter_for_ncep_precipitation,
+            7, 1, 7, Some(CanonicalParameter::PrecipitationRate)
+        ),
+        (
+            canonical_parameter_for_ncep_total_precipitation,
+            7, 1, 8, Some(CanonicalParameter::TotalPrecipitation)
+        ),
+        (
+            canonical_parameter_for_jma_temperature,
+            34, 0, 0, Some(CanonicalParameter::Temperature)
+        ),
+        (
+            canonical_parameter_for_ncep_local_mslp,
+            7, 3, 192, Some(CanonicalParameter::PressureReducedToMsl)
+        ),
+        (
+            canonical_parameter_for_local_code_of_other_centre,
+            34, 3, 192, None
+        ),
+        (
+            canonical_parameter_for_unknown_code,
+            34, 3, 5, None
+        ),
+    }
+
+    #[test]
+    fn canonical_parameter_of_jma_local_precipitation_matches_ncep_one(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let f = std::fs::File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+
+        let (_, submessage) = grib2.iter().next().ok_or("no submessages found")?;
+        let jma_prate = submessage.parameter().ok_or("parameter not found")?;
+        assert_eq!(jma_prate.centre, 34);
+        assert!(jma_prate.is_local());
+        assert_ne!(jma_prate.local_ver, 255);
+
+        let ncep_prate = Parameter {
+            discipline: 0,
+            centre: 7,
+            master_ver: 2,
+            local_ver: 1,
+            category: 1,
+            num: 7,
+        };
+        assert_eq!(jma_prate.canonical(), ncep_prate.canonical());
+        assert_eq!(
+            jma_prate.canonical(),
+            Some(CanonicalParameter::PrecipitationRate)
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parameter_serialization_to_json() -> Result<(), Box<dyn std::error::Error>> {
+        let param = Parameter {
+            discipline: 0,
+            centre: 34,
+            master_ver: 2,
+            local_ver: 1,
+            category: 3,
+            num: 5,
+        };
+        let json = serde_json::to_string(&param)?;
+        assert_eq!(
+            json,
+            r#"{"discipline":0,"centre":34,"master_ver":2,"local_ver":1,"category":3,"num":5}"#
+        );
+        assert_eq!(serde_json::from_str::<Parameter>(&json)?, param);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn forecast_time_serialization_to_json() -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string(&ForecastTime::from_numbers(1, 3))?;
+        assert_eq!(json, r#"{"unit":{"Name":"Hour"},"value":3}"#);
+        let json = serde_json::to_string(&ForecastTime::from_numbers(100, 3))?;
+        assert_eq!(json, r#"{"unit":{"Num":100},"value":3}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn lookup_of_parameter_from_centre_using_only_master_table() {
+        // ECMWF data with local tables not used
+        let param = Parameter {
+            discipline: 0,
+            centre: 98,
+            master_ver: 27,
+            local_ver: 255,
+            category: 3,
+            num: 1,
+        };
+        assert_eq!(
+            param.description(),
+            Some("Pressure reduced to MSL".to_owned())
+        );
+        assert!(param.is_identical_to(NCEP::PRMSL));
+        assert_eq!(
+            param.canonical(),
+            Some(CanonicalParameter::PressureReducedToMsl)
+        );
+    }
+
+    #[test]
+    fn lookup_of_local_parameter_with_local_tables_not_used() {
+        let mut param = Parameter {
+            discipline: 0,
+            centre: 7,
+            master_ver: 2,
+            local_ver: 1,
+            category: 3,
+            num: 192,
+        };
+        assert_eq!(
+            param.canonical(),
+            Some(CanonicalParameter::PressureReducedToMsl)
+        );
+        param.local_ver = 255;
+        assert_eq!(param.canonical(), None);
+    }
+}
@@ -1,19 +1,29 @@
-use std::slice::Iter;
+use std::{
+    fmt::{self, Display, Formatter},
+    slice::Iter,
+};
 
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
 
 use crate::{
-    codetables::SUPPORTED_PROD_DEF_TEMPLATE_NUMBERS,
+    codetables::{
+        grib2::{Table1_3, Table1_4},
+        ArrayLookup, Code, CodeTable0_0, SUPPORTED_PROD_DEF_TEMPLATE_NUMBERS,
+    },
     datatypes::*,
     error::*,
     grid::{
-        GaussianGridDefinition, GridPointIterator, LambertGridDefinition, LatLonGridDefinition,
+        EarthShape, GaussianGridDefinition, GridPointIterator,
+        LambertAzimuthalEqualAreaGridDefinition, LambertGridDefinition, LatLonGridDefinition,
+        SpaceViewGridDefinition, SphericalHarmonicDefinition,
     },
     helpers::{read_as, GribInt},
+    reader::{SECT0_IS_MAGIC, SECT0_IS_SIZE},
     GridPointIndexIterator, PolarStereographicGridDefinition,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Indicator {
     /// Discipline - GRIB Master Table Number (see Code Table 0.0)
     pub discipline: u8,
@@ -36,6 +46,73 @@ impl Indicator {
             total_length,
         })
     }
+
+    /// Returns the name of the discipline in Code Table 0.0, or `None` if the
+    /// code is reserved or unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let indicator = grib::Indicator {
+    ///     discipline: 10,
+    ///     total_length: 0,
+    /// };
+    /// assert_eq!(indicator.discipline_name(), Some("Oceanographic products"));
+    /// ```
+    pub fn discipline_name(&self) -> Option<&'static str> {
+        CodeTable0_0
+            .data()
+            .get(usize::from(self.discipline))
+            .copied()
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl TryFrom<&[u8]> for Indicator {
+    type Error = ParseError;
+
+    /// Parses Section 0 at the beginning of the slice, checking the magic
+    /// "GRIB" and the edition number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let buf = b"GRIB\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\xc1";
+    /// let indicator = grib::Indicator::try_from(&buf[..]).unwrap();
+    /// assert_eq!(indicator.discipline, 0);
+    /// assert_eq!(indicator.total_length, 193);
+    ///
+    /// assert!(grib::Indicator::try_from(&b"GRIB"[..]).is_err());
+    /// ```
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let magic_len = slice.len().min(SECT0_IS_MAGIC.len());
+        if slice[..magic_len] != SECT0_IS_MAGIC[..magic_len] {
+            return Err(ParseError::NotGRIB);
+        }
+        if slice.len() < SECT0_IS_SIZE {
+            return Err(ParseError::UnexpectedEndOfData(slice.len()));
+        }
+        Self::from_slice(slice)
+    }
+}
+
+/// Checks if the byte slice begins with Section 0 of a GRIB2 message.
+///
+/// Only the magic "GRIB" and the edition number in Section 0 are checked, so
+/// this is suitable for detection of the data format.
+///
+/// # Examples
+///
+/// ```
+/// let buf = std::fs::read(
+///     "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+/// )
+/// .unwrap();
+/// assert!(grib::is_grib2(&buf));
+/// assert!(!grib::is_grib2(b"CDF\x01"));
+/// ```
+pub fn is_grib2(bytes: &[u8]) -> bool {
+    Indicator::try_from(bytes).is_ok()
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -111,11 +188,45 @@ impl Identification {
         self.payload[14]
     }
 
+    /// Production status of processed data in this GRIB message as a value of
+    /// Code Table 1.3
+    pub fn prod_status_code(&self) -> Code<Table1_3, u8> {
+        Table1_3::try_from(self.prod_status()).into()
+    }
+
     /// Type of processed data in this GRIB message (see Code Table 1.4)
     #[inline]
     pub fn data_type(&self) -> u8 {
         self.payload[15]
     }
+
+    /// Type of processed data in this GRIB message as a value of Code Table
+    /// 1.4
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::{
+    ///     codetables::{grib2::Table1_4, Code},
+    ///     Identification,
+    /// };
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let payload = vec![
+    ///         0x00, 0x22, 0x00, 0x00, 0x0e, 0x00, 0x01, 0x07, 0xe0, 0x08, 0x16, 0x02, 0x00, 0x00,
+    ///         0x00, 0x02,
+    ///     ];
+    ///     let ident = Identification::from_payload(payload.into_boxed_slice())?;
+    ///     assert_eq!(
+    ///         ident.data_type_code(),
+    ///         Code::Name(Table1_4::AnalysisAndForecastProducts)
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn data_type_code(&self) -> Code<Table1_4, u8> {
+        Table1_4::try_from(self.data_type()).into()
+    }
 }
 
 #[inline]
@@ -150,6 +261,10 @@ impl LocalUse {
     pub fn iter(&self) -> Iter<u8> {
         self.payload.iter()
     }
+
+    pub(crate) fn payload(&self) -> &[u8] {
+        &self.payload
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -171,25 +286,129 @@ impl GridDefinition {
         self.payload.iter()
     }
 
+    /// Source of grid definition defined in Code Table 3.0.
+    ///
+    /// 0 means that the grid is specified in the grid definition template,
+    /// and other values mean that the grid is predetermined by the
+    /// originating centre or by other means, which this library cannot
+    /// interpret.
+    pub fn source_of_grid_definition(&self) -> u8 {
+        self.payload[0]
+    }
+
     /// Number of data points
     pub fn num_points(&self) -> u32 {
         let payload = &self.payload;
         read_as!(u32, payload, 1)
     }
 
+    /// Returns the number of data points, cross-checked against the optional
+    /// list of numbers defining the number of points if it is present.
+    ///
+    /// For thinned (reduced) grids, the number of data points should be equal
+    /// to the sum of the numbers in the list. An error is returned if they do
+    /// not match.
+    pub fn effective_num_points(&self) -> Result<u32, GribError> {
+        let num_points = self.num_points();
+        let Some(list) = self.optional_point_list() else {
+            return Ok(num_points);
+        };
+        let sum = list.iter().map(|n| u64::from(*n)).sum::<u64>();
+        if sum == u64::from(num_points) {
+            Ok(num_points)
+        } else {
+            Err(GribError::InvalidValueError(format!(
+                "number of data points does not match: {num_points} (defined) vs {sum} (sum of list of numbers of points)"
+            )))
+        }
+    }
+
     /// Grid Definition Template Number
     pub fn grid_tmpl_num(&self) -> u16 {
         let payload = &self.payload;
         read_as!(u16, payload, 7)
     }
+
+    /// Returns the optional list of numbers defining the number of points,
+    /// which follows the template in thinned (reduced) grids.
+    ///
+    /// The list is parsed according to the number of octets for each number
+    /// in octet 11. `None` is returned if the list is not present or if the
+    /// template is not known to have the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_, submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     assert_eq!(submessage.grid_def().optional_point_list(), None);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn optional_point_list(&self) -> Option<Vec<u32>> {
+        let payload = &self.payload;
+        let num_octets = usize::from(*payload.get(5)?);
+        if !(1..=4).contains(&num_octets) {
+            return None;
+        }
+        let start = match self.grid_tmpl_num() {
+            0 | 40 => 67,
+            1 | 2 | 41 | 42 => 79,
+            3 | 43 => 91,
+            _ => return None,
+        };
+
+        let list = payload
+            .get(start..)?
+            .chunks_exact(num_octets)
+            .map(|octets| {
+                octets
+                    .iter()
+                    .fold(0_u32, |acc, octet| (acc << 8) | u32::from(*octet))
+            })
+            .collect();
+        Some(list)
+    }
+
+    /// Reads the list of numbers of points along each row, checking that the
+    /// list is interpreted as such.
+    pub(crate) fn points_per_row(&self) -> Result<Vec<u32>, GribError> {
+        let payload = &self.payload;
+        let (Some(&num_octets), Some(&interpretation)) = (payload.get(5), payload.get(6)) else {
+            return Err(GribError::InvalidValueError(
+                "Section 3 is too short for list of numbers of points".to_owned(),
+            ));
+        };
+        if interpretation != 1 {
+            return Err(GribError::NotSupported(format!(
+                "interpretation {interpretation} of list of numbers of points"
+            )));
+        }
+        self.optional_point_list().ok_or_else(|| {
+            GribError::InvalidValueError(format!(
+                "number of octets for list of numbers of points: {num_octets}"
+            ))
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum GridDefinitionTemplateValues {
     Template0(LatLonGridDefinition),
     Template20(PolarStereographicGridDefinition),
     Template30(LambertGridDefinition),
     Template40(GaussianGridDefinition),
+    Template50(SphericalHarmonicDefinition),
+    Template90(SpaceViewGridDefinition),
+    Template140(LambertAzimuthalEqualAreaGridDefinition),
 }
 
 impl GridDefinitionTemplateValues {
@@ -201,6 +420,9 @@ impl GridDefinitionTemplateValues {
             Self::Template20(def) => def.grid_shape(),
             Self::Template30(def) => def.grid_shape(),
             Self::Template40(def) => def.grid_shape(),
+            Self::Template50(def) => def.grid_shape(),
+            Self::Template90(def) => def.grid_shape(),
+            Self::Template140(def) => def.grid_shape(),
         }
     }
 
@@ -218,6 +440,23 @@ impl GridDefinitionTemplateValues {
             Self::Template20(def) => def.short_name(),
             Self::Template30(def) => def.short_name(),
             Self::Template40(def) => def.short_name(),
+            Self::Template50(def) => def.short_name(),
+            Self::Template90(def) => def.short_name(),
+            Self::Template140(def) => def.short_name(),
+        }
+    }
+
+    /// Returns the shape of the Earth used in the grid definition.
+    ///
+    /// `None` is returned if the value of Code Table 3.2 is unknown, or if the
+    /// template is not associated with the shape of the Earth in this library.
+    pub fn earth_shape(&self) -> Option<EarthShape> {
+        match self {
+            Self::Template20(def) => def.earth_shape.shape(),
+            Self::Template30(def) => def.earth_shape.shape(),
+            Self::Template90(def) => def.earth_shape.shape(),
+            Self::Template140(def) => def.earth_shape.shape(),
+            Self::Template0(_) | Self::Template40(_) | Self::Template50(_) => None,
         }
     }
 
@@ -232,6 +471,9 @@ impl GridDefinitionTemplateValues {
             Self::Template20(def) => def.ij(),
             Self::Template30(def) => def.ij(),
             Self::Template40(def) => def.ij(),
+            Self::Template50(_) => Err(SphericalHarmonicDefinition::not_grid_point_error()),
+            Self::Template90(def) => def.ij(),
+            Self::Template140(def) => def.ij(),
         }
     }
 
@@ -243,12 +485,20 @@ impl GridDefinitionTemplateValues {
     /// defined in the data.
     pub fn latlons(&self) -> Result<GridPointIterator, GribError> {
         let iter = match self {
-            Self::Template0(def) => GridPointIterator::LatLon(def.latlons()?),
+            Self::Template0(def) => def.latlons()?,
             #[cfg(feature = "gridpoints-proj")]
             Self::Template20(def) => GridPointIterator::Lambert(def.latlons()?),
             #[cfg(feature = "gridpoints-proj")]
             Self::Template30(def) => GridPointIterator::Lambert(def.latlons()?),
             Self::Template40(def) => GridPointIterator::LatLon(def.latlons()?),
+            Self::Template50(_) => return Err(SphericalHarmonicDefinition::not_grid_point_error()),
+            Self::Template90(_) => {
+                return Err(GribError::NotSupported(
+                    "lat/lon computation for space view perspective or orthographic grids"
+                        .to_owned(),
+                ))
+            }
+            Self::Template140(def) => GridPointIterator::Lambert(def.latlons()?),
             #[cfg(not(feature = "gridpoints-proj"))]
             _ => {
                 return Err(GribError::NotSupported(
@@ -261,22 +511,105 @@ impl GridDefinitionTemplateValues {
     }
 }
 
+impl Display for GridDefinitionTemplateValues {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (ni, nj) = self.grid_shape();
+        write!(f, "{} {ni}x{nj}", self.short_name())?;
+        match self {
+            Self::Template0(def) => {
+                let di = increment(def.first_point_lon, def.last_point_lon, ni);
+                let dj = increment(def.first_point_lat, def.last_point_lat, nj);
+                write!(
+                    f,
+                    ", first point ({}, {}), last point ({}, {}), increments ({di}, {dj})",
+                    degrees(def.first_point_lat),
+                    degrees(def.first_point_lon),
+                    degrees(def.last_point_lat),
+                    degrees(def.last_point_lon),
+                )
+            }
+            Self::Template20(def) => write!(
+                f,
+                ", first point ({}, {}), LaD {}, LoV {}, Dx {} m, Dy {} m",
+                degrees(def.first_point_lat),
+                degrees(def.first_point_lon),
+                degrees(def.lad),
+                degrees(def.lov),
+                f64::from(def.dx) / 1e3,
+                f64::from(def.dy) / 1e3,
+            ),
+            Self::Template30(def) => write!(
+                f,
+                ", first point ({}, {}), LaD {}, LoV {}, Dx {} m, Dy {} m",
+                degrees(def.first_point_lat),
+                degrees(def.first_point_lon),
+                degrees(def.lad),
+                degrees(def.lov),
+                f64::from(def.dx) / 1e3,
+                f64::from(def.dy) / 1e3,
+            ),
+            Self::Template40(def) => write!(
+                f,
+                ", first point ({}, {}), last point ({}, {}), Di {}, N {}",
+                degrees(def.first_point_lat),
+                degrees(def.first_point_lon),
+                degrees(def.last_point_lat),
+                degrees(def.last_point_lon),
+                degrees(def.i_direction_inc as i32),
+                def.n,
+            ),
+            Self::Template50(def) => write!(f, ", J {}, K {}, M {}", def.j, def.k, def.m),
+            Self::Template90(def) => write!(
+                f,
+                ", sub-satellite point ({}, {}), Nr {}",
+                degrees(def.sub_satellite_point_lat),
+                degrees(def.sub_satellite_point_lon),
+                f64::from(def.nr) / 1e6,
+            ),
+            Self::Template140(def) => write!(
+                f,
+                ", first point ({}, {}), standard parallel {}, central longitude {}, Dx {} m, Dy {} m",
+                degrees(def.first_point_lat),
+                degrees(def.first_point_lon),
+                degrees(def.standard_parallel),
+                degrees(def.central_lon),
+                f64::from(def.dx) / 1e3,
+                f64::from(def.dy) / 1e3,
+            ),
+        }
+    }
+}
+
+fn degrees(microdegrees: i32) -> f64 {
+    f64::from(microdegrees) / 1e6
+}
+
+fn increment(first: i32, last: i32, num_points: usize) -> f64 {
+    if num_points < 2 {
+        return 0.;
+    }
+    (degrees(last) - degrees(first)).abs() / (num_points - 1) as f64
+}
+
 impl TryFrom<&GridDefinition> for GridDefinitionTemplateValues {
     type Error = GribError;
 
     fn try_from(value: &GridDefinition) -> Result<Self, Self::Error> {
+        let source = value.source_of_grid_definition();
+        if source != 0 {
+            return Err(GribError::NotSupported(format!(
+                "grid not defined by a template (source of grid definition: {source})"
+            )));
+        }
         let num = value.grid_tmpl_num();
         match num {
             0 => {
                 let buf = &value.payload;
+                let mut def = LatLonGridDefinition::from_buf(&buf[25..]);
                 if buf.len() > 67 {
-                    return Err(GribError::NotSupported(format!(
-                        "template {num} with list of number of points"
-                    )));
+                    def.points_per_row = Some(value.points_per_row()?);
                 }
-                Ok(GridDefinitionTemplateValues::Template0(
-                    LatLonGridDefinition::from_buf(&buf[25..]),
-                ))
+                Ok(GridDefinitionTemplateValues::Template0(def))
             }
             20 => {
                 let buf = &value.payload;
@@ -301,7 +634,28 @@ impl TryFrom<&GridDefinition> for GridDefinitionTemplateValues {
                     GaussianGridDefinition::from_buf(&buf[25..]),
                 ))
             }
-            _ => Err(GribError::NotSupported(format!("template {num}"))),
+            50 => {
+                let buf = &value.payload;
+                Ok(GridDefinitionTemplateValues::Template50(
+                    SphericalHarmonicDefinition::from_buf(&buf[9..]),
+                ))
+            }
+            90 => {
+                let buf = &value.payload;
+                Ok(GridDefinitionTemplateValues::Template90(
+                    SpaceViewGridDefinition::from_buf(&buf[9..]),
+                ))
+            }
+            140 => {
+                let buf = &value.payload;
+                Ok(GridDefinitionTemplateValues::Template140(
+                    LambertAzimuthalEqualAreaGridDefinition::from_buf(&buf[9..]),
+                ))
+            }
+            _ => Err(GribError::UnsupportedTemplate {
+                section: 3,
+                number: num,
+            }),
         }
     }
 }
@@ -333,6 +687,24 @@ impl ProdDefinition {
         read_as!(u16, payload, 0)
     }
 
+    /// Returns the list of coordinate values following the template, such as
+    /// the vertical coordinate parameters used for hybrid levels.
+    ///
+    /// The number of values is given by
+    /// [`num_coordinates`](Self::num_coordinates). An empty list is returned if
+    /// the section is too short to contain them.
+    pub fn coordinate_values(&self) -> Vec<f32> {
+        let len = usize::from(self.num_coordinates()) * 4;
+        let payload = &self.payload;
+        if payload.len() < START_OF_PROD_TEMPLATE + len {
+            return Vec::new();
+        }
+        payload[payload.len() - len..]
+            .chunks_exact(4)
+            .map(|bytes| f32::from_be_bytes(bytes.try_into().unwrap()))
+            .collect()
+    }
+
     /// Product Definition Template Number
     pub fn prod_tmpl_num(&self) -> u16 {
         let payload = &self.payload;
@@ -370,8 +742,33 @@ impl ProdDefinition {
     /// Use [CodeTable4_3](crate::codetables::CodeTable4_3) to get textual
     /// representation of the returned numerical value.
     pub fn generating_process(&self) -> Option<u8> {
+        let index = self.generating_process_index()?;
+        self.payload.get(START_OF_PROD_TEMPLATE + index).copied()
+    }
+
+    /// Returns the background generating process identifier, which is
+    /// defined by the originating centre.
+    pub fn background_process_id(&self) -> Option<u8> {
+        let index = self.generating_process_index()?;
+        self.payload
+            .get(START_OF_PROD_TEMPLATE + index + 1)
+            .copied()
+    }
+
+    /// Returns the analysis or forecast generating process identifier, which
+    /// is defined by the originating centre.
+    pub fn forecast_process_id(&self) -> Option<u8> {
+        let index = self.generating_process_index()?;
+        self.payload
+            .get(START_OF_PROD_TEMPLATE + index + 2)
+            .copied()
+    }
+
+    // The type of generating process is followed by the background and
+    // forecast generating process identifiers in all supported templates.
+    fn generating_process_index(&self) -> Option<usize> {
         if self.template_supported() {
-            let index = match self.prod_tmpl_num() {
+            match self.prod_tmpl_num() {
                 0..=39 => Some(2),
                 40..=43 => Some(4),
                 44..=46 => Some(15),
@@ -396,8 +793,7 @@ impl ProdDefinition {
                 254 => Some(2),
                 1000..=1101 => Some(2),
                 _ => None,
-            }?;
-            self.payload.get(START_OF_PROD_TEMPLATE + index).copied()
+            }
         } else {
             None
         }
@@ -438,6 +834,7 @@ impl ProdDefinition {
             let start = unit_index + 1;
             let end = unit_index + 5;
             let time = u32::from_be_bytes(self.payload[start..end].try_into().unwrap());
+            let time = time.as_grib_int();
             unit.map(|v| ForecastTime::from_numbers(v, time))
         } else {
             None
@@ -481,6 +878,34 @@ impl ProdDefinition {
         }
     }
 
+    /// Returns the percentile value, ranging from 0 to 100, of Product
+    /// Definition Templates 4.6 and 4.10.
+    pub fn percentile_value(&self) -> Option<u8> {
+        match self.prod_tmpl_num() {
+            6 | 10 => self.payload.get(START_OF_PROD_TEMPLATE + 25).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the probability type and limits of Product Definition
+    /// Templates 4.5 and 4.9.
+    pub fn probability(&self) -> Option<ProbabilitySpec> {
+        if !matches!(self.prod_tmpl_num(), 5 | 9) {
+            return None;
+        }
+        let start = START_OF_PROD_TEMPLATE + 25;
+        let buf = self.payload.get(start..start + 13)?;
+        Some(ProbabilitySpec {
+            forecast_probability_number: buf[0],
+            total_num_forecast_probabilities: buf[1],
+            probability_type: buf[2],
+            lower_limit_scale_factor: buf[3].as_grib_int(),
+            lower_limit_scaled_value: read_as!(u32, buf, 4).as_grib_int(),
+            upper_limit_scale_factor: buf[8].as_grib_int(),
+            upper_limit_scaled_value: read_as!(u32, buf, 9).as_grib_int(),
+        })
+    }
+
     fn read_surface_from(&self, index: usize) -> Option<FixedSurface> {
         let index = START_OF_PROD_TEMPLATE + index;
         let surface_type = self.payload.get(index).copied();
@@ -527,6 +952,130 @@ impl ReprDefinition {
         let payload = &self.payload;
         read_as!(u16, payload, 4)
     }
+
+    fn has_simple_packing_params(&self) -> bool {
+        matches!(self.repr_tmpl_num(), 0 | 2 | 3 | 40 | 41 | 42 | 51) && self.payload.len() >= 15
+    }
+
+    fn has_complex_packing_params(&self) -> bool {
+        matches!(self.repr_tmpl_num(), 2 | 3) && self.payload.len() >= 30
+    }
+
+    /// Reference value (R) of templates based on simple packing, i.e. 5.0,
+    /// 5.2, 5.3, 5.40, 5.41, 5.42, and 5.51
+    pub fn reference_value(&self) -> Option<f32> {
+        let payload = &self.payload;
+        self.has_simple_packing_params()
+            .then(|| read_as!(f32, payload, 6))
+    }
+
+    /// Binary scale factor (E) of templates based on simple packing
+    pub fn binary_scale_factor(&self) -> Option<i16> {
+        let payload = &self.payload;
+        self.has_simple_packing_params()
+            .then(|| read_as!(u16, payload, 10).as_grib_int())
+    }
+
+    /// Decimal scale factor (D) of templates based on simple packing
+    pub fn decimal_scale_factor(&self) -> Option<i16> {
+        let payload = &self.payload;
+        self.has_simple_packing_params()
+            .then(|| read_as!(u16, payload, 12).as_grib_int())
+    }
+
+    /// Number of bits used for each packed value of templates based on simple
+    /// packing
+    pub fn num_bits(&self) -> Option<u8> {
+        let payload = &self.payload;
+        self.has_simple_packing_params()
+            .then(|| read_as!(u8, payload, 14))
+    }
+
+    /// Number of groups of data values (NG) of templates 5.2 and 5.3
+    pub fn num_groups(&self) -> Option<u32> {
+        let payload = &self.payload;
+        self.has_complex_packing_params()
+            .then(|| read_as!(u32, payload, 26))
+    }
+
+    /// Missing value management used (Code Table 5.5) of templates 5.2 and
+    /// 5.3
+    pub fn missing_value_management(&self) -> Option<u8> {
+        let payload = &self.payload;
+        self.has_complex_packing_params()
+            .then(|| read_as!(u8, payload, 17))
+    }
+
+    /// Primary missing value substitute of templates 5.2 and 5.3, read as a
+    /// floating-point value
+    ///
+    /// Missing values are decoded as NaN, so the substitute is not used in
+    /// decoding. It is meaningful only when
+    /// [`missing_value_management`](Self::missing_value_management) is 1 or
+    /// 2.
+    pub fn primary_missing_value_substitute(&self) -> Option<f32> {
+        let payload = &self.payload;
+        self.has_complex_packing_params()
+            .then(|| read_as!(f32, payload, 18))
+    }
+
+    /// Secondary missing value substitute of templates 5.2 and 5.3, read as a
+    /// floating-point value
+    ///
+    /// It is meaningful only when
+    /// [`missing_value_management`](Self::missing_value_management) is 2.
+    pub fn secondary_missing_value_substitute(&self) -> Option<f32> {
+        let payload = &self.payload;
+        self.has_complex_packing_params()
+            .then(|| read_as!(f32, payload, 22))
+    }
+
+    /// Order of spatial differencing of template 5.3
+    ///
+    /// Use [Table5_6](crate::codetables::grib2::Table5_6) to get textual
+    /// representation of the returned numerical value.
+    pub fn spatial_differencing_order(&self) -> Option<u8> {
+        if self.repr_tmpl_num() == 3 {
+            self.payload.get(42).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Parameters specific to spectral complex packing (template 5.51)
+    pub fn spectral_complex_packing_params(&self) -> Option<SpectralComplexPackingParams> {
+        let payload = &self.payload;
+        (self.repr_tmpl_num() == 51 && payload.len() >= 31).then(|| SpectralComplexPackingParams {
+            laplacian_scaling_factor: read_as!(u32, payload, 16).as_grib_int(),
+            j_s: read_as!(u16, payload, 20),
+            k_s: read_as!(u16, payload, 22),
+            m_s: read_as!(u16, payload, 24),
+            num_unpacked: read_as!(u32, payload, 26),
+            unpacked_precision: payload[30],
+        })
+    }
+}
+
+/// Parameters of spectral complex packing (template 5.51) in addition to
+/// those of simple packing.
+///
+/// Coefficients of the sub-truncation defined by `j_s`, `k_s`, and `m_s` are
+/// stored unpacked, and the others are packed after scaling with the
+/// Laplacian operator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpectralComplexPackingParams {
+    /// Laplacian scaling factor (P) in units of 10^-6
+    pub laplacian_scaling_factor: i32,
+    /// Pentagonal resolution parameter J of the unpacked subset
+    pub j_s: u16,
+    /// Pentagonal resolution parameter K of the unpacked subset
+    pub k_s: u16,
+    /// Pentagonal resolution parameter M of the unpacked subset
+    pub m_s: u16,
+    /// Total number of values in the unpacked subset (Ts)
+    pub num_unpacked: u32,
+    /// Precision of the unpacked subset defined in Code Table 5.7
+    pub unpacked_precision: u8,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -539,6 +1088,85 @@ pub struct BitMap {
 mod tests {
     use super::*;
 
+    macro_rules! test_indicator_from_slice {
+        ($(($name:ident, $input:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let input: &[u8] = $input;
+                let expected: Result<Indicator, ParseError> = $expected;
+                assert_eq!(Indicator::try_from(input), expected);
+                assert_eq!(is_grib2(input), expected.is_ok());
+            }
+        )*);
+    }
+
+    test_indicator_from_slice! {
+        (
+            indicator_from_grib2_slice,
+            b"GRIB\xff\xff\x0a\x02\x00\x00\x00\x00\x00\x00\x00\xc1\x00\x00",
+            Ok(Indicator {
+                discipline: 10,
+                total_length: 193,
+            })
+        ),
+        (
+            indicator_from_grib1_slice,
+            b"GRIB\x00\x00\xc1\x01\x00\x00\x1c\x02\x07\x60\x00\x80",
+            Err(ParseError::GRIBVersionMismatch(1))
+        ),
+        (
+            indicator_from_non_grib_slice,
+            b"\x89HDF\r\n\x1a\n\x00\x00\x00\x00\x00\x08\x08\x00",
+            Err(ParseError::NotGRIB)
+        ),
+        (
+            indicator_from_short_non_grib_slice,
+            b"CDF",
+            Err(ParseError::NotGRIB)
+        ),
+        (
+            indicator_from_truncated_grib2_slice,
+            b"GRIB\x00\x00\x00\x02",
+            Err(ParseError::UnexpectedEndOfData(8))
+        ),
+        (
+            indicator_from_empty_slice,
+            b"",
+            Err(ParseError::UnexpectedEndOfData(0))
+        ),
+    }
+
+    #[test]
+    fn indicator_from_grib2_file() -> Result<(), Box<dyn std::error::Error>> {
+        let buf = std::fs::read(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let indicator = Indicator::try_from(&buf[..])?;
+        assert_eq!(indicator.total_length, buf.len() as u64);
+        assert!(is_grib2(&buf));
+        assert!(!is_grib2(&buf[1..]));
+        Ok(())
+    }
+
+    macro_rules! test_discipline_name {
+        ($(($name:ident, $discipline:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let indicator = Indicator {
+                    discipline: $discipline,
+                    total_length: 0,
+                };
+                assert_eq!(indicator.discipline_name(), $expected);
+            }
+        )*);
+    }
+
+    test_discipline_name! {
+        (discipline_name_for_meteorological_products, 0, Some("Meteorological products")),
+        (discipline_name_for_reserved_code, 5, None),
+        (discipline_name_for_missing_code, 255, None),
+    }
+
     macro_rules! test_date_time_creation {
         ($((
             $name:ident,
@@ -601,8 +1229,222 @@ mod tests {
             last_point_lat: 20041667,
             last_point_lon: 149937500,
             scanning_mode: crate::grid::ScanningMode(0b00000000),
+            points_per_row: None,
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn grid_definition_template_90() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::codetables::{CodeTable3_1, Lookup};
+
+        let mut payload = vec![0x00, 0x00, 0xc1, 0xd1, 0x90, 0x00, 0x00, 0x00, 0x5a];
+        payload.extend_from_slice(&[
+            0x07, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x03, 0xcd, 0x39, 0xfa, 0x01, 0x03, 0xc9,
+            0xf6, 0xa3, 0x00, 0x00, 0x0d, 0xec, 0x00, 0x00, 0x0d, 0xec, 0x00, 0x00, 0x00, 0x00,
+            0x08, 0x62, 0xe9, 0x60, 0x30, 0x00, 0x00, 0x0d, 0xa1, 0x00, 0x00, 0x0d, 0xa1, 0x00,
+            0x1b, 0x13, 0x38, 0x00, 0x1b, 0x13, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x64,
+            0xdf, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice())?;
+        assert_eq!(
+            CodeTable3_1
+                .lookup(usize::from(data.grid_tmpl_num()))
+                .to_string(),
+            "Space view perspective or orthographic"
+        );
+
+        let def = GridDefinitionTemplateValues::try_from(&data)?;
+        assert_eq!(def.short_name(), "space_view");
+        assert_eq!(def.grid_shape(), (3564, 3564));
+        assert_eq!(
+            def.to_string(),
+            "space_view 3564x3564, sub-satellite point (0, 140.7), Nr 6.61071"
+        );
+        assert_eq!(
+            def.earth_shape(),
+            Some(EarthShape::Oblate {
+                major_axis: 6378137.0,
+                minor_axis: 6356752.3
+            })
+        );
+        assert!(def.latlons().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn grid_definition_with_unsupported_template() -> Result<(), Box<dyn std::error::Error>> {
+        // rotated latitude/longitude (template 3.1)
+        let mut payload = vec![0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01];
+        payload.extend_from_slice(&[0x00; 70]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice())?;
+
+        let result = GridDefinitionTemplateValues::try_from(&data);
+        assert_eq!(
+            result,
+            Err(GribError::UnsupportedTemplate {
+                section: 3,
+                number: 1
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn grid_definition_with_predefined_grid() -> Result<(), Box<dyn std::error::Error>> {
+        // predetermined grid definition with template number 65535 (missing)
+        let mut payload = vec![0x01, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0xff, 0xff];
+        payload.extend_from_slice(&[0x00; 70]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice())?;
+        assert_eq!(data.source_of_grid_definition(), 1);
+
+        let result = GridDefinitionTemplateValues::try_from(&data);
+        assert_eq!(
+            result,
+            Err(GribError::NotSupported(
+                "grid not defined by a template (source of grid definition: 1)".to_owned()
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn grid_definition_template_50() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::codetables::{CodeTable3_1, Lookup};
+
+        // T639 spherical harmonic coefficients
+        let mut payload = vec![0x00, 0x00, 0x06, 0x43, 0x00, 0x00, 0x00, 0x00, 0x32];
+        payload.extend_from_slice(&[
+            0x00, 0x00, 0x02, 0x7f, 0x00, 0x00, 0x02, 0x7f, 0x00, 0x00, 0x02, 0x7f, 0x01, 0x01,
+        ]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice())?;
+        assert_eq!(
+            CodeTable3_1
+                .lookup(usize::from(data.grid_tmpl_num()))
+                .to_string(),
+            "Spherical harmonic coefficients"
+        );
+
+        let def = GridDefinitionTemplateValues::try_from(&data)?;
+        assert_eq!(
+            def,
+            GridDefinitionTemplateValues::Template50(SphericalHarmonicDefinition {
+                j: 639,
+                k: 639,
+                m: 639,
+                representation_type: 1,
+                representation_mode: 1,
+            })
+        );
+        assert_eq!(def.short_name(), "sh");
+        assert_eq!(def.grid_shape(), (205120, 1));
+        assert_eq!(def.to_string(), "sh 205120x1, J 639, K 639, M 639");
+        assert_eq!(def.earth_shape(), None);
+        assert!(def.ij().is_err());
+        assert!(def.latlons().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn grid_definition_with_number_of_points_near_u32_max() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut payload = vec![0x00, 0xff, 0xff, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x00];
+        payload.extend_from_slice(&[0x00; 58]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice())?;
+        assert_eq!(data.num_points(), u32::MAX - 1);
+        assert_eq!(data.effective_num_points(), Ok(u32::MAX - 1));
+        assert_eq!(data.num_points() as usize, 4_294_967_294);
+        Ok(())
+    }
+
+    #[test]
+    fn grid_definition_template_0_with_list_of_numbers_of_points() {
+        let mut payload = vec![
+            0x00, 0x00, 0x00, 0x00, 0x08, 0x02, 0x01, 0x00, 0x00, 0x06, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x00,
+            0x98, 0x96, 0x80, 0x00, 0x00, 0x00, 0x00, 0x30, 0x80, 0x98, 0x96, 0x80, 0x05, 0x5d,
+            0x4a, 0x80, 0xff, 0xff, 0xff, 0xff, 0x00, 0x98, 0x96, 0x80, 0x00,
+        ];
+        payload.extend_from_slice(&[0x00, 0x02, 0x00, 0x04, 0x00, 0x02]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice()).unwrap();
+
+        let actual = GridDefinitionTemplateValues::try_from(&data).unwrap();
+        let expected = GridDefinitionTemplateValues::Template0(LatLonGridDefinition {
+            ni: 0xffffffff,
+            nj: 3,
+            first_point_lat: 10000000,
+            first_point_lon: 0,
+            last_point_lat: -10000000,
+            last_point_lon: 90000000,
+            scanning_mode: crate::grid::ScanningMode(0b00000000),
+            points_per_row: Some(vec![2, 4, 2]),
         });
         assert_eq!(actual, expected);
+
+        let latlons = actual.latlons().unwrap().collect::<Vec<_>>();
+        assert_eq!(latlons.len(), 8);
+        assert_eq!(data.effective_num_points(), Ok(8));
+    }
+
+    #[test]
+    fn effective_num_points_inconsistent_with_list_of_numbers_of_points() {
+        let mut payload = vec![0; 67];
+        payload[4] = 9;
+        payload[5] = 1;
+        payload[6] = 1;
+        payload.extend_from_slice(&[2, 4, 2]);
+        let data = GridDefinition::from_payload(payload.into_boxed_slice()).unwrap();
+
+        assert_eq!(
+            data.effective_num_points(),
+            Err(GribError::InvalidValueError(
+                "number of data points does not match: 9 (defined) vs 8 (sum of list of numbers of points)"
+                    .to_owned()
+            ))
+        );
+    }
+
+    macro_rules! test_optional_point_list {
+        ($(($name:ident, $template:expr, $num_octets:expr, $list:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let mut payload = vec![0; 67];
+                payload[5] = $num_octets;
+                payload[6] = 1;
+                payload[8] = $template;
+                payload.extend_from_slice(&$list);
+                let data = GridDefinition::from_payload(payload.into_boxed_slice()).unwrap();
+
+                assert_eq!(data.optional_point_list(), $expected);
+            }
+        )*);
+    }
+
+    test_optional_point_list! {
+        (
+            optional_point_list_with_2_octet_width_for_template_0,
+            0,
+            2,
+            [0x00, 0x02, 0x01, 0x04, 0x00, 0x02],
+            Some(vec![2, 260, 2])
+        ),
+        (
+            optional_point_list_with_2_octet_width_for_template_40,
+            40,
+            2,
+            [0x00, 0x14, 0x00, 0x18],
+            Some(vec![20, 24])
+        ),
+        (
+            optional_point_list_with_1_octet_width_for_template_0,
+            0,
+            1,
+            [0x02, 0x04, 0x02],
+            Some(vec![2, 4, 2])
+        ),
+        (optional_point_list_absent, 0, 0, [], None),
+        (optional_point_list_for_unknown_template, 30, 2, [0x00, 0x02], None),
     }
 
     #[test]
@@ -618,6 +1460,9 @@ mod tests {
 
         assert_eq!(data.parameter_category(), Some(193));
         assert_eq!(data.parameter_number(), Some(0));
+        assert_eq!(data.generating_process(), Some(2));
+        assert_eq!(data.background_process_id(), Some(153));
+        assert_eq!(data.forecast_process_id(), Some(255));
         assert_eq!(
             data.forecast_time(),
             Some(ForecastTime::from_numbers(0, 40))
@@ -630,4 +1475,209 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn prod_definition_coordinate_values_for_hybrid_level() {
+        let mut payload = vec![0x00, 0x04, 0x00, 0x00];
+        payload.extend_from_slice(&[
+            0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x69,
+            0x00, 0x00, 0x00, 0x00, 0x05, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+        for value in [0.0_f32, 2000.0, 0.0, 0.5] {
+            payload.extend_from_slice(&value.to_be_bytes());
+        }
+        let data = ProdDefinition::from_payload(payload.into_boxed_slice()).unwrap();
+
+        assert_eq!(
+            data.fixed_surfaces().map(|(first, _)| first),
+            Some(FixedSurface::new(105, 0, 5))
+        );
+        let actual = data.coordinate_values();
+        assert_eq!(actual.len(), usize::from(data.num_coordinates()));
+        assert_eq!(actual, vec![0.0, 2000.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn prod_definition_coordinate_values_absent() {
+        let data =
+            ProdDefinition::from_payload(vec![0x00, 0x02, 0x00, 0x00].into_boxed_slice()).unwrap();
+        assert_eq!(data.coordinate_values(), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn grid_definition_template_values_summary() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+        let f = std::fs::File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first message is not found")?;
+        let def = GridDefinitionTemplateValues::try_from(submessage.grid_def())?;
+        assert_eq!(
+            def.to_string(),
+            "regular_ll 1440x721, first point (90, 0), last point (-90, 359.75), increments (0.25, 0.25)"
+        );
+        Ok(())
+    }
+
+    macro_rules! test_prod_definition_statistical_params {
+        ($(($name:ident, $template:expr, $expected_percentile:expr, $expected_probability:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let mut payload = vec![0x00, 0x00];
+                payload.extend_from_slice(&u16::to_be_bytes($template));
+                payload.extend_from_slice(&[
+                    0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+                    0x03, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0x00,
+                    0x00, // end of the part common with template 4.0
+                    0x05, 0x0a, 0x02, 0x81, 0x00, 0x00, 0x00, 0x0f, 0x01, 0x80, 0x00, 0x00,
+                    0x05,
+                ]);
+                let data = ProdDefinition::from_payload(payload.into_boxed_slice()).unwrap();
+                assert_eq!(data.percentile_value(), $expected_percentile);
+                assert_eq!(data.probability(), $expected_probability);
+            }
+        )*);
+    }
+
+    test_prod_definition_statistical_params! {
+        (prod_definition_template_0_statistical_params, 0, None, None),
+        (
+            prod_definition_template_5_statistical_params,
+            5,
+            None,
+            Some(ProbabilitySpec {
+                forecast_probability_number: 5,
+                total_num_forecast_probabilities: 10,
+                probability_type: 2,
+                lower_limit_scale_factor: -1,
+                lower_limit_scaled_value: 15,
+                upper_limit_scale_factor: 1,
+                upper_limit_scaled_value: -5,
+            })
+        ),
+        (prod_definition_template_6_statistical_params, 6, Some(5), None),
+        (
+            prod_definition_template_9_statistical_params,
+            9,
+            None,
+            Some(ProbabilitySpec {
+                forecast_probability_number: 5,
+                total_num_forecast_probabilities: 10,
+                probability_type: 2,
+                lower_limit_scale_factor: -1,
+                lower_limit_scaled_value: 15,
+                upper_limit_scale_factor: 1,
+                upper_limit_scaled_value: -5,
+            })
+        ),
+        (prod_definition_template_10_statistical_params, 10, Some(5), None),
+    }
+
+    #[test]
+    fn prod_definition_probability_from_file() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+        let f = std::fs::File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let probability = grib2
+            .iter()
+            .find_map(|(_, submessage)| submessage.prod_def().probability())
+            .ok_or("no probability product found")?;
+        assert_eq!(probability.probability_type, 1);
+        assert!(probability.lower_limit().is_nan());
+        assert_eq!(probability.upper_limit(), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn prod_definition_process_ids() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+        let f = std::fs::File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        for (_, submessage) in grib2.iter() {
+            let prod_def = submessage.prod_def();
+            assert_eq!(prod_def.generating_process(), Some(2));
+            assert_eq!(prod_def.background_process_id(), Some(31));
+            assert_eq!(prod_def.forecast_process_id(), Some(40));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn repr_definition_packing_params() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        let mut buf = Vec::new();
+        let f = std::fs::File::open(
+            "testdata/Z__C_RJTD_20190605000000_MEPS_GPV_Rjp_L-pall_FH00-15_grib2.bin.0-20.xz",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first message is not found")?;
+        let repr_def = submessage.repr_def();
+        assert_eq!(repr_def.repr_tmpl_num(), 3);
+        assert_eq!(repr_def.reference_value(), Some(-14.655413));
+        assert_eq!(repr_def.binary_scale_factor(), Some(-6));
+        assert_eq!(repr_def.decimal_scale_factor(), Some(0));
+        assert_eq!(repr_def.num_bits(), Some(14));
+        assert_eq!(repr_def.num_groups(), Some(1906));
+        assert_eq!(repr_def.spatial_differencing_order(), Some(2));
+        Ok(())
+    }
+
+    #[test]
+    fn repr_definition_spectral_complex_packing_params() -> Result<(), Box<dyn std::error::Error>> {
+        let payload = vec![
+            0x00, 0x00, 0x01, 0xce, // number of values
+            0x00, 0x33, // template number
+            0x3f, 0x80, 0x00, 0x00, // reference value
+            0x80, 0x02, // binary scale factor
+            0x00, 0x01, // decimal scale factor
+            0x10, // number of bits
+            0x00, // type of original field values
+            0x80, 0x07, 0xa1, 0x20, // laplacian scaling factor
+            0x00, 0x14, 0x00, 0x14, 0x00, 0x14, // pentagonal resolution parameters
+            0x00, 0x00, 0x01, 0xce, // number of unpacked values
+            0x01, // precision of unpacked values
+        ];
+        let repr_def = ReprDefinition::from_payload(payload.into_boxed_slice())?;
+        assert_eq!(repr_def.reference_value(), Some(1.0));
+        assert_eq!(repr_def.binary_scale_factor(), Some(-2));
+        assert_eq!(repr_def.num_bits(), Some(16));
+        assert_eq!(
+            repr_def.spectral_complex_packing_params(),
+            Some(SpectralComplexPackingParams {
+                laplacian_scaling_factor: -500000,
+                j_s: 20,
+                k_s: 20,
+                m_s: 20,
+                num_unpacked: 462,
+                unpacked_precision: 1,
+            })
+        );
+        Ok(())
+    }
 }
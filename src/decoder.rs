@@ -1,21 +1,36 @@
-#[cfg(target_arch = "wasm32")]
-use std::marker::PhantomData;
+#[cfg(not(jpeg2000))]
+use alloc::vec;
+use alloc::{boxed::Box, format, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+#[cfg(feature = "std")]
+use std::error::Error;
 
 use num::ToPrimitive;
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(jpeg2000)]
 use crate::decoder::jpeg2000::Jpeg2000CodeStreamDecodeError;
+#[cfg(feature = "std")]
+use crate::{
+    context::{SectionBody, SectionInfo, SubMessage},
+    decoder::png::PngDecodeError,
+    reader::Grib2Read,
+};
 use crate::{
-    context::{SectionBody, SubMessage},
     decoder::{
-        bitmap::{create_bitmap_for_nonnullable_data, BitmapDecodeIterator},
+        bitmap::{create_bitmap_for_nonnullable_data, has_present_points, BitmapDecodeIterator},
         complex::ComplexPackingDecodeError,
-        png::PngDecodeError,
-        run_length::RunLengthEncodingDecodeError,
+        ieee::{IeeeDecodeError, IeeeDecodeIterator},
+        run_length::{RunLengthDecodeIterator, RunLengthEncodingDecodeError},
         simple::{SimplePackingDecodeError, SimplePackingDecodeIteratorWrapper},
+        spectral::SpectralSimpleDecodeIterator,
+        stream::{PositionRecorder, ReadPosition},
     },
     error::*,
-    reader::Grib2Read,
+    helpers::read_as,
 };
 
 /// Decoder for grid point values of GRIB2 submessages.
@@ -47,12 +62,36 @@ use crate::{
 /// }
 /// ```
 pub struct Grib2SubmessageDecoder {
-    num_points_total: usize,
+    pub(crate) num_points_total: usize,
     pub(crate) num_points_encoded: usize,
     template_num: u16,
     pub(crate) sect5_payload: Box<[u8]>,
     bitmap: Vec<u8>,
     pub(crate) sect7_payload: Box<[u8]>,
+    diagnostics: Option<DiagnosticsCounter>,
+}
+
+// Statistics recorded during decoding when diagnostics are enabled.
+#[derive(Default)]
+struct DiagnosticsCounter {
+    sect7_end: Cell<usize>,
+    num_produced: Cell<usize>,
+}
+
+/// Statistics of the last decoding process of a [`Grib2SubmessageDecoder`]
+/// with diagnostics enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DecodeDiagnostics {
+    /// Number of bytes of the Section 7 payload consumed to produce the
+    /// values, i.e. the end of the data read by the decoder so far. JPEG 2000
+    /// code streams and PNG images are read through by their decoders at
+    /// once, so the whole payload is counted when they are decoded.
+    pub sect7_bytes_consumed: usize,
+    /// Length of the Section 7 payload in bytes.
+    pub sect7_payload_size: usize,
+    /// Number of values produced, including NaN for grid points masked by the
+    /// bitmap.
+    pub num_values_produced: usize,
 }
 
 impl Grib2SubmessageDecoder {
@@ -71,15 +110,103 @@ impl Grib2SubmessageDecoder {
             sect5_payload,
             bitmap,
             sect7_payload,
+            diagnostics: None,
         }
     }
 
+    /// Enables diagnostics, with which the decoding process started by
+    /// [`dispatch`](Self::dispatch) records the number of bytes consumed from
+    /// Section 7 and the number of values produced.
+    ///
+    /// The statistics can be obtained with [`diagnostics`](Self::diagnostics)
+    /// after values are consumed. Comparing the number of bytes consumed with
+    /// the size of the Section 7 payload helps to detect mis-sized reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::Grib2SubmessageDecoder;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let decoder = Grib2SubmessageDecoder::from(first_submessage)?.with_diagnostics();
+    ///     assert_eq!(decoder.dispatch()?.count(), 2949120);
+    ///
+    ///     let diagnostics = decoder.diagnostics().unwrap();
+    ///     assert_eq!(diagnostics.num_values_produced, 2949120);
+    ///     assert!(diagnostics.sect7_bytes_consumed <= diagnostics.sect7_payload_size);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_diagnostics(self) -> Self {
+        Self {
+            diagnostics: Some(DiagnosticsCounter::default()),
+            ..self
+        }
+    }
+
+    /// Returns statistics of the last decoding process, or `None` if
+    /// diagnostics are not enabled with
+    /// [`with_diagnostics`](Self::with_diagnostics).
+    pub fn diagnostics(&self) -> Option<DecodeDiagnostics> {
+        let counter = self.diagnostics.as_ref()?;
+        Some(DecodeDiagnostics {
+            sect7_bytes_consumed: counter.sect7_end.get(),
+            sect7_payload_size: self.sect7_payload.len(),
+            num_values_produced: counter.num_produced.get(),
+        })
+    }
+
     /// Sets up a decoder for grid point values of `submessage`.
-    pub fn from<R: Grib2Read>(submessage: SubMessage<R>) -> Result<Self, GribError> {
-        let mut reader = submessage.9;
+    ///
+    /// Returns [`GribError::UnsupportedTemplate`] if values packed
+    /// with the Data Representation Template of `submessage` cannot be decoded
+    /// in this build, and [`DecodeError::SectionReadError`] if the payload of a
+    /// section required for decoding cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::GribError;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     match grib::Grib2SubmessageDecoder::from(first_submessage) {
+    ///         Ok(decoder) => assert_eq!(decoder.dispatch()?.count(), 2949120),
+    ///         Err(GribError::UnsupportedTemplate { section: 5, .. }) => {
+    ///             unreachable!("simple packing is always supported")
+    ///         }
+    ///         Err(e) => return Err(e.into()),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from<R: Grib2Read>(mut submessage: SubMessage<R>) -> Result<Self, GribError> {
+        Self::from_submessage(&mut submessage)
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn from_submessage<R: Grib2Read>(
+        submessage: &mut SubMessage<R>,
+    ) -> Result<Self, GribError> {
+        let reader = &mut submessage.9;
         let sect5 = submessage.5.body;
         let sect6 = submessage.6.body;
         let sect7 = submessage.7.body;
+        let previous_sect6 = submessage.11.as_ref().map(|sect| sect.body);
         let (sect3_body, sect5_body, sect6_body) = match (
             submessage.3.body.body.as_ref(),
             sect5.body.as_ref(),
@@ -93,10 +220,36 @@ impl Grib2SubmessageDecoder {
             _ => return Err(GribError::InternalDataError),
         };
         let sect3_num_points = sect3_body.num_points() as usize;
+        let template_num = sect5_body.repr_tmpl_num();
+        match PackingKind::from(template_num) {
+            PackingKind::SpectralComplex => return Err(spectral::complex_packing_not_decoded()),
+            kind if !kind.is_supported() => {
+                return Err(GribError::UnsupportedTemplate {
+                    section: 5,
+                    number: template_num,
+                })
+            }
+            _ => {}
+        }
+
+        let mut read_payload = |sect: &SectionInfo| {
+            reader
+                .read_sect_payload_as_slice(sect)
+                .map_err(|e| GribError::DecodeError(DecodeError::SectionReadError(sect.num, e)))
+        };
 
         let bitmap = match sect6_body.bitmap_indicator {
             0x00 => {
-                let sect6_data = reader.read_sect_payload_as_slice(sect6)?;
+                let sect6_data = read_payload(sect6)?;
+                sect6_data[1..].into()
+            }
+            0xfe => {
+                let sect6 = previous_sect6.ok_or_else(|| {
+                    GribError::InvalidValueError(
+                        "no bitmap previously defined in the same message".into(),
+                    )
+                })?;
+                let sect6_data = read_payload(sect6)?;
                 sect6_data[1..].into()
             }
             0xff => {
@@ -113,38 +266,453 @@ impl Grib2SubmessageDecoder {
         Ok(Self::new(
             sect3_num_points,
             sect5_body.num_points() as usize,
-            sect5_body.repr_tmpl_num(),
-            reader.read_sect_payload_as_slice(sect5)?,
+            template_num,
+            read_payload(sect5)?,
             bitmap,
-            reader.read_sect_payload_as_slice(sect7)?,
+            read_payload(sect7)?,
         ))
     }
 
+    /// Returns the packing algorithm of the submessage, determined from the
+    /// Data Representation Template number in Section 5.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::{Grib2SubmessageDecoder, PackingKind};
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let decoder = Grib2SubmessageDecoder::from(first_submessage)?;
+    ///     assert_eq!(decoder.packing_kind(), PackingKind::RunLength);
+    ///     assert!(decoder.packing_kind().is_supported());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn packing_kind(&self) -> PackingKind {
+        PackingKind::from(self.template_num)
+    }
+
     /// Dispatches a decoding process and gets an iterator of decoded values.
+    ///
+    /// If Section 7 contains no packed values and the bitmap marks all the grid
+    /// points as missing, all the decoded values are NaN regardless of the
+    /// template.
     pub fn dispatch(
         &self,
     ) -> Result<Grib2DecodedValues<impl Iterator<Item = f32> + '_>, GribError> {
+        // Statistics are recorded by separately instantiated decoders so that
+        // decoding without diagnostics is not slowed down.
+        let values = match self.diagnostics.as_ref() {
+            None => {
+                let encoded = self.dispatch_encoded(())?;
+                DecodedValues::Plain(BitmapDecodeIterator::new(
+                    self.bitmap.iter(),
+                    encoded,
+                    self.num_points_total,
+                )?)
+            }
+            Some(counter) => {
+                counter.sect7_end.set(0);
+                counter.num_produced.set(0);
+                let encoded = self.dispatch_encoded(PositionRecorder::new(&counter.sect7_end))?;
+                DecodedValues::Diagnosed(
+                    BitmapDecodeIterator::new(self.bitmap.iter(), encoded, self.num_points_total)?,
+                    &counter.num_produced,
+                )
+            }
+        };
+        Ok(Grib2DecodedValues(values, PhantomData))
+    }
+
+    // Checks that Section 5 is long enough for the parameters read by the
+    // decoder of the template, so that truncated sections are reported as
+    // errors instead of causing out-of-range slicing. Templates 5.4 and 5.50 are
+    // checked by their decoders.
+    fn check_sect5_payload_len(&self) -> Result<(), GribError> {
+        let payload = &self.sect5_payload;
+        let min_len = match self.template_num {
+            0 | 40 | 41 => 16,
+            2 => 42,
+            3 => 44,
+            // level values follow the maximum level in octets 15-16
+            200 if payload.len() >= 11 => 12 + 2 * usize::from(read_as!(u16, payload, 9)),
+            200 => 12,
+            _ => return Ok(()),
+        };
+        if payload.len() < min_len {
+            return Err(GribError::InvalidValueError(format!(
+                "Section 5 too short for template 5.{}: {} octets",
+                self.template_num,
+                payload.len() + 5
+            )));
+        }
+        Ok(())
+    }
+
+    // Decodes values encoded in Section 7, i.e. values of grid points present
+    // in the bitmap, reporting how far Section 7 has been read to `position`.
+    fn dispatch_encoded<'a, P: ReadPosition + 'a>(
+        &'a self,
+        position: P,
+    ) -> Result<impl Iterator<Item = f32> + 'a, GribError> {
+        self.check_sect5_payload_len()?;
         let decoder = match self.template_num {
-            0 => Grib2ValueIterator::Template0(simple::decode(self)?),
-            2 => Grib2ValueIterator::Template2(complex::decode_7_2(self)?),
-            3 => Grib2ValueIterator::Template3(complex::decode_7_3(self)?),
-            #[cfg(not(target_arch = "wasm32"))]
-            40 => Grib2ValueIterator::Template40(jpeg2000::decode(self)?),
-            41 => Grib2ValueIterator::Template41(png::decode(self)?),
-            200 => Grib2ValueIterator::Template200(run_length::decode(self)?),
+            _ if self.sect7_payload.is_empty()
+                && !has_present_points(&self.bitmap, self.num_points_total) =>
+            {
+                Grib2ValueIterator::Empty
+            }
+            0 => Grib2ValueIterator::Template0(simple::decode(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                self.num_points_encoded,
+                position,
+            )?),
+            2 => Grib2ValueIterator::Template2(complex::decode_7_2(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                position,
+            )?),
+            3 => Grib2ValueIterator::Template3(complex::decode_7_3(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                position,
+            )?),
+            4 => Grib2ValueIterator::Template4(ieee::decode(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                self.num_points_encoded,
+                position,
+            )?),
+            #[cfg(jpeg2000)]
+            40 => Grib2ValueIterator::Template40(jpeg2000::decode(self, position)?),
+            #[cfg(feature = "std")]
+            41 => Grib2ValueIterator::Template41(png::decode(self, position)?),
+            50 => Grib2ValueIterator::Template50(spectral::decode(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                self.num_points_encoded,
+                position,
+            )?),
+            51 => return Err(spectral::complex_packing_not_decoded()),
+            200 => Grib2ValueIterator::Template200(run_length::decode(
+                &self.sect5_payload,
+                &self.sect7_payload,
+                self.num_points_encoded,
+                position,
+            )?),
             _ => {
-                return Err(GribError::DecodeError(
-                    DecodeError::TemplateNumberUnsupported,
-                ))
+                return Err(GribError::UnsupportedTemplate {
+                    section: 5,
+                    number: self.template_num,
+                })
             }
         };
-        let decoder =
-            BitmapDecodeIterator::new(self.bitmap.iter(), decoder, self.num_points_total)?;
-        Ok(Grib2DecodedValues(decoder))
+        Ok(decoder)
+    }
+
+    // Decodes values of grid points present in the bitmap together with their
+    // indices in the grid, without producing values for masked grid points.
+    #[cfg(feature = "std")]
+    pub(crate) fn present_values(&self) -> Result<Vec<(usize, f32)>, GribError> {
+        let indices = bitmap::present_indices(&self.bitmap, self.num_points_total);
+        let values = indices.zip(self.dispatch_encoded(())?).collect::<Vec<_>>();
+        if values.len() != self.num_points_encoded {
+            return Err(GribError::DecodeError(DecodeError::LengthMismatch));
+        }
+        Ok(values)
+    }
+
+    /// Decodes grid point values into `out`, a caller-provided buffer, without
+    /// allocating a buffer for the output.
+    ///
+    /// Values of grid points masked by the bitmap are written as NaN. The
+    /// length of `out` should be equal to the number of grid points defined in
+    /// Section 3; otherwise an error is returned without writing any values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::Grib2SubmessageDecoder;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f =
+    ///         std::fs::File::open("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///     let (_index, first_submessage) = grib2.iter().next().unwrap();
+    ///
+    ///     let decoder = Grib2SubmessageDecoder::from(first_submessage)?;
+    ///     let mut values = vec![0.0_f32; 1126500];
+    ///     decoder.decode_into(&mut values)?;
+    ///     assert_eq!(values[0].round(), 236.0_f32);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn decode_into(&self, out: &mut [f32]) -> Result<(), GribError> {
+        if out.len() != self.num_points_total {
+            return Err(GribError::InvalidValueError(format!(
+                "length of output buffer does not match: {} (buffer) vs {} (grid points)",
+                out.len(),
+                self.num_points_total
+            )));
+        }
+
+        let mut num_written = 0;
+        for (dst, value) in out.iter_mut().zip(self.dispatch()?) {
+            *dst = value;
+            num_written += 1;
+        }
+        if num_written != self.num_points_total {
+            return Err(GribError::DecodeError(DecodeError::LengthMismatch));
+        }
+        Ok(())
+    }
+
+    /// Decodes level values of run-length packing (template 5.200) without
+    /// mapping them to representative values.
+    ///
+    /// Level values of grid points masked by the bitmap are 0, which denotes
+    /// missing values in run-length packing. An error is returned for other
+    /// packing algorithms.
+    pub fn level_values(&self) -> Result<Vec<u16>, GribError> {
+        if self.packing_kind() != PackingKind::RunLength {
+            return Err(GribError::InvalidValueError(
+                "not packed with run-length packing".into(),
+            ));
+        }
+        self.check_sect5_payload_len()?;
+        let mut levels = run_length::decode_levels(
+            &self.sect5_payload,
+            &self.sect7_payload,
+            self.num_points_encoded,
+        )?
+        .into_iter();
+        (0..self.num_points_total)
+            .map(|i| {
+                let is_present = self
+                    .bitmap
+                    .get(i / 8)
+                    .is_some_and(|byte| byte & (0b10000000 >> (i % 8)) != 0);
+                if is_present {
+                    levels
+                        .next()
+                        .ok_or(GribError::DecodeError(DecodeError::LengthMismatch))
+                } else {
+                    Ok(0)
+                }
+            })
+            .collect()
+    }
+
+    /// Decodes values in a rectangular region of the image encoded in the
+    /// JPEG 2000 code stream format (template 5.40).
+    ///
+    /// The region starts at column `x` and row `y` of the image, whose rows
+    /// are runs of consecutive grid points in the scanning order, and has
+    /// `width` columns and `height` rows. Only code blocks covering the
+    /// region are decoded. Returned values are arranged row by row.
+    ///
+    /// An error is returned for other packing algorithms and for data with
+    /// grid points masked by the bitmap, whose image does not cover the
+    /// grid.
+    #[cfg(jpeg2000)]
+    pub fn decode_region(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<Vec<f32>, GribError> {
+        if self.packing_kind() != PackingKind::Jpeg2000 {
+            return Err(GribError::InvalidValueError(
+                "not packed with JPEG 2000 code stream format".into(),
+            ));
+        }
+        if self.num_points_encoded != self.num_points_total {
+            return Err(GribError::NotSupported(
+                "region decoding of data with grid points masked by the bitmap".into(),
+            ));
+        }
+        self.check_sect5_payload_len()?;
+        let to_u32 = |value: usize| {
+            u32::try_from(value)
+                .map_err(|_| GribError::InvalidValueError(format!("region too large: {value}")))
+        };
+        let values = jpeg2000::decode_region(
+            self,
+            to_u32(x)?,
+            to_u32(y)?,
+            to_u32(width)?,
+            to_u32(height)?,
+        )?
+        .collect::<Vec<_>>();
+        if width.checked_mul(height) != Some(values.len()) {
+            return Err(GribError::DecodeError(DecodeError::LengthMismatch));
+        }
+        Ok(values)
+    }
+}
+
+/// Packing algorithm of grid point values, determined from the Data
+/// Representation Template number in Section 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PackingKind {
+    /// Simple packing (template 5.0)
+    Simple,
+    /// Complex packing (template 5.2)
+    Complex,
+    /// Complex packing and spatial differencing (template 5.3)
+    ComplexWithSpatialDifferencing,
+    /// IEEE floating point data (template 5.4)
+    Ieee,
+    /// JPEG 2000 code stream format (template 5.40)
+    Jpeg2000,
+    /// Portable Network Graphics (PNG) format (template 5.41)
+    Png,
+    /// CCSDS recommended lossless compression (template 5.42)
+    Ccsds,
+    /// Spectral data - simple packing (template 5.50)
+    SpectralSimple,
+    /// Spectral data - complex packing (template 5.51)
+    SpectralComplex,
+    /// Run length packing with level values (template 5.200)
+    RunLength,
+    /// Other templates, holding the template number
+    Other(u16),
+}
+
+impl PackingKind {
+    /// Returns `true` if grid point values packed with this algorithm can be
+    /// decoded in this build.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Self::Jpeg2000 => cfg!(jpeg2000),
+            Self::Png => cfg!(feature = "std"),
+            _ => matches!(
+                self,
+                Self::Simple
+                    | Self::Complex
+                    | Self::ComplexWithSpatialDifferencing
+                    | Self::Ieee
+                    | Self::SpectralSimple
+                    | Self::RunLength
+            ),
+        }
+    }
+}
+
+#[cfg(jpeg2000)]
+const SUPPORTED_PACKING_TEMPLATES: &[u16] = &[0, 2, 3, 4, 40, 41, 50, 200];
+#[cfg(all(feature = "std", not(jpeg2000)))]
+const SUPPORTED_PACKING_TEMPLATES: &[u16] = &[0, 2, 3, 4, 41, 50, 200];
+#[cfg(not(feature = "std"))]
+const SUPPORTED_PACKING_TEMPLATES: &[u16] = &[0, 2, 3, 4, 50, 200];
+
+/// Returns numbers of Data Representation Templates whose values can be
+/// decoded in this build, in ascending order.
+///
+/// Templates depending on optional features or platforms, such as the JPEG
+/// 2000 code stream format (template 5.40), which is available on `wasm32`
+/// only with the `jpeg2000-wasm` feature, are listed only if they are
+/// compiled in. Each number is also
+/// reported as supported by [`PackingKind::is_supported`].
+///
+/// # Examples
+///
+/// ```
+/// let templates = grib::supported_packing_templates();
+/// assert!(templates.contains(&0));
+/// assert!(!templates.contains(&42));
+/// ```
+pub fn supported_packing_templates() -> &'static [u16] {
+    SUPPORTED_PACKING_TEMPLATES
+}
+
+impl From<u16> for PackingKind {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => Self::Simple,
+            2 => Self::Complex,
+            3 => Self::ComplexWithSpatialDifferencing,
+            4 => Self::Ieee,
+            40 => Self::Jpeg2000,
+            41 => Self::Png,
+            42 => Self::Ccsds,
+            50 => Self::SpectralSimple,
+            51 => Self::SpectralComplex,
+            200 => Self::RunLength,
+            _ => Self::Other(value),
+        }
     }
 }
 
-pub struct Grib2DecodedValues<'b, I>(BitmapDecodeIterator<std::slice::Iter<'b, u8>, I>);
+/// Decodes grid point values from raw bytes of Sections 5, 6, and 7.
+///
+/// Each of `sect5`, `sect6`, and `sect7` should contain the whole section
+/// including the 5-octet section header. `num_points` is the number of grid
+/// points defined in Section 3. Decoding is dispatched on the template number
+/// in Section 5, so that values can be decoded without a [`Grib2`] context, for
+/// example from sections extracted from a custom container.
+///
+/// [`Grib2`]: crate::Grib2
+pub fn decode_values(
+    sect5: &[u8],
+    sect6: &[u8],
+    sect7: &[u8],
+    num_points: usize,
+) -> Result<Vec<f32>, GribError> {
+    let sect5_payload = section_payload(sect5, 5)?;
+    let sect6_payload = section_payload(sect6, 6)?;
+    let sect7_payload = section_payload(sect7, 7)?;
+    if sect5_payload.len() < 6 || sect6_payload.is_empty() {
+        return Err(GribError::InvalidValueError(
+            "section too short to be decoded".into(),
+        ));
+    }
+
+    let bitmap = match sect6_payload[0] {
+        0x00 => sect6_payload[1..].into(),
+        0xff => create_bitmap_for_nonnullable_data(num_points),
+        _ => {
+            return Err(GribError::DecodeError(
+                DecodeError::BitMapIndicatorUnsupported,
+            ));
+        }
+    };
+    let num_points_encoded = read_as!(u32, sect5_payload, 0) as usize;
+    let template_num = read_as!(u16, sect5_payload, 4);
+
+    let decoder = Grib2SubmessageDecoder::new(
+        num_points,
+        num_points_encoded,
+        template_num,
+        sect5_payload.into(),
+        bitmap,
+        sect7_payload.into(),
+    );
+    let values = decoder.dispatch()?.collect();
+    Ok(values)
+}
+
+fn section_payload(sect: &[u8], num: u8) -> Result<&[u8], GribError> {
+    match sect.get(4) {
+        Some(actual) if *actual == num => Ok(&sect[5..]),
+        _ => Err(GribError::InvalidValueError(format!(
+            "Section {num} not found in the given bytes"
+        ))),
+    }
+}
+
+pub struct Grib2DecodedValues<'b, I>(I, PhantomData<&'b ()>);
 
 impl<I> Iterator for Grib2DecodedValues<'_, I>
 where
@@ -153,42 +721,122 @@ where
     type Item = f32;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let Self(inner) = self;
+        let Self(inner, _) = self;
         inner.next()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let Self(inner) = self;
+        let Self(inner, _) = self;
         inner.size_hint()
     }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let Self(inner, _) = self;
+        inner.fold(init, f)
+    }
+}
+
+// Values with grid points masked by the bitmap, counted only when diagnostics
+// are enabled.
+enum DecodedValues<'b, I, J> {
+    Plain(BitmapDecodeIterator<core::slice::Iter<'b, u8>, I>),
+    Diagnosed(
+        BitmapDecodeIterator<core::slice::Iter<'b, u8>, J>,
+        &'b Cell<usize>,
+    ),
+}
+
+impl<I, J> Iterator for DecodedValues<'_, I, J>
+where
+    I: Iterator<Item = f32>,
+    J: Iterator<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Plain(inner) => inner.next(),
+            Self::Diagnosed(inner, num_produced) => {
+                let value = inner.next();
+                if value.is_some() {
+                    num_produced.set(num_produced.get() + 1);
+                }
+                value
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Plain(inner) => inner.size_hint(),
+            Self::Diagnosed(inner, _) => inner.size_hint(),
+        }
+    }
+
+    // Iterates without checking the variant for each value.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self {
+            Self::Plain(inner) => inner.fold(init, f),
+            Self::Diagnosed(inner, num_produced) => inner.fold(init, |acc, value| {
+                num_produced.set(num_produced.get() + 1);
+                f(acc, value)
+            }),
+        }
+    }
 }
 
 // Rust does not allow modification of generics type parameters or where clauses
 // in conditonal compilation at this time. This is a trick to allow compilation
-// even when JPEG 2000 code stream format support is not available (there may be
-// a better way).
-#[cfg(target_arch = "wasm32")]
-type Grib2ValueIterator<T0, T2, T3, T41> =
-    Grib2SubmessageDecoderIteratorWrapper<T0, T2, T3, std::vec::IntoIter<f32>, T41>;
-#[cfg(not(target_arch = "wasm32"))]
-type Grib2ValueIterator<T0, T2, T3, T40, T41> =
-    Grib2SubmessageDecoderIteratorWrapper<T0, T2, T3, T40, T41>;
-
-enum Grib2SubmessageDecoderIteratorWrapper<T0, T2, T3, T40, T41> {
+// even when JPEG 2000 code stream format support or PNG support is not available
+// (there may be a better way).
+#[cfg(not(feature = "std"))]
+type Grib2ValueIterator<'a, P, T0, T2, T3> = Grib2SubmessageDecoderIteratorWrapper<
+    'a,
+    P,
+    T0,
+    T2,
+    T3,
+    vec::IntoIter<f32>,
+    vec::IntoIter<f32>,
+>;
+#[cfg(all(feature = "std", not(jpeg2000)))]
+type Grib2ValueIterator<'a, P, T0, T2, T3, T41> =
+    Grib2SubmessageDecoderIteratorWrapper<'a, P, T0, T2, T3, vec::IntoIter<f32>, T41>;
+#[cfg(jpeg2000)]
+type Grib2ValueIterator<'a, P, T0, T2, T3, T40, T41> =
+    Grib2SubmessageDecoderIteratorWrapper<'a, P, T0, T2, T3, T40, T41>;
+
+enum Grib2SubmessageDecoderIteratorWrapper<'a, P, T0, T2, T3, T40, T41> {
     Template0(SimplePackingDecodeIteratorWrapper<T0>),
     Template2(SimplePackingDecodeIteratorWrapper<T2>),
     Template3(SimplePackingDecodeIteratorWrapper<T3>),
+    Template4(IeeeDecodeIterator<'a, P>),
     #[allow(dead_code)]
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(not(jpeg2000))]
     Template40(PhantomData<T40>),
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(jpeg2000)]
     Template40(SimplePackingDecodeIteratorWrapper<T40>),
+    #[allow(dead_code)]
+    #[cfg(not(feature = "std"))]
+    Template41(PhantomData<T41>),
+    #[cfg(feature = "std")]
     Template41(SimplePackingDecodeIteratorWrapper<T41>),
-    Template200(std::vec::IntoIter<f32>),
+    Template50(SpectralSimpleDecodeIterator<'a, P>),
+    Template200(RunLengthDecodeIterator<P>),
+    // for data without any packed values
+    Empty,
 }
 
-impl<T0, T2, T3, T40, T41> Iterator for Grib2SubmessageDecoderIteratorWrapper<T0, T2, T3, T40, T41>
+impl<P, T0, T2, T3, T40, T41> Iterator
+    for Grib2SubmessageDecoderIteratorWrapper<'_, P, T0, T2, T3, T40, T41>
 where
+    P: ReadPosition,
     T0: Iterator,
     <T0 as Iterator>::Item: ToPrimitive,
     T2: Iterator,
@@ -207,12 +855,18 @@ where
             Self::Template0(inner) => inner.next(),
             Self::Template2(inner) => inner.next(),
             Self::Template3(inner) => inner.next(),
-            #[cfg(not(target_arch = "wasm32"))]
+            Self::Template4(inner) => inner.next(),
+            #[cfg(jpeg2000)]
             Self::Template40(inner) => inner.next(),
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(not(jpeg2000))]
             Self::Template40(_) => unreachable!(),
+            #[cfg(feature = "std")]
             Self::Template41(inner) => inner.next(),
+            #[cfg(not(feature = "std"))]
+            Self::Template41(_) => unreachable!(),
+            Self::Template50(inner) => inner.next(),
             Self::Template200(inner) => inner.next(),
+            Self::Empty => None,
         }
     }
 
@@ -221,27 +875,42 @@ where
             Self::Template0(inner) => inner.size_hint(),
             Self::Template2(inner) => inner.size_hint(),
             Self::Template3(inner) => inner.size_hint(),
-            #[cfg(not(target_arch = "wasm32"))]
+            Self::Template4(inner) => inner.size_hint(),
+            #[cfg(jpeg2000)]
             Self::Template40(inner) => inner.size_hint(),
-            #[cfg(target_arch = "wasm32")]
+            #[cfg(not(jpeg2000))]
             Self::Template40(_) => unreachable!(),
+            #[cfg(feature = "std")]
             Self::Template41(inner) => inner.size_hint(),
+            #[cfg(not(feature = "std"))]
+            Self::Template41(_) => unreachable!(),
+            Self::Template50(inner) => inner.size_hint(),
             Self::Template200(inner) => inner.size_hint(),
+            Self::Empty => (0, Some(0)),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum DecodeError {
+    #[deprecated(
+        note = "This error is replaced with GribError::UnsupportedTemplate and no more used"
+    )]
     TemplateNumberUnsupported,
     BitMapIndicatorUnsupported,
     SimplePackingDecodeError(SimplePackingDecodeError),
     ComplexPackingDecodeError(ComplexPackingDecodeError),
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(jpeg2000)]
     Jpeg2000CodeStreamDecodeError(Jpeg2000CodeStreamDecodeError),
+    #[cfg(feature = "std")]
     PngDecodeError(PngDecodeError),
     RunLengthEncodingDecodeError(RunLengthEncodingDecodeError),
+    IeeeDecodeError(IeeeDecodeError),
     LengthMismatch,
+    UnsupportedBitWidth(u8),
+    NonFiniteReferenceValue,
+    SectionReadError(u8, ParseError),
 }
 
 impl From<SimplePackingDecodeError> for DecodeError {
@@ -256,7 +925,7 @@ impl From<ComplexPackingDecodeError> for DecodeError {
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(jpeg2000)]
 impl From<Jpeg2000CodeStreamDecodeError> for DecodeError {
     fn from(e: Jpeg2000CodeStreamDecodeError) -> Self {
         Self::Jpeg2000CodeStreamDecodeError(e)
@@ -269,12 +938,583 @@ impl From<RunLengthEncodingDecodeError> for DecodeError {
     }
 }
 
+impl From<IeeeDecodeError> for DecodeError {
+    fn from(e: IeeeDecodeError) -> Self {
+        Self::IeeeDecodeError(e)
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            #[allow(deprecated)]
+            Self::TemplateNumberUnsupported => write!(f, "template number not supported"),
+            Self::BitMapIndicatorUnsupported => write!(f, "bit map indicator not supported"),
+            Self::SimplePackingDecodeError(_) => write!(f, "error in decoding simple packing"),
+            Self::ComplexPackingDecodeError(_) => {
+                write!(f, "error in decoding complex packing")
+            }
+            #[cfg(jpeg2000)]
+            Self::Jpeg2000CodeStreamDecodeError(_) => {
+                write!(f, "error in decoding JPEG 2000 code stream")
+            }
+            #[cfg(feature = "std")]
+            Self::PngDecodeError(_) => write!(f, "error in decoding PNG"),
+            Self::RunLengthEncodingDecodeError(_) => {
+                write!(f, "error in decoding run length encoding")
+            }
+            Self::IeeeDecodeError(_) => write!(f, "error in decoding IEEE floating point data"),
+            Self::LengthMismatch => write!(f, "number of decoded values does not match"),
+            Self::UnsupportedBitWidth(nbit) => write!(f, "bit width {nbit} not supported"),
+            Self::NonFiniteReferenceValue => write!(f, "reference value is not finite"),
+            Self::SectionReadError(num, _) => write!(f, "failed to read Section {num}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SimplePackingDecodeError(e) => Some(e),
+            Self::ComplexPackingDecodeError(e) => Some(e),
+            #[cfg(jpeg2000)]
+            Self::Jpeg2000CodeStreamDecodeError(e) => Some(e),
+            Self::PngDecodeError(e) => Some(e),
+            Self::RunLengthEncodingDecodeError(e) => Some(e),
+            Self::IeeeDecodeError(e) => Some(e),
+            Self::SectionReadError(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SimplePackingDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::OriginalFieldValueTypeNotSupported => {
+                write!(f, "type of original field values not supported")
+            }
+            Self::LengthMismatch => write!(f, "number of decoded values does not match"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SimplePackingDecodeError {}
+
+impl Display for ComplexPackingDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::LengthMismatch => write!(f, "number of decoded values does not match"),
+            Self::GroupLengthOverflow => write!(f, "group lengths overflow"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ComplexPackingDecodeError {}
+
+#[cfg(jpeg2000)]
+impl Display for Jpeg2000CodeStreamDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::DecoderSetupError => write!(f, "failed to set up the decoder"),
+            Self::MainHeaderReadError => write!(f, "failed to read the main header"),
+            Self::BodyReadError => write!(f, "failed to read the body"),
+            Self::LengthMismatch => write!(f, "number of decoded values does not match"),
+        }
+    }
+}
+
+#[cfg(jpeg2000)]
+impl Error for Jpeg2000CodeStreamDecodeError {}
+
+#[cfg(feature = "std")]
+impl Display for PngDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::PngError(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PngDecodeError {}
+
+impl Display for RunLengthEncodingDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "not supported"),
+            Self::InvalidFirstValue => write!(f, "invalid first value"),
+            Self::LengthMismatch => write!(f, "number of decoded values does not match"),
+            Self::InvalidLevelValue(level) => write!(f, "invalid level value: {level}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RunLengthEncodingDecodeError {}
+
+impl Display for IeeeDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::PrecisionNotSupported(precision) => {
+                write!(f, "precision {precision} not supported")
+            }
+            Self::LengthMismatch => write!(f, "length of data does not match"),
+            Self::PrecisionMissing => write!(f, "precision not found in Section 5"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for IeeeDecodeError {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{BufReader, Read},
+    };
+
+    use super::*;
+
+    macro_rules! test_packing_kind {
+        ($(($name:ident, $template:expr, $expected:expr, $supported:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let kind = PackingKind::from($template);
+                assert_eq!(kind, $expected);
+                assert_eq!(kind.is_supported(), $supported);
+            }
+        )*);
+    }
+
+    test_packing_kind! {
+        (packing_kind_for_template_0, 0, PackingKind::Simple, true),
+        (packing_kind_for_template_2, 2, PackingKind::Complex, true),
+        (
+            packing_kind_for_template_3,
+            3,
+            PackingKind::ComplexWithSpatialDifferencing,
+            true
+        ),
+        (packing_kind_for_template_4, 4, PackingKind::Ieee, true),
+        (
+            packing_kind_for_template_40,
+            40,
+            PackingKind::Jpeg2000,
+            cfg!(jpeg2000)
+        ),
+        (packing_kind_for_template_41, 41, PackingKind::Png, true),
+        (packing_kind_for_template_42, 42, PackingKind::Ccsds, false),
+        (packing_kind_for_template_51, 51, PackingKind::SpectralComplex, false),
+        (packing_kind_for_template_200, 200, PackingKind::RunLength, true),
+        (packing_kind_for_unknown_template, 61, PackingKind::Other(61), false),
+    }
+
+    #[test]
+    fn supported_packing_templates_consistent_with_packing_kinds() {
+        let templates = supported_packing_templates();
+        assert_eq!(templates.contains(&40), cfg!(jpeg2000));
+        for num in 0..=u16::MAX {
+            assert_eq!(
+                templates.contains(&num),
+                PackingKind::from(num).is_supported(),
+                "template {num}"
+            );
+        }
+    }
+
+    #[test]
+    fn source_chain_of_decode_error() {
+        let err = crate::GribError::DecodeError(DecodeError::RunLengthEncodingDecodeError(
+            RunLengthEncodingDecodeError::InvalidLevelValue(3),
+        ));
+        let source = err.source().expect("source not found");
+        assert_eq!(source.to_string(), "error in decoding run length encoding");
+        let source = source.source().expect("source not found");
+        assert_eq!(
+            source.downcast_ref::<RunLengthEncodingDecodeError>(),
+            Some(&RunLengthEncodingDecodeError::InvalidLevelValue(3))
+        );
+        assert_eq!(source.to_string(), "invalid level value: 3");
+    }
+
+    #[test]
+    fn decoder_construction_for_unsupported_template() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = std::fs::read(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let sect5_offset = {
+            let grib2 = crate::from_slice(&buf)?;
+            let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+            submessage.5.body.offset
+        };
+        // replaces the template number with that for CCSDS (5.42)
+        buf[sect5_offset + 9..sect5_offset + 11].copy_from_slice(&[0x00, 0x2a]);
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let result = Grib2SubmessageDecoder::from(submessage);
+        assert_eq!(
+            result.err(),
+            Some(GribError::UnsupportedTemplate {
+                section: 5,
+                number: 42
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decoder_construction_with_failure_in_reading_section(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        struct BreakableReader<R> {
+            inner: R,
+            broken: std::rc::Rc<std::cell::Cell<bool>>,
+        }
+
+        impl<R: Read> Read for BreakableReader<R> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.broken.get() {
+                    return Err(std::io::Error::other("broken"));
+                }
+                self.inner.read(buf)
+            }
+        }
+
+        impl<R: std::io::Seek> std::io::Seek for BreakableReader<R> {
+            fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+                self.inner.seek(pos)
+            }
+        }
+
+        let f = File::open(
+            "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+        )?;
+        let broken = std::rc::Rc::new(std::cell::Cell::new(false));
+        let reader = BreakableReader {
+            inner: BufReader::new(f),
+            broken: broken.clone(),
+        };
+        let grib2 = crate::from_reader(reader)?;
+        broken.set(true);
+
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let result = Grib2SubmessageDecoder::from(submessage);
+        assert_eq!(
+            result.err(),
+            Some(GribError::DecodeError(DecodeError::SectionReadError(
+                5,
+                ParseError::ReadError("broken".to_owned())
+            )))
+        );
+        Ok(())
+    }
+
+    macro_rules! test_diagnostics {
+        ($(($name:ident, $xz_compressed_input:expr, $input:expr, $packing_kind:expr),)*) => ($(
+            #[test]
+            fn $name() -> Result<(), Box<dyn std::error::Error>> {
+                let mut buf = Vec::new();
+                let f = BufReader::new(File::open($input)?);
+                if $xz_compressed_input {
+                    xz2::bufread::XzDecoder::new(f).read_to_end(&mut buf)?;
+                } else {
+                    let mut f = f;
+                    f.read_to_end(&mut buf)?;
+                }
+                let grib2 = crate::from_slice(&buf)?;
+                let (_, submessage) = grib2.iter().next().ok_or("first submessage not found")?;
+                let decoder = Grib2SubmessageDecoder::from(submessage)?;
+                assert_eq!(decoder.packing_kind(), $packing_kind);
+                assert_eq!(decoder.diagnostics(), None);
+
+                let decoder = decoder.with_diagnostics();
+                decoder.dispatch()?.take(100).for_each(drop);
+                let partial = decoder.diagnostics().ok_or("diagnostics not recorded")?;
+                assert_eq!(partial.num_values_produced, 100);
+                assert!(partial.sect7_bytes_consumed > 0);
+                assert!(partial.sect7_bytes_consumed < partial.sect7_payload_size);
+
+                let num_values = decoder.dispatch()?.count();
+                let actual = decoder.diagnostics().ok_or("diagnostics not recorded")?;
+                let expected = DecodeDiagnostics {
+                    sect7_bytes_consumed: decoder.sect7_payload.len(),
+                    sect7_payload_size: decoder.sect7_payload.len(),
+                    num_values_produced: num_values,
+                };
+                assert_eq!(actual, expected);
+                Ok(())
+            }
+        )*);
+    }
+
+    test_diagnostics! {
+        (
+            diagnostics_of_simple_packing,
+            true,
+            "testdata/Z__C_RJTD_20170221120000_MSG_GPV_Gll0p5deg_Pys_B20170221120000_F2017022115-2017022212_grib2.bin.xz",
+            PackingKind::Simple
+        ),
+        (
+            diagnostics_of_complex_packing,
+            true,
+            "testdata/wind_solar_ind_0.125_20240521_12Z.grib2.0.xz",
+            PackingKind::ComplexWithSpatialDifferencing
+        ),
+        (
+            diagnostics_of_run_length_packing,
+            false,
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+            PackingKind::RunLength
+        ),
+    }
+
+    #[test]
+    fn decoding_into_reused_buffer() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+
+        let mut buf = vec![0.0_f32; 86016];
+        for (_, submessage) in grib2.iter().take(2) {
+            let decoder = Grib2SubmessageDecoder::from(submessage)?;
+            decoder.decode_into(&mut buf)?;
+            let expected = decoder.dispatch()?.collect::<Vec<_>>();
+            assert!(buf
+                .iter()
+                .zip(expected.iter())
+                .all(|(a, e)| a == e || (a.is_nan() && e.is_nan())));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn decoding_into_buffer_with_wrong_length() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let decoder = Grib2SubmessageDecoder::from(submessage)?;
+
+        let mut buf = vec![1.0_f32; 86015];
+        let result = decoder.decode_into(&mut buf);
+        assert_eq!(
+            result,
+            Err(GribError::InvalidValueError(
+                "length of output buffer does not match: 86015 (buffer) vs 86016 (grid points)"
+                    .to_owned()
+            ))
+        );
+        assert!(buf.iter().all(|v| *v == 1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn decode_values_from_section_slices() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(
+            "testdata/Z__C_RJTD_20170221120000_MSG_GPV_Gll0p5deg_Pys_B20170221120000_F2017022115-2017022212_grib2.bin.xz",
+        )?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2
+            .iter()
+            .find(|(index, _)| *index == (0, 3))
+            .ok_or("submessage not found")?;
+        let num_points = submessage.grid_def().num_points() as usize;
+        let carve = |sect: &crate::SectionInfo| &buf[sect.offset..sect.offset + sect.size];
+        let sect5 = carve(submessage.5.body);
+        let sect6 = carve(submessage.6.body);
+        let sect7 = carve(submessage.7.body);
+
+        let actual = decode_values(sect5, sect6, sect7, num_points)?;
+
+        let mut expected = Vec::new();
+        let f = File::open("testdata/gen/kousa-wgrib2-le.bin.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut expected)?;
+        let expected = expected
+            .chunks(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual.len(), num_points);
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_ieee_values_with_bitmap_from_section_slices() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let values = [285.25_f32, -1.0e-3, 12345.678];
+        let sect5 = [
+            0x00, 0x00, 0x00, 0x0c, 0x05, 0x00, 0x00, 0x00, 0x03, 0x00, 0x04, 0x01,
+        ];
+        let sect6 = [0x00, 0x00, 0x00, 0x07, 0x06, 0x00, 0b10110000];
+        let mut sect7 = vec![0x00, 0x00, 0x00, 0x11, 0x07];
+        sect7.extend(values.iter().flat_map(|v| v.to_be_bytes()));
+
+        let actual = decode_values(&sect5, &sect6, &sect7, 4)?;
+        let expected = [values[0], f32::NAN, values[1], values[2]];
+        assert_eq!(actual.len(), expected.len());
+        assert!(actual
+            .iter()
+            .zip(expected.iter())
+            .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())));
+        Ok(())
+    }
+
+    #[test]
+    fn decode_values_with_empty_section_7_and_all_points_missing(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let sect5 = [
+            0x00, 0x00, 0x00, 0x31, 0x05, // header
+            0x00, 0x00, 0x00, 0x00, // number of values
+            0x00, 0x03, // template number
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, // simple packing
+            0x01, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // complex packing
+            0x00, 0x00, 0x00, 0x00, // number of groups
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, // group widths and lengths
+            0x02, 0x01, // spatial differencing
+        ];
+        let sect6 = [0x00, 0x00, 0x00, 0x08, 0x06, 0x00, 0x00, 0x00];
+        let sect7 = [0x00, 0x00, 0x00, 0x05, 0x07];
+
+        let actual = decode_values(&sect5, &sect6, &sect7, 10)?;
+        assert_eq!(actual.len(), 10);
+        assert!(actual.iter().all(|v| v.is_nan()));
+        Ok(())
+    }
+
+    #[test]
+    fn decode_values_with_spectral_complex_packing() {
+        let sect5 = [
+            0x00, 0x00, 0x00, 0x24, 0x05, // header
+            0x00, 0x00, 0x00, 0x01, // number of values
+            0x00, 0x33, // template number
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, // simple packing
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+            0x01, // spectral complex packing
+        ];
+        let sect6 = [0x00, 0x00, 0x00, 0x06, 0x06, 0xff];
+        let sect7 = [0x00, 0x00, 0x00, 0x09, 0x07, 0x00, 0x00, 0x00, 0x00];
+
+        let result = decode_values(&sect5, &sect6, &sect7, 1);
+        assert!(matches!(result, Err(GribError::NotSupported(_))));
+    }
+
+    macro_rules! test_decode_values_with_truncated_section_5 {
+        ($(($name:ident, $template:expr, $sect5_payload_len:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let template: u16 = $template;
+                let mut sect5 = vec![0x00; 5 + $sect5_payload_len];
+                sect5[3] = (5 + $sect5_payload_len) as u8;
+                sect5[4] = 0x05;
+                sect5[8] = 0x04; // 4 values
+                sect5[9..11].copy_from_slice(&template.to_be_bytes());
+                let sect6 = [0x00, 0x00, 0x00, 0x06, 0x06, 0xff];
+                let sect7 = [0x00, 0x00, 0x00, 0x07, 0x07, 0xff, 0xff];
+
+                let result = decode_values(&sect5, &sect6, &sect7, 4);
+                assert_eq!(
+                    result,
+                    Err(GribError::InvalidValueError(format!(
+                        "Section 5 too short for template 5.{template}: {} octets",
+                        sect5.len()
+                    )))
+                );
+            }
+        )*);
+    }
+
+    test_decode_values_with_truncated_section_5! {
+        (decode_values_with_truncated_section_5_for_simple_packing, 0, 6),
+        (decode_values_with_truncated_section_5_for_complex_packing, 2, 41),
+        (
+            decode_values_with_truncated_section_5_for_complex_packing_with_spatial_differencing,
+            3,
+            43
+        ),
+        (decode_values_with_truncated_section_5_for_png, 41, 15),
+        (decode_values_with_truncated_section_5_for_run_length_packing, 200, 10),
+    }
+
+    #[test]
+    fn decode_values_with_truncated_section_5_for_ieee() {
+        let sect5 = [
+            0x00, 0x00, 0x00, 0x0b, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04,
+        ];
+        let sect6 = [0x00, 0x00, 0x00, 0x06, 0x06, 0xff];
+        let sect7 = [0x00, 0x00, 0x00, 0x09, 0x07, 0x3f, 0x80, 0x00, 0x00];
+
+        let result = decode_values(&sect5, &sect6, &sect7, 1);
+        assert_eq!(
+            result,
+            Err(GribError::DecodeError(DecodeError::IeeeDecodeError(
+                IeeeDecodeError::PrecisionMissing
+            )))
+        );
+    }
+
+    #[test]
+    fn decode_values_with_run_length_level_values_truncated() {
+        // maximum level 2 requires 2 level values of 2 octets each
+        let sect5 = [
+            0x00, 0x00, 0x00, 0x13, 0x05, 0x00, 0x00, 0x00, 0x04, 0x00, 0xc8, 0x08, 0x00, 0x03,
+            0x00, 0x02, 0x01, 0x00, 0x01,
+        ];
+        let sect6 = [0x00, 0x00, 0x00, 0x06, 0x06, 0xff];
+        let sect7 = [0x00, 0x00, 0x00, 0x07, 0x07, 0x01, 0x02];
+
+        let result = decode_values(&sect5, &sect6, &sect7, 4);
+        assert_eq!(
+            result,
+            Err(GribError::InvalidValueError(
+                "Section 5 too short for template 5.200: 19 octets".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn decode_values_with_wrong_section() {
+        let sect = [0x00, 0x00, 0x00, 0x06, 0x06, 0xff];
+        let result = decode_values(&sect, &sect, &sect, 0);
+        assert_eq!(
+            result,
+            Err(GribError::InvalidValueError(
+                "Section 5 not found in the given bytes".to_owned()
+            ))
+        );
+    }
+}
+
 mod bitmap;
 mod complex;
-#[cfg(not(target_arch = "wasm32"))]
+mod ieee;
+#[cfg(jpeg2000)]
 mod jpeg2000;
 mod param;
+#[cfg(feature = "std")]
 mod png;
 mod run_length;
 mod simple;
+mod spectral;
 mod stream;
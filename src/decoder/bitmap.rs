@@ -1,4 +1,5 @@
-use std::iter::Peekable;
+use alloc::{vec, vec::Vec};
+use core::iter::Peekable;
 
 use crate::{decoder::DecodeError, error::GribError};
 
@@ -7,6 +8,7 @@ pub(crate) struct BitmapDecodeIterator<B: Iterator, I> {
     values: I,
     len: usize,
     offset: usize,
+    pos: usize,
 }
 
 impl<'b, B, I> BitmapDecodeIterator<B, I>
@@ -23,6 +25,7 @@ where
             values,
             len,
             offset: 0,
+            pos: 0,
         })
     }
 }
@@ -36,7 +39,7 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let offset = self.offset;
-        if offset >= self.len {
+        if self.pos >= self.len {
             return None;
         }
         let byte = if self.offset < 7 {
@@ -47,15 +50,17 @@ where
             self.bitmap.next()?
         };
 
-        if has_zero_at_offset(byte, &offset) {
+        let value = if has_zero_at_offset(byte, &offset) {
             Some(f32::NAN)
         } else {
             self.values.next()
-        }
+        };
+        self.pos += 1;
+        value
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.len - self.offset;
+        let size = self.len - self.pos;
         (size, Some(size))
     }
 }
@@ -67,6 +72,36 @@ fn has_zero_at_offset(byte: &u8, offset: &usize) -> bool {
     masked == 0
 }
 
+// Checks if any of the first `num_points` bits in `bitmap` is set.
+pub(crate) fn has_present_points(bitmap: &[u8], num_points: usize) -> bool {
+    let (div, mod_) = (num_points / 8, num_points % 8);
+    let whole_bytes = bitmap.iter().take(div).any(|byte| *byte != 0);
+    let last_byte = mod_ > 0
+        && bitmap
+            .get(div)
+            .is_some_and(|byte| byte & !(0xff >> mod_) != 0);
+    whole_bytes || last_byte
+}
+
+// Returns indices of the bits set among the first `num_points` bits in
+// `bitmap`, skipping bytes with no bits set.
+#[cfg(feature = "std")]
+pub(crate) fn present_indices(
+    bitmap: &[u8],
+    num_points: usize,
+) -> impl Iterator<Item = usize> + '_ {
+    bitmap
+        .iter()
+        .enumerate()
+        .filter(|(_, byte)| **byte != 0)
+        .flat_map(|(i, byte)| {
+            (0..8)
+                .filter(move |offset| !has_zero_at_offset(byte, offset))
+                .map(move |offset| i * 8 + offset)
+        })
+        .take_while(move |index| *index < num_points)
+}
+
 pub(crate) fn create_bitmap_for_nonnullable_data(num_points: usize) -> Vec<u8> {
     let (div, mod_) = (num_points / 8, num_points % 8);
     if mod_ == 0 {
@@ -80,6 +115,13 @@ pub(crate) fn create_bitmap_for_nonnullable_data(num_points: usize) -> Vec<u8> {
 mod test {
     use super::*;
 
+    #[test]
+    fn present_indices_in_bitmap() {
+        let bitmap = [0b01001100u8, 0b00000000, 0b11110000];
+        let actual = present_indices(&bitmap, 19).collect::<Vec<_>>();
+        assert_eq!(actual, vec![1, 4, 5, 16, 17, 18]);
+    }
+
     #[test]
     fn bitmap_iterator_works() {
         let bitmap = [0b01001100u8, 0b01110000, 0b11110000];
@@ -115,10 +157,56 @@ mod test {
             f32::NAN,
         ];
 
-        actual
+        assert_eq!(actual.len(), expected.len());
+        assert!(actual
             .iter()
             .zip(expected.iter())
-            .all(|(a, b)| (a.is_nan() && b.is_nan()) || (a == b));
+            .all(|(a, b)| (a.is_nan() && b.is_nan()) || (a == b)));
+    }
+
+    #[test]
+    fn bitmap_iterator_ignores_padding_bits() {
+        // 17 points; the last 7 bits of the third byte are padding and are set.
+        let bitmap = [0b11111111u8, 0b00000000, 0b11111111];
+        let values = (0..10).map(|n| n as f32).collect::<Vec<_>>();
+        let values = values.into_iter();
+
+        let iter = BitmapDecodeIterator::new(bitmap.iter(), values, 17).unwrap();
+        let actual = iter.collect::<Vec<_>>();
+
+        assert_eq!(actual.len(), 17);
+        assert_eq!(actual[..8], [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert!(actual[8..16].iter().all(|v| v.is_nan()));
+        assert_eq!(actual[16], 8.0);
+    }
+
+    #[test]
+    fn bitmap_iterator_with_bitmap_shorter_than_number_of_points() {
+        let bitmap = [0b11111111u8, 0b11111111];
+        let values = (0..17).map(|n| n as f32);
+
+        let result = BitmapDecodeIterator::new(bitmap.iter(), values, 17);
+        assert!(matches!(
+            result,
+            Err(GribError::DecodeError(DecodeError::LengthMismatch))
+        ));
+    }
+
+    macro_rules! test_present_point_check {
+        ($(($name:ident, $bitmap:expr, $num_points:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                assert_eq!(has_present_points(&$bitmap, $num_points), $expected);
+            }
+        )*);
+    }
+
+    test_present_point_check! {
+        (present_point_check_for_empty_bitmap, [0b00000000u8; 2], 16, false),
+        (present_point_check_for_bitmap_with_first_bit_set, [0b10000000, 0b00000000], 16, true),
+        (present_point_check_for_bitmap_with_last_bit_set, [0b00000000, 0b00000001], 16, true),
+        (present_point_check_for_bitmap_with_only_padding_bits_set, [0b00000000, 0b00111111], 10, false),
+        (present_point_check_for_bitmap_with_last_point_present, [0b00000000, 0b01000000], 10, true),
     }
 
     #[test]
@@ -132,5 +220,7 @@ mod test {
         assert_eq!(iter.size_hint(), (24, Some(24)));
         let _ = iter.next();
         assert_eq!(iter.size_hint(), (23, Some(23)));
+        let _ = iter.nth(8);
+        assert_eq!(iter.size_hint(), (14, Some(14)));
     }
 }
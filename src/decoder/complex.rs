@@ -1,4 +1,5 @@
-use std::iter;
+use alloc::{format, vec, vec::Vec};
+use core::iter;
 
 use num::ToPrimitive;
 
@@ -14,8 +15,8 @@ use crate::{
     decoder::{
         param::{ComplexPackingParam, SimplePackingParam},
         simple::*,
-        stream::{BitStream, NBitwiseIterator},
-        DecodeError, Grib2SubmessageDecoder,
+        stream::{all_ones, BitReader, BitStream, ReadPosition},
+        DecodeError,
     },
     error::*,
     helpers::{read_as, GribInt},
@@ -25,17 +26,20 @@ use crate::{
 pub enum ComplexPackingDecodeError {
     NotSupported,
     LengthMismatch,
+    GroupLengthOverflow,
 }
 
-pub(crate) fn decode_7_2(
-    target: &Grib2SubmessageDecoder,
+pub(crate) fn decode_7_2<'a, P: ReadPosition + 'a>(
+    sect5_data: &[u8],
+    sect7_data: &'a [u8],
+    position: P,
 ) -> Result<
-    SimplePackingDecodeIteratorWrapper<impl Iterator<Item = DecodedValue<i32>> + '_>,
+    SimplePackingDecodeIteratorWrapper<impl Iterator<Item = DecodedValue<i32>> + 'a>,
     GribError,
 > {
-    let sect5_data = &target.sect5_payload;
     let simple_param = SimplePackingParam::from_buf(&sect5_data[6..16])?;
     let complex_param = ComplexPackingParam::from_buf(&sect5_data[16..42]);
+    complex_param.check_bit_widths()?;
 
     if complex_param.group_splitting_method_used != 1
         || complex_param.missing_value_management_used > 2
@@ -45,23 +49,32 @@ pub(crate) fn decode_7_2(
         ));
     }
 
-    let sect7_data = &target.sect7_payload;
-
-    let unpacked_data = decode_complex_packing(complex_param, sect7_data, 0, simple_param.nbit, 0);
+    let num_values = read_as!(u32, sect5_data, 0);
+    let unpacked_data = decode_complex_packing(
+        complex_param,
+        num_values,
+        sect7_data,
+        0,
+        simple_param.nbit,
+        0,
+        position,
+    )?;
     let decoder = SimplePackingDecodeIterator::new(unpacked_data, &simple_param);
     let decoder = SimplePackingDecodeIteratorWrapper::SimplePacking(decoder);
     Ok(decoder)
 }
 
-pub(crate) fn decode_7_3(
-    target: &Grib2SubmessageDecoder,
+pub(crate) fn decode_7_3<'a, P: ReadPosition + 'a>(
+    sect5_data: &[u8],
+    sect7_data: &'a [u8],
+    position: P,
 ) -> Result<
-    SimplePackingDecodeIteratorWrapper<impl Iterator<Item = DecodedValue<i32>> + '_>,
+    SimplePackingDecodeIteratorWrapper<impl Iterator<Item = DecodedValue<i32>> + 'a>,
     GribError,
 > {
-    let sect5_data = &target.sect5_payload;
     let simple_param = SimplePackingParam::from_buf(&sect5_data[6..16])?;
     let complex_param = ComplexPackingParam::from_buf(&sect5_data[16..42]);
+    complex_param.check_bit_widths()?;
     let spdiff_order = read_as!(u8, sect5_data, 42);
     let spdiff_order = Table5_6::try_from(spdiff_order).map_err(|e| {
         let number = e.number;
@@ -78,22 +91,25 @@ pub(crate) fn decode_7_3(
         ));
     }
 
-    let sect7_data = &target.sect7_payload;
     let sect7_params = diff::SpatialDifferencingExtraDescriptors::new(
         sect7_data,
         u8::from(spdiff_order.clone()),
         spdiff_param_octet,
     )?;
 
+    let first_values = sect7_params.first_values();
+    let first_values = first_values.collect::<Vec<_>>().into_iter();
+    position.record(sect7_params.len() * 8);
+    let num_values = read_as!(u32, sect5_data, 0);
     let unpacked_data = decode_complex_packing(
         complex_param,
+        num_values,
         sect7_data,
         sect7_params.len(),
         simple_param.nbit,
         sect7_params.minimum(),
-    );
-    let first_values = sect7_params.first_values();
-    let first_values = first_values.collect::<Vec<_>>().into_iter();
+        position,
+    )?;
     let spdiff_unpacked = match spdiff_order {
         Table5_6::FirstOrderSpatialDifferencing => SpatialDifferencingDecodeIterator::FirstOrder(
             FirstOrderSpatialDifferencingDecodeIterator::new(unpacked_data, first_values),
@@ -108,78 +124,107 @@ pub(crate) fn decode_7_3(
     Ok(decoder)
 }
 
-fn decode_complex_packing(
+fn decode_complex_packing<'a, P: ReadPosition + 'a>(
     complex_param: ComplexPackingParam,
-    sect7_data: &[u8],
+    num_values: u32,
+    sect7_data: &'a [u8],
     sect7_offset: usize,
     nbit: u8,
     z_min: i32,
-) -> impl Iterator<Item = DecodedValue<i32>> + '_ {
-    fn get_octet_length(nbit: u8, ngroup: u32) -> usize {
-        let total_bit: u32 = ngroup * u32::from(nbit);
-        let total_octet = (total_bit + 0b111) >> 3;
-        total_octet as usize
+    position: P,
+) -> Result<impl Iterator<Item = DecodedValue<i32>> + 'a, GribError> {
+    fn error(e: ComplexPackingDecodeError) -> GribError {
+        GribError::DecodeError(DecodeError::ComplexPackingDecodeError(e))
+    }
+
+    fn get_octet_length(nbit: u8, ngroup: u32) -> Option<usize> {
+        let total_bit = usize::try_from(ngroup)
+            .ok()?
+            .checked_mul(usize::from(nbit))?;
+        Some(total_bit.div_ceil(8))
     }
 
+    // Without groups, no values are encoded.
+    if complex_param.ngroup == 0 && num_values != 0 {
+        return Err(error(ComplexPackingDecodeError::LengthMismatch));
+    }
+
+    // Group lengths are reconstructed from scaled ones, which are at most all
+    // ones in `group_len_nbit` bits.
+    u32::from(complex_param.group_len_inc)
+        .checked_mul(all_ones(usize::from(complex_param.group_len_nbit)))
+        .and_then(|max_len| max_len.checked_add(complex_param.group_len_ref))
+        .ok_or_else(|| error(ComplexPackingDecodeError::GroupLengthOverflow))?;
+
+    let section_end = |start: usize, nbit: u8| {
+        get_octet_length(nbit, complex_param.ngroup)
+            .and_then(|len| start.checked_add(len))
+            .filter(|end| *end <= sect7_data.len())
+            .ok_or_else(|| error(ComplexPackingDecodeError::LengthMismatch))
+    };
     let params_end_octet = sect7_offset;
-    let group_refs_end_octet = params_end_octet + get_octet_length(nbit, complex_param.ngroup);
-    let group_widths_end_octet = group_refs_end_octet
-        + get_octet_length(complex_param.group_width_nbit, complex_param.ngroup);
-    let group_lens_end_octet = group_widths_end_octet
-        + get_octet_length(complex_param.group_len_nbit, complex_param.ngroup);
+    let group_refs_end_octet = section_end(params_end_octet, nbit)?;
+    let group_widths_end_octet = section_end(group_refs_end_octet, complex_param.group_width_nbit)?;
+    let group_lens_end_octet = section_end(group_widths_end_octet, complex_param.group_len_nbit)?;
 
     let group_refs_iter = BitStream::new(
         &sect7_data[params_end_octet..group_refs_end_octet],
         usize::from(nbit),
         complex_param.ngroup as usize,
-    );
+    )
+    .recording(position.clone().with_offset(params_end_octet));
     let group_refs_iter = group_refs_iter.take(complex_param.ngroup as usize);
 
     let group_widths_iter = BitStream::new(
         &sect7_data[group_refs_end_octet..group_widths_end_octet],
         usize::from(complex_param.group_width_nbit),
         complex_param.ngroup as usize,
-    );
+    )
+    .recording(position.clone().with_offset(group_refs_end_octet));
     let group_widths_iter = group_widths_iter
         .take(complex_param.ngroup as usize)
         .map(move |v| u32::from(complex_param.group_width_ref) + v);
 
+    // Scaled group lengths are stored for all groups, but the one for the last
+    // group is superseded by the true length of the last group in Section 5.
+    let num_scaled_group_lens = (complex_param.ngroup as usize).saturating_sub(1);
     let group_lens_iter = BitStream::new(
         &sect7_data[group_widths_end_octet..group_lens_end_octet],
         usize::from(complex_param.group_len_nbit),
-        (complex_param.ngroup - 1) as usize,
-    );
+        num_scaled_group_lens,
+    )
+    .recording(position.clone().with_offset(group_widths_end_octet));
     let group_lens_iter = group_lens_iter
-        .take((complex_param.ngroup - 1) as usize)
+        .take(num_scaled_group_lens)
         .map(move |v| complex_param.group_len_ref + u32::from(complex_param.group_len_inc) * v)
         .chain(iter::once(complex_param.group_len_last));
 
-    ComplexPackingValueDecodeIterator::new(
+    let iter = ComplexPackingValueDecodeIterator::new(
         group_refs_iter,
         group_widths_iter,
         group_lens_iter,
         complex_param.missing_value_management_used,
         nbit,
         z_min,
-        sect7_data[group_lens_end_octet..].to_vec(),
+        BitReader::new(sect7_data[group_lens_end_octet..].to_vec())
+            .recording(position.with_offset(group_lens_end_octet)),
     )
-    .flatten()
+    .flatten();
+    Ok(iter)
 }
 
 #[derive(Clone)]
-struct ComplexPackingValueDecodeIterator<I, J, K> {
+struct ComplexPackingValueDecodeIterator<I, J, K, P> {
     ref_iter: I,
     width_iter: J,
     length_iter: K,
     missing_value_management: u8,
     nbit: u8,
     z_min: i32,
-    data: Vec<u8>,
-    pos: usize,
-    start_offset_bits: usize,
+    reader: BitReader<Vec<u8>, P>,
 }
 
-impl<I, J, K> ComplexPackingValueDecodeIterator<I, J, K> {
+impl<I, J, K, P> ComplexPackingValueDecodeIterator<I, J, K, P> {
     pub(crate) fn new(
         ref_iter: I,
         width_iter: J,
@@ -187,7 +232,7 @@ impl<I, J, K> ComplexPackingValueDecodeIterator<I, J, K> {
         missing_value_management: u8,
         nbit: u8,
         z_min: i32,
-        data: Vec<u8>,
+        reader: BitReader<Vec<u8>, P>,
     ) -> Self {
         Self {
             ref_iter,
@@ -196,21 +241,20 @@ impl<I, J, K> ComplexPackingValueDecodeIterator<I, J, K> {
             missing_value_management,
             nbit,
             z_min,
-            data,
-            pos: 0,
-            start_offset_bits: 0,
+            reader,
         }
     }
 }
 
-impl<I, J, K, N, O, P> Iterator for ComplexPackingValueDecodeIterator<I, J, K>
+impl<I, J, K, P, N, O, Q> Iterator for ComplexPackingValueDecodeIterator<I, J, K, P>
 where
     I: Iterator<Item = N>,
     J: Iterator<Item = O>,
-    K: Iterator<Item = P>,
+    K: Iterator<Item = Q>,
+    P: ReadPosition,
     N: ToPrimitive,
     O: ToPrimitive,
-    P: ToPrimitive,
+    Q: ToPrimitive,
 {
     type Item = Vec<DecodedValue<i32>>;
 
@@ -223,17 +267,17 @@ where
             (Some(_ref), Some(width), Some(length)) if width.to_usize().unwrap() == 0 => {
                 // The specification states as follows: "For groups with a constant value,
                 // associated field width is 0, and no incremental data are physically present."
-                let _ref = _ref.to_i32().unwrap();
+                let _ref = _ref.to_u32().unwrap();
                 let length = length.to_usize().unwrap();
-                let missing1 = (1 << self.nbit) - 1;
-                let missing2 = missing1 - 1;
+                let missing1 = all_ones(usize::from(self.nbit));
+                let missing2 = missing1.wrapping_sub(1);
 
                 if self.missing_value_management > 0 && _ref == missing1 {
                     Some(vec![Missing1; length])
                 } else if self.missing_value_management == 2 && _ref == missing2 {
                     Some(vec![Missing2; length])
                 } else {
-                    Some(vec![Normal(_ref + self.z_min); length])
+                    Some(vec![Normal(_ref as i32 + self.z_min); length])
                 }
             }
             (Some(_ref), Some(width), Some(length)) => {
@@ -242,27 +286,23 @@ where
                     width.to_usize().unwrap(),
                     length.to_usize().unwrap(),
                 );
-                let bits = self.start_offset_bits + width * length;
-                let (pos_end, offset_bits) = (self.pos + bits / 8, bits % 8);
-                let offset_byte = usize::from(offset_bits > 0);
-                let missing1 = (1 << width) - 1;
+                let missing1 = all_ones(width);
                 let missing2 = missing1 - 1;
-                let group_values =
-                    NBitwiseIterator::new(&self.data[self.pos..pos_end + offset_byte], width)
-                        .with_offset(self.start_offset_bits)
-                        .take(length)
-                        .map(|v| {
-                            if self.missing_value_management > 0 && v == missing1 {
-                                Missing1
-                            } else if self.missing_value_management == 2 && v == missing2 {
-                                Missing2
-                            } else {
-                                Normal(v.as_grib_int() + _ref + self.z_min)
-                            }
-                        })
-                        .collect::<Vec<_>>();
-                self.pos = pos_end;
-                self.start_offset_bits = offset_bits;
+                let missing_value_management = self.missing_value_management;
+                let z_min = self.z_min;
+                let reader = &mut self.reader;
+                let group_values = (0..length)
+                    .map_while(|_| reader.read_bits(width))
+                    .map(|v| {
+                        if missing_value_management > 0 && v == missing1 {
+                            Missing1
+                        } else if missing_value_management == 2 && v == missing2 {
+                            Missing2
+                        } else {
+                            Normal(v.as_grib_int() + _ref + z_min)
+                        }
+                    })
+                    .collect::<Vec<_>>();
                 Some(group_values)
             }
             _ => None,
@@ -270,5 +310,335 @@ where
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{BufReader, Read},
+    };
+
+    use super::*;
+    use crate::decoder::Grib2SubmessageDecoder;
+
+    fn get_uncompressed(file_path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(file_path)?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn complex_packing_sect5_payload(nbit: u8, group_width_nbit: u8) -> Vec<u8> {
+        let mut buf = vec![
+            0x00,
+            0x00,
+            0x00,
+            0x02,
+            0x00,
+            0x02,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            nbit,
+            0x00,
+            0x01,
+            0x00,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            0x00,
+            group_width_nbit,
+            0x00,
+            0x00,
+            0x00,
+            0x02,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x02,
+            0x00,
+        ];
+        // parameters of spatial differencing for template 5.3
+        buf.extend_from_slice(&[0x01, 0x02]);
+        buf
+    }
+
+    macro_rules! test_complex_packing_with_unsupported_bit_width {
+        ($(($name:ident, $decode:expr, $nbit:expr, $group_width_nbit:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let sect5 = complex_packing_sect5_payload($nbit, $group_width_nbit);
+                let sect7 = vec![0x00; 16];
+                let result = $decode(&sect5, &sect7, ()).map(|_| ());
+                assert_eq!(
+                    result,
+                    Err(GribError::DecodeError(DecodeError::UnsupportedBitWidth($expected)))
+                );
+            }
+        )*);
+    }
+
+    test_complex_packing_with_unsupported_bit_width! {
+        (complex_packing_with_40_bit_values, decode_7_2, 40, 1, 40),
+        (complex_packing_with_40_bit_group_widths, decode_7_2, 8, 40, 40),
+        (
+            complex_packing_with_spatial_differencing_with_40_bit_values,
+            decode_7_3,
+            40,
+            1,
+            40
+        ),
+        (
+            complex_packing_with_spatial_differencing_with_40_bit_group_widths,
+            decode_7_3,
+            8,
+            40,
+            40
+        ),
+    }
+
+    #[test]
+    fn complex_packing_with_last_group_length_differing_from_others(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let sect5 = vec![
+            0x00, 0x00, 0x00, 0x0a, // number of values
+            0x00, 0x02, // template number
+            0x00, 0x00, 0x00, 0x00, // reference value
+            0x00, 0x00, // binary scale factor
+            0x00, 0x00, // decimal scale factor
+            0x04, // number of bits for group reference values
+            0x00, // type of original field values
+            0x01, // group splitting method
+            0x00, // missing value management
+            0xff, 0xff, 0xff, 0xff, // primary missing value substitute
+            0xff, 0xff, 0xff, 0xff, // secondary missing value substitute
+            0x00, 0x00, 0x00, 0x03, // number of groups
+            0x01, // reference for group widths
+            0x01, // number of bits for group widths
+            0x00, 0x00, 0x00, 0x02, // reference for group lengths
+            0x03, // length increment for the group lengths
+            0x00, 0x00, 0x00, 0x03, // true length of last group
+            0x02, // number of bits for scaled group lengths
+        ];
+        // group reference values: 1, 5, 10
+        let group_refs = [0b00010101, 0b10100000];
+        // group widths: 1 + (0, 1, 0)
+        let group_widths = [0b01000000];
+        // group lengths: 2 + 3 * (0, 1), where the scaled value for the last group
+        // is ignored
+        let group_lens = [0b00011100];
+        let values = [0b10110001, 0b10110110];
+        let sect7 = [&group_refs[..], &group_widths, &group_lens, &values].concat();
+
+        let actual = decode_7_2(&sect5, &sect7, ())?.collect::<Vec<_>>();
+        let expected = vec![2., 1., 8., 5., 6., 7., 8., 10., 11., 11.];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    macro_rules! test_complex_packing_with_invalid_groups {
+        ($(($name:ident, $ngroup:expr, $group_len_ref:expr, $sect7_len:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let mut sect5 = vec![
+                    0x00, 0x00, 0x00, 0x0a, // number of values
+                    0x00, 0x02, // template number
+                    0x00, 0x00, 0x00, 0x00, // reference value
+                    0x00, 0x00, // binary scale factor
+                    0x00, 0x00, // decimal scale factor
+                    0x04, // number of bits for group reference values
+                    0x00, // type of original field values
+                    0x01, // group splitting method
+                    0x00, // missing value management
+                    0xff, 0xff, 0xff, 0xff, // primary missing value substitute
+                    0xff, 0xff, 0xff, 0xff, // secondary missing value substitute
+                ];
+                sect5.extend_from_slice(&u32::to_be_bytes($ngroup)); // number of groups
+                sect5.extend_from_slice(&[
+                    0x01, // reference for group widths
+                    0x01, // number of bits for group widths
+                ]);
+                sect5.extend_from_slice(&u32::to_be_bytes($group_len_ref)); // reference for group lengths
+                sect5.extend_from_slice(&[
+                    0x03, // length increment for the group lengths
+                    0x00, 0x00, 0x00, 0x03, // true length of last group
+                    0x02, // number of bits for scaled group lengths
+                ]);
+                let sect7 = vec![0; $sect7_len];
+
+                let actual = decode_7_2(&sect5, &sect7, ()).err();
+                let expected = Some(GribError::DecodeError(
+                    DecodeError::ComplexPackingDecodeError($expected),
+                ));
+                assert_eq!(actual, expected);
+            }
+        )*);
+    }
+
+    test_complex_packing_with_invalid_groups! {
+        (
+            complex_packing_without_groups,
+            0,
+            2,
+            5,
+            ComplexPackingDecodeError::LengthMismatch
+        ),
+        (
+            complex_packing_with_overflowing_group_lengths,
+            3,
+            u32::MAX - 8,
+            5,
+            ComplexPackingDecodeError::GroupLengthOverflow
+        ),
+        (
+            complex_packing_with_group_descriptors_exceeding_section_7,
+            3,
+            2,
+            2,
+            ComplexPackingDecodeError::LengthMismatch
+        ),
+    }
+
+    #[test]
+    fn complex_packing_with_spatial_differencing_with_10_bit_group_reference_values(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let sect5 = vec![
+            0x00, 0x00, 0x00, 0x06, // number of values
+            0x00, 0x03, // template number
+            0x00, 0x00, 0x00, 0x00, // reference value
+            0x00, 0x00, // binary scale factor
+            0x00, 0x00, // decimal scale factor
+            0x0a, // number of bits for group reference values
+            0x00, // type of original field values
+            0x01, // group splitting method
+            0x00, // missing value management
+            0xff, 0xff, 0xff, 0xff, // primary missing value substitute
+            0xff, 0xff, 0xff, 0xff, // secondary missing value substitute
+            0x00, 0x00, 0x00, 0x03, // number of groups
+            0x00, // reference for group widths
+            0x01, // number of bits for group widths
+            0x00, 0x00, 0x00, 0x02, // reference for group lengths
+            0x01, // length increment for the group lengths
+            0x00, 0x00, 0x00, 0x02, // true length of last group
+            0x01, // number of bits for scaled group lengths
+            0x01, // order of spatial differencing
+            0x02, // number of octets for extra descriptors
+        ];
+        // first value: 500, overall minimum: 0
+        let descriptors = [0x01, 0xf4, 0x00, 0x00];
+        // group reference values: 300, 600, 1000
+        let group_refs = [0b01001011, 0b00100101, 0b10001111, 0b10100000];
+        // group widths: 0 + (0, 1, 0)
+        let group_widths = [0b01000000];
+        // group lengths: 2 + 1 * (0, 0), where the scaled value for the last group
+        // is ignored
+        let group_lens = [0b00000000];
+        let values = [0b01000000];
+        let sect7 = [
+            &descriptors[..],
+            &group_refs,
+            &group_widths,
+            &group_lens,
+            &values,
+        ]
+        .concat();
+
+        let actual = decode_7_3(&sect5, &sect7, ())?.collect::<Vec<_>>();
+        let expected = vec![500., 800., 1400., 2001., 3001., 4001.];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn complex_packing_with_spatial_differencing_descriptors_exceeding_section_7() {
+        let sect5 = vec![
+            0x00, 0x00, 0x00, 0x06, // number of values
+            0x00, 0x03, // template number
+            0x00, 0x00, 0x00, 0x00, // reference value
+            0x00, 0x00, // binary scale factor
+            0x00, 0x00, // decimal scale factor
+            0x0a, // number of bits for group reference values
+            0x00, // type of original field values
+            0x01, // group splitting method
+            0x00, // missing value management
+            0xff, 0xff, 0xff, 0xff, // primary missing value substitute
+            0xff, 0xff, 0xff, 0xff, // secondary missing value substitute
+            0x00, 0x00, 0x00, 0x03, // number of groups
+            0x00, // reference for group widths
+            0x01, // number of bits for group widths
+            0x00, 0x00, 0x00, 0x02, // reference for group lengths
+            0x01, // length increment for the group lengths
+            0x00, 0x00, 0x00, 0x02, // true length of last group
+            0x01, // number of bits for scaled group lengths
+            0x02, // order of spatial differencing
+            0x02, // number of octets for extra descriptors
+        ];
+        let sect7 = [0x01];
+
+        let actual = decode_7_3(&sect5, &sect7, ()).err();
+        let expected = Some(GribError::DecodeError(
+            DecodeError::ComplexPackingDecodeError(ComplexPackingDecodeError::LengthMismatch),
+        ));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn complex_packing_param_with_missing_value_management(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let buf = get_uncompressed("testdata/ds.minrh.bin.xz")?;
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let repr_def = submessage.repr_def();
+
+        assert_eq!(repr_def.missing_value_management(), Some(1));
+        assert_eq!(repr_def.primary_missing_value_substitute(), Some(9999.0));
+
+        let decoder = Grib2SubmessageDecoder::from(submessage)?;
+        let param = ComplexPackingParam::from_buf(&decoder.sect5_payload[16..42]);
+        assert_eq!(param.missing_value_management_used, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nan_count_in_complex_packing_with_missing_value_management(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let buf = get_uncompressed("testdata/ds.minrh.bin.xz")?;
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+        let decoder = Grib2SubmessageDecoder::from(submessage)?;
+        let actual = decoder.dispatch()?.filter(|v| v.is_nan()).count();
+
+        // wgrib2 writes missing values as 9.999e20.
+        let expected = get_uncompressed("testdata/gen/ds.minrh.bin.0.xz")?
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .filter(|v| *v == 9.999e20)
+            .count();
+        assert!(expected > 0);
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
+
 mod diff;
 mod missing;
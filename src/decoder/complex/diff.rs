@@ -2,7 +2,10 @@ use super::{
     missing::DecodedValue::{self, Normal},
     ComplexPackingDecodeError,
 };
-use crate::{decoder::DecodeError, error::GribError, helpers::grib_int_from_bytes};
+use crate::{
+    decoder::{stream::BitReader, DecodeError},
+    error::GribError,
+};
 
 pub(crate) struct SpatialDifferencingExtraDescriptors<'a> {
     slice: &'a [u8],
@@ -21,12 +24,14 @@ impl<'a> SpatialDifferencingExtraDescriptors<'a> {
             ));
         }
         let num_octets = usize::from(num_octets);
-        let byte_length = usize::from(spdiff_order + 1) * num_octets;
-
-        Ok(Self {
-            slice: &parent_slice[..byte_length],
-            num_octets,
-        })
+        let byte_length = (usize::from(spdiff_order) + 1) * num_octets;
+        let slice = parent_slice
+            .get(..byte_length)
+            .ok_or(GribError::DecodeError(
+                DecodeError::ComplexPackingDecodeError(ComplexPackingDecodeError::LengthMismatch),
+            ))?;
+
+        Ok(Self { slice, num_octets })
     }
 
     // total number of octets for descriptors
@@ -36,8 +41,10 @@ impl<'a> SpatialDifferencingExtraDescriptors<'a> {
 
     // overall minimum of the differences
     pub(crate) fn minimum(&self) -> i32 {
-        let slice = &self.slice[self.first_value_end_pos()..];
-        grib_int_from_bytes(slice)
+        BitReader::new(self.slice)
+            .with_offset(self.first_value_end_pos() * 8)
+            .read_signed_bits(self.num_octets * 8)
+            .unwrap()
     }
 
     pub(crate) fn first_values(&self) -> FirstValues<'_, 'a> {
@@ -72,10 +79,11 @@ impl Iterator for FirstValues<'_, '_> {
         }
 
         let num_octets = self.spdiff_info.num_octets;
-        let slice = &self.spdiff_info.slice[self.pos..self.pos + num_octets];
-        let val = grib_int_from_bytes(slice);
+        let val = BitReader::new(self.spdiff_info.slice)
+            .with_offset(self.pos * 8)
+            .read_signed_bits(num_octets * 8);
         self.pos += num_octets;
-        Some(val)
+        val
     }
 }
 
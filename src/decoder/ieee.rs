@@ -0,0 +1,176 @@
+use core::slice::ChunksExact;
+
+use crate::{
+    decoder::{stream::ReadPosition, DecodeError},
+    error::*,
+    helpers::read_as,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IeeeDecodeError {
+    PrecisionNotSupported(u8),
+    LengthMismatch,
+    /// Section 5 is too short to contain the precision.
+    PrecisionMissing,
+}
+
+pub(crate) fn decode<'a, P: ReadPosition>(
+    sect5_data: &[u8],
+    sect7_data: &'a [u8],
+    num_points_encoded: usize,
+    position: P,
+) -> Result<IeeeDecodeIterator<'a, P>, GribError> {
+    // Code Table 5.7: 1 for IEEE 32-bit and 2 for IEEE 64-bit
+    let precision = *sect5_data.get(6).ok_or(DecodeError::IeeeDecodeError(
+        IeeeDecodeError::PrecisionMissing,
+    ))?;
+    let size = match precision {
+        1 => core::mem::size_of::<f32>(),
+        2 => core::mem::size_of::<f64>(),
+        _ => {
+            return Err(
+                DecodeError::IeeeDecodeError(IeeeDecodeError::PrecisionNotSupported(precision))
+                    .into(),
+            )
+        }
+    };
+
+    let len = num_points_encoded
+        .checked_mul(size)
+        .filter(|len| *len <= sect7_data.len())
+        .ok_or(DecodeError::IeeeDecodeError(
+            IeeeDecodeError::LengthMismatch,
+        ))?;
+
+    Ok(IeeeDecodeIterator {
+        chunks: sect7_data[..len].chunks_exact(size),
+        end: 0,
+        position,
+    })
+}
+
+pub(crate) struct IeeeDecodeIterator<'a, P> {
+    chunks: ChunksExact<'a, u8>,
+    end: usize,
+    position: P,
+}
+
+impl<P: ReadPosition> Iterator for IeeeDecodeIterator<'_, P> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        self.end += chunk.len();
+        self.position.record(self.end * 8);
+        let value = match chunk.len() {
+            4 => read_as!(f32, chunk, 0),
+            _ => read_as!(f64, chunk, 0) as f32,
+        };
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sect5_payload(num_points: u32, precision: u8) -> Vec<u8> {
+        let mut buf = num_points.to_be_bytes().to_vec();
+        buf.extend_from_slice(&[0x00, 0x04, precision]);
+        buf
+    }
+
+    #[test]
+    fn decode_ieee_32bit_values() -> Result<(), Box<dyn std::error::Error>> {
+        let expected = vec![1.5_f32, -273.15, 1.0e20, 0.0];
+        let sect5 = sect5_payload(4, 1);
+        let sect7 = expected
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+
+        let iter = decode(&sect5, &sect7, 4, ())?;
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_ieee_64bit_values() -> Result<(), Box<dyn std::error::Error>> {
+        let input = [1.5_f64, -273.15, 1.0e20, 0.0];
+        let sect5 = sect5_payload(4, 2);
+        let sect7 = input
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+
+        let actual = decode(&sect5, &sect7, 4, ())?.collect::<Vec<_>>();
+        let expected = input.iter().map(|v| *v as f32).collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    macro_rules! test_decoding_ieee_with_errors {
+        ($(($name:ident, $precision:expr, $sect7_len:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let sect5 = sect5_payload(4, $precision);
+                let sect7 = vec![0; $sect7_len];
+                let result = decode(&sect5, &sect7, 4, ()).map(|_| ());
+                assert_eq!(
+                    result,
+                    Err(GribError::DecodeError(DecodeError::IeeeDecodeError($expected)))
+                );
+            }
+        )*);
+    }
+
+    #[test]
+    fn decode_ieee_values_with_too_short_section_5() {
+        let sect5 = [0x00, 0x00, 0x00, 0x04, 0x00, 0x04];
+        let result = decode(&sect5, &[0; 16], 4, ()).map(|_| ());
+        assert_eq!(
+            result,
+            Err(GribError::DecodeError(DecodeError::IeeeDecodeError(
+                IeeeDecodeError::PrecisionMissing
+            )))
+        );
+    }
+
+    #[test]
+    fn decode_ieee_values_with_overflowing_number_of_points() {
+        let sect5 = sect5_payload(u32::MAX, 2);
+        let result = decode(&sect5, &[0; 16], usize::MAX / 4, ()).map(|_| ());
+        assert_eq!(
+            result,
+            Err(GribError::DecodeError(DecodeError::IeeeDecodeError(
+                IeeeDecodeError::LengthMismatch
+            )))
+        );
+    }
+
+    test_decoding_ieee_with_errors! {
+        (
+            decode_ieee_128bit_values,
+            3,
+            64,
+            IeeeDecodeError::PrecisionNotSupported(3)
+        ),
+        (
+            decode_ieee_32bit_values_with_too_short_data,
+            1,
+            15,
+            IeeeDecodeError::LengthMismatch
+        ),
+        (
+            decode_ieee_64bit_values_with_too_short_data,
+            2,
+            31,
+            IeeeDecodeError::LengthMismatch
+        ),
+    }
+}
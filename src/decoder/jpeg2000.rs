@@ -1,15 +1,24 @@
+#[cfg(not(target_arch = "wasm32"))]
 use openjpeg_sys as opj;
 
 use crate::{
     decoder::{
-        param::SimplePackingParam, simple::*, stream::FixedValueIterator, DecodeError,
-        Grib2SubmessageDecoder,
+        param::SimplePackingParam,
+        simple::*,
+        stream::{FixedValueIterator, ReadPosition},
+        DecodeError, Grib2SubmessageDecoder,
     },
     error::*,
 };
 
+#[cfg(not(target_arch = "wasm32"))]
 mod ext;
+#[cfg(not(target_arch = "wasm32"))]
 use ext::*;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+#[cfg(target_arch = "wasm32")]
+use wasm::decode_jp2;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Jpeg2000CodeStreamDecodeError {
@@ -20,8 +29,34 @@ pub enum Jpeg2000CodeStreamDecodeError {
     LengthMismatch,
 }
 
-pub(crate) fn decode(
+pub(crate) fn decode<P: ReadPosition>(
     target: &Grib2SubmessageDecoder,
+    position: P,
+) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = i32>>, GribError> {
+    decode_area(target, None, position)
+}
+
+// Decodes only the rectangular area of the image starting at `(x, y)` with
+// `width` columns and `height` rows, using the decode area of OpenJPEG so
+// that code blocks outside the area are not decoded.
+pub(crate) fn decode_region(
+    target: &Grib2SubmessageDecoder,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = i32>>, GribError> {
+    decode_area(
+        target,
+        Some((x, y, x.saturating_add(width), y.saturating_add(height))),
+        (),
+    )
+}
+
+fn decode_area<P: ReadPosition>(
+    target: &Grib2SubmessageDecoder,
+    area: Option<(u32, u32, u32, u32)>,
+    position: P,
 ) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = i32>>, GribError> {
     let sect5_data = &target.sect5_payload;
     let simple_param = SimplePackingParam::from_buf(&sect5_data[6..16])?;
@@ -31,23 +66,40 @@ pub(crate) fn decode(
             "WARNING: nbit = 0 for JPEG 2000 code stream format decoder is not tested.
             Please report your data and help us develop the library."
         );
+        let num_points = match area {
+            Some((x0, y0, x1, y1)) => ((x1 - x0) as usize)
+                .checked_mul((y1 - y0) as usize)
+                .ok_or_else(|| {
+                    GribError::InvalidValueError(format!(
+                        "region too large: {}x{}",
+                        x1 - x0,
+                        y1 - y0
+                    ))
+                })?,
+            None => target.num_points_encoded,
+        };
         let decoder = SimplePackingDecodeIteratorWrapper::FixedValue(FixedValueIterator::new(
             simple_param.zero_bit_reference_value(),
-            target.num_points_encoded,
+            num_points,
         ));
         return Ok(decoder);
     };
 
-    let stream = Stream::from_bytes(&target.sect7_payload)
-        .map_err(|e| GribError::DecodeError(DecodeError::Jpeg2000CodeStreamDecodeError(e)))?;
-    let jp2_unpacked = decode_jp2(stream)
+    let jp2_unpacked = decode_jp2(&target.sect7_payload, area)
         .map_err(|e| GribError::DecodeError(DecodeError::Jpeg2000CodeStreamDecodeError(e)))?;
+    // The code stream is read through by the decoder at once.
+    position.record(target.sect7_payload.len() * 8);
     let decoder = SimplePackingDecodeIterator::new(jp2_unpacked, &simple_param);
     let decoder = SimplePackingDecodeIteratorWrapper::SimplePacking(decoder);
     Ok(decoder)
 }
 
-fn decode_jp2(stream: Stream) -> Result<impl Iterator<Item = i32>, Jpeg2000CodeStreamDecodeError> {
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_jp2(
+    buf: &[u8],
+    area: Option<(u32, u32, u32, u32)>,
+) -> Result<impl Iterator<Item = i32>, Jpeg2000CodeStreamDecodeError> {
+    let stream = Stream::from_bytes(buf)?;
     let codec = Codec::j2k()?;
 
     let mut decode_params = unsafe { std::mem::zeroed::<opj::opj_dparameters>() };
@@ -63,6 +115,15 @@ fn decode_jp2(stream: Stream) -> Result<impl Iterator<Item = i32>, Jpeg2000CodeS
         return Err(Jpeg2000CodeStreamDecodeError::MainHeaderReadError);
     }
 
+    if let Some((x0, y0, x1, y1)) = area {
+        let [x0, y0, x1, y1] = [x0, y0, x1, y1]
+            .map(i32::try_from)
+            .map(|v| v.map_err(|_| Jpeg2000CodeStreamDecodeError::DecoderSetupError));
+        if unsafe { opj::opj_set_decode_area(codec.0.as_ptr(), image.0, x0?, y0?, x1?, y1?) } != 1 {
+            return Err(Jpeg2000CodeStreamDecodeError::DecoderSetupError);
+        }
+    }
+
     if unsafe { opj::opj_decode(codec.0.as_ptr(), stream.0, image.0) } != 1 {
         return Err(Jpeg2000CodeStreamDecodeError::BodyReadError);
     }
@@ -77,12 +138,38 @@ fn decode_jp2(stream: Stream) -> Result<impl Iterator<Item = i32>, Jpeg2000CodeS
     let width = value_for_discard_level(width, factor);
     let height = value_for_discard_level(height, factor);
 
+    let len = (width as usize)
+        .checked_mul(height as usize)
+        .ok_or(Jpeg2000CodeStreamDecodeError::LengthMismatch)?;
     if let [comp_gray] = image.components() {
-        let vec = unsafe {
-            std::slice::from_raw_parts(comp_gray.data, (width * height) as usize).to_vec()
-        };
+        // The buffer of the component holds `w * h` values.
+        let buf_len = (comp_gray.w as usize)
+            .checked_mul(comp_gray.h as usize)
+            .unwrap_or(0);
+        if comp_gray.data.is_null() || buf_len < len {
+            return Err(Jpeg2000CodeStreamDecodeError::LengthMismatch);
+        }
+        let vec = unsafe { std::slice::from_raw_parts(comp_gray.data, len).to_vec() };
         Ok(vec.into_iter())
     } else {
         Err(Jpeg2000CodeStreamDecodeError::NotSupported)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn region_of_constant_field_with_more_than_u32_max_points() -> Result<(), GribError> {
+        let mut sect5 = vec![0x00; 21];
+        sect5[4..6].copy_from_slice(&40_u16.to_be_bytes());
+        sect5[6..10].copy_from_slice(&1.5_f32.to_be_bytes());
+        let decoder = Grib2SubmessageDecoder::new(0, 0, 40, sect5.into(), Vec::new(), [].into());
+
+        let region = decode_region(&decoder, 0, 0, 0x10000, 0x10000)?;
+        assert_eq!(region.size_hint(), (1 << 32, Some(1 << 32)));
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use hayro_jpeg2000::{DecodeSettings, DecoderContext, Image};
+
+use crate::decoder::jpeg2000::Jpeg2000CodeStreamDecodeError;
+
+// Decodes the code stream with `hayro-jpeg2000`, which, unlike OpenJPEG, builds
+// for wasm32. It has no equivalent to the decode area of OpenJPEG, so the
+// whole image is decoded and the area is cut out of it afterwards.
+pub(crate) fn decode_jp2(
+    buf: &[u8],
+    area: Option<(u32, u32, u32, u32)>,
+) -> Result<impl Iterator<Item = i32>, Jpeg2000CodeStreamDecodeError> {
+    let image = Image::new(buf, &DecodeSettings::default())
+        .map_err(|_| Jpeg2000CodeStreamDecodeError::MainHeaderReadError)?;
+    let width = image.width() as usize;
+    let height = image.height() as usize;
+
+    let mut context = DecoderContext::default();
+    let decoded = image
+        .decode(&mut context)
+        .map_err(|_| Jpeg2000CodeStreamDecodeError::BodyReadError)?;
+    let [comp_gray] = decoded.components() else {
+        return Err(Jpeg2000CodeStreamDecodeError::NotSupported);
+    };
+    let samples = comp_gray.samples();
+    if width.checked_mul(height) != Some(samples.len()) {
+        return Err(Jpeg2000CodeStreamDecodeError::LengthMismatch);
+    }
+
+    // Samples are integers stored as `f32`, except that those reconstructed
+    // with the irreversible wavelet transform need rounding as in OpenJPEG.
+    let values = match area {
+        None => samples.iter().map(|v| v.round() as i32).collect::<Vec<_>>(),
+        Some((x0, y0, x1, y1)) => {
+            // As in OpenJPEG, the area is clipped to the image.
+            let [x0, y0, x1, y1] = [x0, y0, x1, y1].map(|v| v as usize);
+            let (x1, y1) = (x1.min(width), y1.min(height));
+            if x0 > x1 || y0 > y1 {
+                return Err(Jpeg2000CodeStreamDecodeError::DecoderSetupError);
+            }
+            (y0..y1)
+                .flat_map(|y| &samples[y * width + x0..y * width + x1])
+                .map(|v| v.round() as i32)
+                .collect::<Vec<_>>()
+        }
+    };
+    Ok(values.into_iter())
+}
@@ -1,8 +1,14 @@
+#[cfg(not(feature = "std"))]
+use num::Float;
+
 use crate::{
     helpers::{read_as, GribInt},
     GribError,
 };
 
+// Packed values are read into `u32`.
+pub(crate) const MAX_BIT_WIDTH: u8 = 32;
+
 pub(crate) struct SimplePackingParam {
     pub(crate) ref_val: f32,
     pub(crate) exp: i16,
@@ -18,6 +24,20 @@ impl SimplePackingParam {
         let nbit = read_as!(u8, buf, 8);
         let original_field_type_value = read_as!(u8, buf, 9);
 
+        if nbit > MAX_BIT_WIDTH {
+            return Err(GribError::DecodeError(
+                crate::DecodeError::UnsupportedBitWidth(nbit),
+            ));
+        }
+
+        // A NaN or infinite reference value would make all decoded values
+        // non-finite.
+        if !ref_val.is_finite() {
+            return Err(GribError::DecodeError(
+                crate::DecodeError::NonFiniteReferenceValue,
+            ));
+        }
+
         if original_field_type_value != 0 {
             return Err(GribError::DecodeError(
                 crate::DecodeError::SimplePackingDecodeError(
@@ -52,6 +72,17 @@ pub(crate) struct ComplexPackingParam {
 }
 
 impl ComplexPackingParam {
+    pub(crate) fn check_bit_widths(&self) -> Result<(), GribError> {
+        for nbit in [self.group_width_nbit, self.group_len_nbit] {
+            if nbit > MAX_BIT_WIDTH {
+                return Err(GribError::DecodeError(
+                    crate::DecodeError::UnsupportedBitWidth(nbit),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn from_buf(buf: &[u8]) -> Self {
         let group_splitting_method_used = read_as!(u8, buf, 0);
         let missing_value_management_used = read_as!(u8, buf, 1);
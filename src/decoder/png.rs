@@ -2,7 +2,7 @@ use crate::{
     decoder::{
         param::SimplePackingParam,
         simple::{SimplePackingDecodeIterator, SimplePackingDecodeIteratorWrapper},
-        stream::{FixedValueIterator, NBitwiseIterator},
+        stream::{FixedValueIterator, NBitwiseIterator, ReadPosition},
     },
     DecodeError, Grib2SubmessageDecoder, GribError,
 };
@@ -13,8 +13,9 @@ pub enum PngDecodeError {
     PngError(String),
 }
 
-pub(crate) fn decode(
+pub(crate) fn decode<P: ReadPosition>(
     target: &Grib2SubmessageDecoder,
+    position: P,
 ) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = u32> + '_>, GribError> {
     let sect5_data = &target.sect5_payload;
     let param = SimplePackingParam::from_buf(&sect5_data[6..16])?;
@@ -24,6 +25,8 @@ pub(crate) fn decode(
             e.to_string(),
         )))
     })?;
+    // The image is read through by the decoder at once.
+    position.record(target.sect7_payload.len() * 8);
 
     if param.nbit == 0 {
         eprintln!(
@@ -1,5 +1,13 @@
+use alloc::{vec, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use num::Float;
+
 use crate::{
-    decoder::{stream::NBitwiseIterator, DecodeError, Grib2SubmessageDecoder},
+    decoder::{
+        stream::{NBitwiseIterator, ReadPosition},
+        DecodeError,
+    },
     error::*,
     helpers::read_as,
 };
@@ -12,16 +20,18 @@ pub enum RunLengthEncodingDecodeError {
     InvalidLevelValue(u16),
 }
 
-pub(crate) fn decode(
-    target: &Grib2SubmessageDecoder,
-) -> Result<std::vec::IntoIter<f32>, GribError> {
-    let sect5_data = &target.sect5_payload;
+pub(crate) fn decode<P: ReadPosition>(
+    sect5_data: &[u8],
+    sect7_data: &[u8],
+    num_points_encoded: usize,
+    position: P,
+) -> Result<RunLengthDecodeIterator<P>, GribError> {
     let nbit = read_as!(u8, sect5_data, 6);
     let maxv = read_as!(u16, sect5_data, 7);
     let max_level = read_as!(u16, sect5_data, 9);
     let num_digits = read_as!(u8, sect5_data, 11);
 
-    let mut level_map = Vec::with_capacity(max_level.into());
+    let mut level_map = Vec::with_capacity(usize::from(max_level) + 1);
     level_map.push(f32::NAN);
     let mut pos = 12;
 
@@ -31,70 +41,128 @@ pub(crate) fn decode(
         let factor = 10_f32.powi(-num_digits);
         let val = val * factor;
         level_map.push(val);
-        pos += std::mem::size_of::<u16>();
+        pos += core::mem::size_of::<u16>();
     }
 
-    let decoded_levels = rleunpack(
-        &target.sect7_payload,
-        nbit,
-        maxv,
-        Some(target.num_points_encoded),
-    )
-    .map_err(DecodeError::RunLengthEncodingDecodeError)?;
-
-    let level_to_value = |level: &u16| -> Result<f32, DecodeError> {
-        let index: usize = (*level).into();
-        level_map
-            .get(index)
-            .copied()
-            .ok_or(DecodeError::RunLengthEncodingDecodeError(
-                RunLengthEncodingDecodeError::InvalidLevelValue(*level),
-            ))
-    };
-
-    let decoded: Result<Vec<_>, _> = (*decoded_levels).iter().map(level_to_value).collect();
-    Ok(decoded?.into_iter())
+    let runs = decode_runs(sect7_data, nbit, maxv, max_level, num_points_encoded)
+        .map_err(DecodeError::RunLengthEncodingDecodeError)?;
+
+    Ok(RunLengthDecodeIterator {
+        runs: runs.into_iter(),
+        level_map,
+        value: f32::NAN,
+        num_repeats: 0,
+        len: num_points_encoded,
+        position,
+    })
+}
+
+// Decodes level values without mapping them to representative values.
+pub(crate) fn decode_levels(
+    sect5_data: &[u8],
+    sect7_data: &[u8],
+    num_points_encoded: usize,
+) -> Result<Vec<u16>, GribError> {
+    let nbit = read_as!(u8, sect5_data, 6);
+    let maxv = read_as!(u16, sect5_data, 7);
+    let max_level = read_as!(u16, sect5_data, 9);
+
+    let runs = decode_runs(sect7_data, nbit, maxv, max_level, num_points_encoded)
+        .map_err(DecodeError::RunLengthEncodingDecodeError)?;
+    let levels = runs
+        .iter()
+        .flat_map(|run| (0..run.len).map(|_| run.level))
+        .collect();
+    Ok(levels)
+}
+
+// A level value repeated `len` times, encoded in Section 7 up to `end_bits`.
+#[derive(Debug, PartialEq, Eq)]
+struct Run {
+    level: u16,
+    len: usize,
+    end_bits: usize,
 }
 
+// Decodes the stream into runs of level values, validating it in the same
+// pass so that errors are reported before iteration starts. Runs are kept
+// compact, so that values are expanded lazily without allocating memory for
+// each grid point.
+//
 // Since maxv is represented as a 16-bit integer, values are 16 bits or less.
-fn rleunpack(
-    input: &[u8],
+fn decode_runs(
+    sect7_data: &[u8],
     nbit: u8,
     maxv: u16,
-    expected_len: Option<usize>,
-) -> Result<Box<[u16]>, RunLengthEncodingDecodeError> {
-    let mut out_buf = match expected_len {
-        Some(sz) => Vec::with_capacity(sz),
-        None => Vec::new(),
-    };
-
+    max_level: u16,
+    num_points_encoded: usize,
+) -> Result<Vec<Run>, RunLengthEncodingDecodeError> {
     let rlbase = maxv + 1;
-    let lngu: usize = ((1u16 << nbit) - rlbase).into();
-    let mut cached = None;
+    let lngu = usize::from((1u16 << nbit) - rlbase);
+    let mut runs: Vec<Run> = Vec::new();
     let mut exp: usize = 1;
-    let iter = NBitwiseIterator::new(input, usize::from(nbit));
+    let mut num_decoded: usize = 0;
 
-    for value in iter {
+    for (i, value) in NBitwiseIterator::new(sect7_data, usize::from(nbit)).enumerate() {
         let value = value as u16;
+        let end_bits = (i + 1) * usize::from(nbit);
         if rlbase > value {
-            out_buf.push(value);
-            cached = Some(value);
+            if value > max_level {
+                return Err(RunLengthEncodingDecodeError::InvalidLevelValue(value));
+            }
+            runs.push(Run {
+                level: value,
+                len: 1,
+                end_bits,
+            });
+            num_decoded = num_decoded.saturating_add(1);
             exp = 1;
         } else {
-            let prev = cached.ok_or(RunLengthEncodingDecodeError::InvalidFirstValue)?;
-            let length: usize = ((value - rlbase) as usize) * exp;
-            out_buf.append(&mut vec![prev; length]);
-            exp *= lngu;
+            let run = runs
+                .last_mut()
+                .ok_or(RunLengthEncodingDecodeError::InvalidFirstValue)?;
+            let num_repeats = usize::from(value - rlbase).saturating_mul(exp);
+            run.len = run.len.saturating_add(num_repeats);
+            run.end_bits = end_bits;
+            num_decoded = num_decoded.saturating_add(num_repeats);
+            exp = exp.saturating_mul(lngu);
         }
     }
 
-    if let Some(len) = expected_len {
-        if len != out_buf.len() {
-            return Err(RunLengthEncodingDecodeError::LengthMismatch);
+    if num_decoded != num_points_encoded {
+        return Err(RunLengthEncodingDecodeError::LengthMismatch);
+    }
+    Ok(runs)
+}
+
+pub(crate) struct RunLengthDecodeIterator<P> {
+    runs: vec::IntoIter<Run>,
+    level_map: Vec<f32>,
+    value: f32,
+    num_repeats: usize,
+    len: usize,
+    position: P,
+}
+
+impl<P: ReadPosition> Iterator for RunLengthDecodeIterator<P> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.num_repeats == 0 {
+            let run = self.runs.next()?;
+            self.position.record(run.end_bits);
+            // Levels are checked not to exceed the maximum level in decoding.
+            self.value = self.level_map[usize::from(run.level)];
+            self.num_repeats = run.len;
         }
+        self.num_repeats -= 1;
+        self.len = self.len.saturating_sub(1);
+        Some(self.value)
     }
 
-    Ok(out_buf.into_boxed_slice())
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
 }
 
 #[cfg(test)]
@@ -113,10 +181,8 @@ mod tests {
         let input: Vec<u8> = input.iter().map(|n| n + 240).collect();
         let output: Vec<u16> = output.iter().map(|n| n + 240).collect();
 
-        assert_eq!(
-            rleunpack(&input, 8, 250, Some(21)),
-            Ok(output.into_boxed_slice())
-        );
+        let runs = decode_runs(&input, 8, 250, 250, output.len());
+        assert_eq!(runs.map(expand), Ok(output));
     }
 
     #[test]
@@ -124,6 +190,70 @@ mod tests {
         let input: Vec<u8> = vec![0x00, 0x14, 0x1c];
         let output: Vec<u16> = vec![0; 6065];
 
-        assert_eq!(rleunpack(&input, 8, 3, None), Ok(output.into_boxed_slice()));
+        let runs = decode_runs(&input, 8, 3, 3, output.len());
+        assert_eq!(runs.map(expand), Ok(output));
+    }
+
+    macro_rules! test_decoding_invalid_run_length_encoding {
+        ($(($name:ident, $input:expr, $max_level:expr, $len:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let input: Vec<u8> = $input;
+                let actual = decode_runs(&input, 8, 250, $max_level, $len);
+                assert_eq!(actual, Err($expected));
+            }
+        )*);
+    }
+
+    test_decoding_invalid_run_length_encoding! {
+        (
+            decoding_run_length_encoding_starting_with_run_length,
+            vec![252, 1],
+            250,
+            3,
+            RunLengthEncodingDecodeError::InvalidFirstValue
+        ),
+        (
+            decoding_run_length_encoding_with_level_exceeding_max_level,
+            vec![1, 5, 252],
+            3,
+            4,
+            RunLengthEncodingDecodeError::InvalidLevelValue(5)
+        ),
+        (
+            decoding_run_length_encoding_with_length_mismatch,
+            vec![1, 2, 253],
+            250,
+            3,
+            RunLengthEncodingDecodeError::LengthMismatch
+        ),
+    }
+
+    fn expand(runs: Vec<Run>) -> Vec<u16> {
+        runs.iter()
+            .flat_map(|run| (0..run.len).map(|_| run.level))
+            .collect()
+    }
+
+    #[test]
+    fn size_hint_of_decoded_values() -> Result<(), Box<dyn std::error::Error>> {
+        let f = std::fs::File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+
+        for (_, submessage) in grib2.iter() {
+            let num_points = submessage.grid_def().num_points() as usize;
+            let decoder = crate::Grib2SubmessageDecoder::from(submessage)?;
+            let mut values = decoder.dispatch()?;
+            assert_eq!(values.size_hint(), (num_points, Some(num_points)));
+
+            let _ = values.nth(99);
+            let num_remaining = num_points - 100;
+            assert_eq!(values.size_hint(), (num_remaining, Some(num_remaining)));
+            assert_eq!(values.count(), num_remaining);
+        }
+        Ok(())
     }
 }
@@ -1,10 +1,11 @@
+#[cfg(not(feature = "std"))]
+use num::Float;
 use num::ToPrimitive;
 
 use crate::{
     decoder::{
         param::SimplePackingParam,
-        stream::{FixedValueIterator, NBitwiseIterator},
-        Grib2SubmessageDecoder,
+        stream::{FixedValueIterator, NBitwiseIterator, ReadPosition},
     },
     error::*,
 };
@@ -45,19 +46,25 @@ pub enum SimplePackingDecodeError {
     LengthMismatch,
 }
 
-pub(crate) fn decode(
-    target: &Grib2SubmessageDecoder,
-) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = u32> + '_>, GribError> {
-    let sect5_data = &target.sect5_payload;
-    let param = SimplePackingParam::from_buf(&sect5_data[6..16])?;
+pub(crate) fn decode<'a, P: ReadPosition + 'a>(
+    sect5_payload: &[u8],
+    sect7_payload: &'a [u8],
+    num_points_encoded: usize,
+    position: P,
+) -> Result<SimplePackingDecodeIteratorWrapper<impl Iterator<Item = u32> + 'a>, GribError> {
+    let param = SimplePackingParam::from_buf(&sect5_payload[6..16])?;
 
     let decoder = if param.nbit == 0 {
         SimplePackingDecodeIteratorWrapper::FixedValue(FixedValueIterator::new(
             param.zero_bit_reference_value(),
-            target.num_points_encoded,
+            num_points_encoded,
         ))
     } else {
-        let iter = NBitwiseIterator::new(&target.sect7_payload, usize::from(param.nbit));
+        // Padding bits at the end of Section 7 can be long enough to be read as
+        // extra values when the bit width is not a multiple of 8.
+        let iter = NBitwiseIterator::new(sect7_payload, usize::from(param.nbit))
+            .recording(position)
+            .take(num_points_encoded);
         let iter = SimplePackingDecodeIterator::new(iter, &param);
         SimplePackingDecodeIteratorWrapper::SimplePacking(iter)
     };
@@ -107,7 +114,10 @@ mod tests {
     };
 
     use super::*;
-    use crate::context::from_reader;
+    use crate::{
+        context::from_reader,
+        decoder::{stream::all_ones, Grib2SubmessageDecoder},
+    };
 
     #[test]
     fn decode_simple_packing() {
@@ -128,6 +138,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_simple_packing_from_raw_bytes() {
+        let sect5_payload = vec![
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x35, 0x3e, 0x6b, 0xf6, 0x80, 0x1a, 0x00, 0x00,
+            0x10, 0x00,
+        ];
+        let sect7_payload = vec![0x00, 0x06, 0x00, 0x0d];
+        let expected: Vec<f32> = vec![7.987_831_6e-7, 9.030_913e-7];
+
+        let actual = decode(&sect5_payload, &sect7_payload, 2, ())
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!(*a < e + 0.00000001);
+            assert!(*a > e - 0.00000001);
+        }
+    }
+
+    #[test]
+    fn decode_simple_packing_with_unsupported_bit_width() {
+        let sect5_payload = vec![
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x35, 0x3e, 0x6b, 0xf6, 0x80, 0x1a, 0x00, 0x00,
+            0x28, 0x00,
+        ];
+        let sect7_payload = vec![0x00; 10];
+
+        let result = decode(&sect5_payload, &sect7_payload, 2, ()).map(|_| ());
+        assert_eq!(
+            result,
+            Err(GribError::DecodeError(
+                crate::DecodeError::UnsupportedBitWidth(40)
+            ))
+        );
+    }
+
+    fn pack_bits(values: &[u32], nbit: usize) -> Vec<u8> {
+        let mut out = vec![0u8; (values.len() * nbit).div_ceil(8)];
+        for (n, value) in values.iter().enumerate() {
+            for bit in 0..nbit {
+                if value >> (nbit - 1 - bit) & 1 == 1 {
+                    let pos = n * nbit + bit;
+                    out[pos / 8] |= 0b10000000 >> (pos % 8);
+                }
+            }
+        }
+        out
+    }
+
+    macro_rules! test_simple_packing_with_bit_width {
+        ($(($name:ident, $nbit:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let max = all_ones($nbit);
+                let values = vec![0, 1, max, max / 2, max - 1, max / 3, 1, 0, max];
+                let mut sect5_payload = vec![0x00, 0x00, 0x00, values.len() as u8, 0x00, 0x00];
+                sect5_payload.extend_from_slice(&[
+                    0x00, 0x00, 0x00, 0x00, // reference value
+                    0x00, 0x00, // binary scale factor
+                    0x00, 0x00, // decimal scale factor
+                    $nbit, // number of bits
+                    0x00, // type of original field values
+                ]);
+                let sect7_payload = pack_bits(&values, $nbit);
+
+                let actual = decode(&sect5_payload, &sect7_payload, values.len(), ())
+                    .unwrap()
+                    .collect::<Vec<_>>();
+                let expected = values.iter().map(|v| *v as f32).collect::<Vec<_>>();
+                assert_eq!(actual, expected);
+            }
+        )*);
+    }
+
+    test_simple_packing_with_bit_width! {
+        (decode_simple_packing_with_bit_width_1, 1),
+        (decode_simple_packing_with_bit_width_7, 7),
+        (decode_simple_packing_with_bit_width_12, 12),
+        (decode_simple_packing_with_bit_width_13, 13),
+        (decode_simple_packing_with_bit_width_17, 17),
+        (decode_simple_packing_with_bit_width_24, 24),
+    }
+
+    #[test]
+    fn decode_simple_packing_with_infinite_reference_value() {
+        let sect5_payload = vec![
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x7f, 0x80, 0x00, 0x00, 0x80, 0x1a, 0x00, 0x00,
+            0x10, 0x00,
+        ];
+        let sect7_payload = vec![0x00, 0x06, 0x00, 0x0d];
+
+        let result = decode(&sect5_payload, &sect7_payload, 2, ()).map(|_| ());
+        assert_eq!(
+            result,
+            Err(GribError::DecodeError(
+                crate::DecodeError::NonFiniteReferenceValue
+            ))
+        );
+    }
+
     #[test]
     fn decode_simple_packing_when_nbit_is_zero() {
         let f = File::open(
@@ -0,0 +1,108 @@
+use alloc::format;
+use core::iter;
+
+use crate::{
+    decoder::{
+        param::SimplePackingParam,
+        simple::{SimplePackingDecodeIterator, SimplePackingDecodeIteratorWrapper},
+        stream::{FixedValueIterator, NBitwiseIterator, ReadPosition},
+    },
+    error::*,
+    helpers::read_as,
+};
+
+pub(crate) type SpectralSimpleDecodeIterator<'a, P> = iter::Chain<
+    iter::Once<f32>,
+    SimplePackingDecodeIteratorWrapper<iter::Take<NBitwiseIterator<&'a [u8], P>>>,
+>;
+
+// Values are the real and imaginary parts of the coefficients in turn. The
+// real part of the (0,0) coefficient is stored in Section 5 as an IEEE 32-bit
+// floating-point number and the rest are packed with simple packing.
+pub(crate) fn decode<'a, P: ReadPosition>(
+    sect5_payload: &[u8],
+    sect7_payload: &'a [u8],
+    num_points_encoded: usize,
+    position: P,
+) -> Result<SpectralSimpleDecodeIterator<'a, P>, GribError> {
+    // Template 5.50 has the same layout as template 5.0 up to the number of bits,
+    // which is followed by the real part of the (0,0) coefficient instead of the
+    // type of original field values.
+    if sect5_payload.len() < 19 {
+        return Err(GribError::InvalidValueError(format!(
+            "Section 5 too short for template 5.50: {} octets",
+            sect5_payload.len() + 5
+        )));
+    }
+    let mut param_buf = [0; 10];
+    param_buf[..9].copy_from_slice(&sect5_payload[6..15]);
+    let param = SimplePackingParam::from_buf(&param_buf)?;
+    let first = read_as!(f32, sect5_payload, 15);
+
+    let num_packed = num_points_encoded.saturating_sub(1);
+    let packed = if param.nbit == 0 {
+        SimplePackingDecodeIteratorWrapper::FixedValue(FixedValueIterator::new(
+            param.zero_bit_reference_value(),
+            num_packed,
+        ))
+    } else {
+        let iter = NBitwiseIterator::new(sect7_payload, usize::from(param.nbit))
+            .recording(position)
+            .take(num_packed);
+        let iter = SimplePackingDecodeIterator::new(iter, &param);
+        SimplePackingDecodeIteratorWrapper::SimplePacking(iter)
+    };
+    Ok(iter::once(first).chain(packed))
+}
+
+// Parameters of spectral complex packing (template 5.51) are recognized, but
+// reconstruction of coefficients from the unpacked subset and the packed
+// values scaled with the Laplacian operator is not implemented yet.
+pub(crate) fn complex_packing_not_decoded() -> GribError {
+    GribError::NotSupported(
+        "spectral data packed with complex packing (template 5.51) is not yet fully decoded".into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spectral_simple_packing() -> Result<(), Box<dyn std::error::Error>> {
+        let mut sect5 = vec![
+            0x00, 0x00, 0x00, 0x06, // number of values
+            0x00, 0x32, // template number
+            0x3f, 0x80, 0x00, 0x00, // reference value
+            0x80, 0x01, // binary scale factor
+            0x00, 0x00, // decimal scale factor
+            0x04, // number of bits
+        ];
+        sect5.extend_from_slice(&285.5_f32.to_be_bytes());
+        let sect7 = vec![0x01, 0x23, 0x45];
+
+        let actual = decode(&sect5, &sect7, 6, ())?.collect::<Vec<_>>();
+        let expected = vec![285.5, 1.0, 1.5, 2.0, 2.5, 3.0];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn spectral_simple_packing_with_too_short_section_5() {
+        let sect5 = vec![
+            0x00, 0x00, 0x00, 0x06, // number of values
+            0x00, 0x32, // template number
+            0x3f, 0x80, 0x00, 0x00, // reference value
+            0x80, 0x01, // binary scale factor
+            0x00, 0x00, // decimal scale factor
+            0x04, // number of bits
+        ];
+        let sect7 = vec![0x01, 0x23, 0x45];
+
+        let actual = decode(&sect5, &sect7, 6, ()).map(|_| ());
+        let expected = Err(GribError::InvalidValueError(
+            "Section 5 too short for template 5.50: 20 octets".to_owned(),
+        ));
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,6 +1,65 @@
-pub(crate) enum BitStream<T> {
+use core::cell::Cell;
+
+/// A destination to which readers of Section 7 report how far they have read,
+/// used for decoding diagnostics.
+///
+/// `()` discards positions so that readers used without diagnostics are not
+/// slowed down.
+pub(crate) trait ReadPosition: Clone {
+    /// Reports that data up to `end_bits` bits from the start of the data
+    /// being read has been consumed.
+    fn record(&self, end_bits: usize);
+
+    /// Returns a destination for a reader of data starting at `offset` bytes
+    /// from the start of the data of `self`.
+    fn with_offset(self, offset: usize) -> Self;
+}
+
+impl ReadPosition for () {
+    #[inline(always)]
+    fn record(&self, _end_bits: usize) {}
+
+    #[inline(always)]
+    fn with_offset(self, _offset: usize) -> Self {}
+}
+
+/// Records the end of the data read from Section 7 in bytes.
+#[derive(Clone, Copy)]
+pub(crate) struct PositionRecorder<'a> {
+    end: &'a Cell<usize>,
+    offset_bits: usize,
+}
+
+impl<'a> PositionRecorder<'a> {
+    pub(crate) fn new(end: &'a Cell<usize>) -> Self {
+        Self {
+            end,
+            offset_bits: 0,
+        }
+    }
+}
+
+impl ReadPosition for PositionRecorder<'_> {
+    fn record(&self, end_bits: usize) {
+        // Readers of different parts of Section 7 may advance in turn, so the
+        // furthest position is kept.
+        let end = (self.offset_bits + end_bits).div_ceil(8);
+        if end > self.end.get() {
+            self.end.set(end);
+        }
+    }
+
+    fn with_offset(self, offset: usize) -> Self {
+        Self {
+            offset_bits: self.offset_bits + offset * 8,
+            ..self
+        }
+    }
+}
+
+pub(crate) enum BitStream<T, P = ()> {
     ZeroSized(FixedValueIterator<u32>),
-    NonZeroSized(NBitwiseIterator<T>),
+    NonZeroSized(NBitwiseIterator<T, P>),
 }
 
 impl<T> BitStream<T> {
@@ -13,11 +72,19 @@ impl<T> BitStream<T> {
             Self::NonZeroSized(iter)
         }
     }
+
+    pub(crate) fn recording<P>(self, position: P) -> BitStream<T, P> {
+        match self {
+            Self::ZeroSized(z) => BitStream::ZeroSized(z),
+            Self::NonZeroSized(n) => BitStream::NonZeroSized(n.recording(position)),
+        }
+    }
 }
 
-impl<T> Iterator for BitStream<T>
+impl<T, P> Iterator for BitStream<T, P>
 where
     T: AsRef<[u8]>,
+    P: ReadPosition,
 {
     type Item = u32;
 
@@ -60,73 +127,144 @@ where
     }
 }
 
+/// A reader extracting MSB-first bit fields of arbitrary widths from a byte
+/// sequence.
+///
+/// The position after each read is reported to `position`.
 #[derive(Clone)]
-pub(crate) struct NBitwiseIterator<T> {
-    pub(crate) data: T,
-    pub(crate) size: usize,
-    pub(crate) pos: usize,
-    pub(crate) offset: usize,
+pub(crate) struct BitReader<T, P = ()> {
+    data: T,
+    pos: usize,
+    position: P,
 }
 
-impl<T> NBitwiseIterator<T> {
-    pub(crate) fn new(data: T, size: usize) -> Self {
+impl<T> BitReader<T> {
+    pub(crate) fn new(data: T) -> Self {
         Self {
             data,
-            size,
             pos: 0,
-            offset: 0,
+            position: (),
         }
     }
+}
 
+impl<T, P> BitReader<T, P> {
     pub(crate) fn with_offset(self, offset_bits: usize) -> Self {
         Self {
-            offset: offset_bits,
+            pos: offset_bits,
             ..self
         }
     }
+
+    pub(crate) fn recording<Q>(self, position: Q) -> BitReader<T, Q> {
+        let Self { data, pos, .. } = self;
+        BitReader {
+            data,
+            pos,
+            position,
+        }
+    }
 }
 
-impl<T> Iterator for NBitwiseIterator<T>
+impl<T, P> BitReader<T, P>
 where
     T: AsRef<[u8]>,
+    P: ReadPosition,
 {
-    type Item = u32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let new_offset = self.offset + self.size;
-        let (new_pos, new_offset) = (self.pos + new_offset / 8, new_offset % 8);
-        let slice = self.data.as_ref();
+    pub(crate) fn remaining_bits(&self) -> usize {
+        (self.data.as_ref().len() * 8).saturating_sub(self.pos)
+    }
 
-        if self.pos >= slice.len()
-            || new_pos > slice.len()
-            || (new_pos == slice.len() && new_offset > 0)
-        {
+    /// Reads `n` bits as an unsigned integer.
+    ///
+    /// Returns `Some(0)` without consuming any bits if `n` is 0, and `None` if
+    /// `n` is greater than 32 or fewer than `n` bits remain.
+    pub(crate) fn read_bits(&mut self, n: usize) -> Option<u32> {
+        if n > 32 || n > self.remaining_bits() {
             return None;
         }
+        if n == 0 {
+            return Some(0);
+        }
 
-        let val = slice[self.pos] << self.offset >> self.offset;
-        let mut val: u32 = u32::from(val);
-        if new_pos == self.pos {
-            val >>= 8 - new_offset;
-        } else {
-            let mut pos = self.pos + 1;
-            while pos < new_pos {
-                val = (val << 8) | u32::from(slice[pos]);
-                pos += 1;
-            }
-            if new_offset > 0 {
-                let shift = 8 - new_offset;
-                let last_val = u32::from(slice[pos]) >> shift;
-                val = (val << new_offset) | last_val;
-            }
+        let slice = self.data.as_ref();
+        let (start, end) = (self.pos / 8, (self.pos + n).div_ceil(8));
+        let mut val: u64 = 0;
+        for byte in &slice[start..end] {
+            val = (val << 8) | u64::from(*byte);
         }
+        let trailing = end * 8 - (self.pos + n);
+        let val = (val >> trailing) & (u64::MAX >> (64 - n));
+
+        self.pos += n;
+        self.position.record(self.pos);
+        Some(val as u32)
+    }
 
-        self.pos = new_pos;
-        self.offset = new_offset;
+    /// Reads `n` bits as a signed integer in which the most significant bit
+    /// represents the sign and the remaining bits represent the absolute
+    /// value, as used in GRIB2.
+    pub(crate) fn read_signed_bits(&mut self, n: usize) -> Option<i32> {
+        let val = self.read_bits(n)?;
+        if n == 0 {
+            return Some(0);
+        }
+        let sign_mask = 1 << (n - 1);
+        let abs = (val & !sign_mask) as i32;
+        let val = if val & sign_mask == 0 { abs } else { -abs };
         Some(val)
     }
 }
 
+/// Returns the value of `nbit` bits all set to 1, which is used to represent
+/// missing values.
+pub(crate) fn all_ones(nbit: usize) -> u32 {
+    if nbit >= 32 {
+        u32::MAX
+    } else {
+        (1 << nbit) - 1
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct NBitwiseIterator<T, P = ()> {
+    reader: BitReader<T, P>,
+    size: usize,
+}
+
+impl<T> NBitwiseIterator<T> {
+    pub(crate) fn new(data: T, size: usize) -> Self {
+        Self {
+            reader: BitReader::new(data),
+            size,
+        }
+    }
+}
+
+impl<T, P> NBitwiseIterator<T, P> {
+    pub(crate) fn recording<Q>(self, position: Q) -> NBitwiseIterator<T, Q> {
+        NBitwiseIterator {
+            reader: self.reader.recording(position),
+            size: self.size,
+        }
+    }
+}
+
+impl<T, P> Iterator for NBitwiseIterator<T, P>
+where
+    T: AsRef<[u8]>,
+    P: ReadPosition,
+{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reader.remaining_bits() == 0 {
+            return None;
+        }
+        self.reader.read_bits(self.size)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,11 +332,11 @@ mod tests {
     }
 
     #[test]
-    fn nbitwise_iterator_with_offset() {
+    fn bit_reader_with_offset() {
         let slice: [u8; 5] = [0, 255, 255, 0, 0];
 
-        let mut iter = NBitwiseIterator::new(&slice, 2).with_offset(7);
-        assert_eq!(iter.next(), Some(0b01));
+        let mut reader = BitReader::new(&slice).with_offset(7);
+        assert_eq!(reader.read_bits(2), Some(0b01));
     }
 
     #[test]
@@ -208,4 +346,152 @@ mod tests {
         let mut iter = NBitwiseIterator::new(&slice, 0);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn bit_reader_recording_position() {
+        let slice: [u8; 5] = [0, 255, 255, 0, 0];
+        let end = Cell::new(0);
+
+        let mut reader =
+            BitReader::new(&slice).recording(PositionRecorder::new(&end).with_offset(2));
+        assert_eq!(end.get(), 0);
+        assert_eq!(reader.read_bits(3), Some(0b000));
+        assert_eq!(end.get(), 3);
+        assert_eq!(reader.read_bits(13), Some(0b0000011111111));
+        assert_eq!(end.get(), 4);
+
+        // positions behind the furthest one recorded are ignored
+        let mut reader = BitReader::new(&slice).recording(PositionRecorder::new(&end));
+        assert_eq!(reader.read_bits(9), Some(0b000000001));
+        assert_eq!(end.get(), 4);
+    }
+
+    #[test]
+    fn bit_reader_reading_across_byte_boundaries() {
+        let slice: [u8; 4] = [0b10110011, 0b01011100, 0b11110000, 0b00001111];
+
+        let mut reader = BitReader::new(&slice);
+        assert_eq!(reader.read_bits(3), Some(0b101));
+        assert_eq!(reader.read_bits(7), Some(0b1001101));
+        assert_eq!(reader.read_bits(12), Some(0b011100111100));
+        assert_eq!(reader.remaining_bits(), 10);
+        assert_eq!(reader.read_bits(10), Some(0b0000001111));
+        assert_eq!(reader.remaining_bits(), 0);
+    }
+
+    #[test]
+    fn bit_reader_reading_zero_bits() {
+        let slice: [u8; 1] = [0xff];
+
+        let mut reader = BitReader::new(&slice);
+        assert_eq!(reader.read_bits(0), Some(0));
+        assert_eq!(reader.read_signed_bits(0), Some(0));
+        assert_eq!(reader.remaining_bits(), 8);
+
+        let slice: [u8; 0] = [];
+        let mut reader = BitReader::new(&slice);
+        assert_eq!(reader.read_bits(0), Some(0));
+    }
+
+    #[test]
+    fn bit_reader_reading_32_bits() {
+        let slice: [u8; 5] = [0xff, 0xff, 0xff, 0xff, 0b10101010];
+
+        let mut reader = BitReader::new(&slice);
+        assert_eq!(reader.read_bits(32), Some(u32::MAX));
+        assert_eq!(reader.read_bits(8), Some(0b10101010));
+
+        let mut reader = BitReader::new(&slice).with_offset(4);
+        assert_eq!(reader.read_bits(32), Some(0xfffffffa));
+        assert_eq!(reader.read_bits(4), Some(0b1010));
+    }
+
+    #[test]
+    fn bit_reader_reading_more_than_32_bits() {
+        let slice: [u8; 5] = [0; 5];
+
+        let mut reader = BitReader::new(&slice);
+        assert_eq!(reader.read_bits(33), None);
+        assert_eq!(reader.remaining_bits(), 40);
+    }
+
+    #[test]
+    fn bit_reader_reading_beyond_end_of_data() {
+        let slice: [u8; 2] = [0xff, 0xff];
+
+        let mut reader = BitReader::new(&slice).with_offset(5);
+        assert_eq!(reader.read_bits(12), None);
+        assert_eq!(reader.remaining_bits(), 11);
+        assert_eq!(reader.read_bits(11), Some(0b11111111111));
+        assert_eq!(reader.read_bits(1), None);
+    }
+
+    #[test]
+    fn bit_reader_reading_signed_bits_across_byte_boundaries() {
+        let slice: [u8; 2] = [0b00011100, 0b10100000];
+
+        let mut reader = BitReader::new(&slice).with_offset(3);
+        assert_eq!(reader.read_signed_bits(5), Some(-0b1100));
+        assert_eq!(reader.read_signed_bits(4), Some(-0b010));
+    }
+
+    macro_rules! test_reading_signed_bits_of_whole_bytes {
+        ($(($name:ident, $input:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let bytes = $input;
+                let actual = BitReader::new(&bytes).read_signed_bits(bytes.len() * 8);
+                let expected = Some($expected);
+                assert_eq!(actual, expected)
+            }
+        )*);
+    }
+
+    test_reading_signed_bits_of_whole_bytes! {
+        (
+            reading_signed_bits_for_1_byte_positive,
+            vec![0b01010101],
+            0b01010101
+        ),
+        (
+            reading_signed_bits_for_1_byte_negative,
+            vec![0b11010101],
+            -0b01010101
+        ),
+        (
+            reading_signed_bits_for_2_bytes_positive,
+            vec![0b01010101, 0b10101010],
+            0b0101_0101_1010_1010
+        ),
+        (
+            reading_signed_bits_for_2_bytes_negative,
+            vec![0b11010101, 0b10101010],
+            -0b0101_0101_1010_1010
+        ),
+        (
+            reading_signed_bits_for_3_bytes_positive,
+            vec![0b01010101, 0b10101010, 0b10101010],
+            0b0101_0101_1010_1010_1010_1010
+        ),
+        (
+            reading_signed_bits_for_3_bytes_negative,
+            vec![0b11010101, 0b10101010, 0b10101010],
+            -0b0101_0101_1010_1010_1010_1010
+        ),
+        (
+            reading_signed_bits_for_3_bytes_negative_starting_from_0x80,
+            vec![0b10000000, 0b10101010, 0b10101010],
+            -0b0000_0000_1010_1010_1010_1010
+        ),
+        (
+            reading_signed_bits_for_4_bytes_positive,
+            vec![0b01010101, 0b10101010, 0b10101010, 0b10101010],
+            0b0101_0101_1010_1010_1010_1010_1010_1010
+        ),
+        (
+            reading_signed_bits_for_4_bytes_negative,
+            vec![0b11010101, 0b10101010, 0b10101010, 0b10101010],
+            -0b0101_0101_1010_1010_1010_1010_1010_1010
+        ),
+    }
 }
@@ -1,8 +1,7 @@
-use std::{
-    error::Error,
-    fmt::{self, Display, Formatter},
-    io,
-};
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::{error::Error, io};
 
 use crate::decoder::*;
 
@@ -13,13 +12,29 @@ pub enum GribError {
     DecodeError(DecodeError),
     InvalidValueError(String),
     NotSupported(String),
+    /// A template required for the operation is not supported. `section` is
+    /// the number of the section where the template is used, and `number` is
+    /// the template number.
+    UnsupportedTemplate {
+        section: u8,
+        number: u16,
+    },
     Unknown(String),
 }
 
+#[cfg(feature = "std")]
 impl Error for GribError {
     fn description(&self) -> &str {
         "grib error"
     }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParseError(e) => Some(e),
+            Self::DecodeError(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 impl From<ParseError> for GribError {
@@ -39,9 +54,12 @@ impl Display for GribError {
         match self {
             Self::InternalDataError => write!(f, "Something unexpected happend"),
             Self::ParseError(e) => write!(f, "{e}"),
-            Self::DecodeError(e) => write!(f, "{e:#?}"),
+            Self::DecodeError(e) => write!(f, "{e}"),
             Self::InvalidValueError(s) => write!(f, "invalid value ({s})"),
             Self::NotSupported(s) => write!(f, "not supported ({s})"),
+            Self::UnsupportedTemplate { section, number } => {
+                write!(f, "template {section}.{number} not supported")
+            }
             Self::Unknown(s) => write!(f, "unknown error: {s}"),
         }
     }
@@ -64,6 +82,7 @@ pub enum ParseError {
     NoGridDefinition(usize),
 }
 
+#[cfg(feature = "std")]
 impl Error for ParseError {
     fn description(&self) -> &str {
         "grib parse error"
@@ -77,6 +96,10 @@ impl Display for ParseError {
             #[allow(deprecated)]
             Self::FileTypeCheckError(s) => write!(f, "Error in checking file type: {s}"),
             Self::NotGRIB => write!(f, "Not GRIB data"),
+            Self::GRIBVersionMismatch(1) => write!(
+                f,
+                "GRIB edition 1 is not supported (this crate handles GRIB2 only)"
+            ),
             Self::GRIBVersionMismatch(i) => write!(f, "Not GRIB version 2: {i}"),
             Self::UnknownSectionNumber(s) => write!(f, "Unknown section number: {s}"),
             Self::EndSectionMismatch => write!(f, "Content of End Section is not valid"),
@@ -93,6 +116,7 @@ impl Display for ParseError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<io::Error> for ParseError {
     fn from(e: io::Error) -> Self {
         Self::ReadError(e.to_string())
@@ -110,6 +134,9 @@ pub enum BuildError {
     SectionSizeTooSmall(usize),
 }
 
+#[cfg(feature = "std")]
+impl Error for BuildError {}
+
 impl Display for BuildError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -117,3 +144,36 @@ impl Display for BuildError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn source_of_decode_error() {
+        let err = GribError::DecodeError(DecodeError::LengthMismatch);
+        let source = err.source().expect("source not found");
+        assert_eq!(
+            source.downcast_ref::<DecodeError>(),
+            Some(&DecodeError::LengthMismatch)
+        );
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn source_of_parse_error() {
+        let err = GribError::ParseError(ParseError::NotGRIB);
+        let source = err.source().expect("source not found");
+        assert_eq!(
+            source.downcast_ref::<ParseError>(),
+            Some(&ParseError::NotGRIB)
+        );
+    }
+
+    #[test]
+    fn no_source_of_errors_without_inner_errors() {
+        assert!(GribError::NotSupported("foo".to_owned()).source().is_none());
+    }
+}
@@ -1,11 +1,16 @@
 use helpers::RegularGridIterator;
+#[cfg(feature = "std")]
+pub(crate) use resample::resample_nearest;
 
 pub use self::{
-    earth::EarthShapeDefinition,
+    earth::{EarthShape, EarthShapeDefinition},
     gaussian::{compute_gaussian_latitudes, GaussianGridDefinition},
     lambert::LambertGridDefinition,
+    lambert_azimuthal::LambertAzimuthalEqualAreaGridDefinition,
     latlon::LatLonGridDefinition,
     polar_stereographic::PolarStereographicGridDefinition,
+    space_view::SpaceViewGridDefinition,
+    spherical_harmonic::SphericalHarmonicDefinition,
 };
 
 /// An iterator over latitudes and longitudes of grid points in a submessage.
@@ -19,6 +24,7 @@ pub use self::{
 pub enum GridPointIterator {
     LatLon(RegularGridIterator),
     Lambert(std::vec::IntoIter<(f32, f32)>),
+    ReducedLatLon(std::vec::IntoIter<(f32, f32)>),
 }
 
 impl Iterator for GridPointIterator {
@@ -28,6 +34,7 @@ impl Iterator for GridPointIterator {
         match self {
             Self::LatLon(iter) => iter.next(),
             Self::Lambert(iter) => iter.next(),
+            Self::ReducedLatLon(iter) => iter.next(),
         }
     }
 
@@ -35,12 +42,82 @@ impl Iterator for GridPointIterator {
         match self {
             Self::LatLon(iter) => iter.size_hint(),
             Self::Lambert(iter) => iter.size_hint(),
+            Self::ReducedLatLon(iter) => iter.size_hint(),
         }
     }
 }
 
+impl GridPointIterator {
+    /// Converts the iterator into one that yields longitudes in the range
+    /// `[-180, 180]` instead of `[0, 360]`.
+    ///
+    /// Longitudes greater than 180 degrees are wrapped into the negative range.
+    /// Latitudes and the order of grid points are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{
+    ///     fs::File,
+    ///     io::{BufReader, Read},
+    /// };
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut buf = Vec::new();
+    ///
+    ///     let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+    ///     let f = BufReader::new(f);
+    ///     let mut f = xz2::bufread::XzDecoder::new(f);
+    ///     f.read_to_end(&mut buf)?;
+    ///
+    ///     let grib2 = grib::from_slice(&buf)?;
+    ///     let (_, message) = grib2.iter().next().ok_or_else(|| "first message is not found")?;
+    ///
+    ///     let mut latlons = message.latlons()?.signed_longitudes();
+    ///     assert_eq!(latlons.next(), Some((90.0, 0.0)));
+    ///     let (lat, lon) = latlons.nth(1438).ok_or_else(|| "point not found")?;
+    ///     assert_eq!(lat, 90.0);
+    ///     assert!((lon - -0.25).abs() < 1e-4);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn signed_longitudes(self) -> SignedLongitudeIterator {
+        SignedLongitudeIterator(self)
+    }
+}
+
+/// An iterator over latitudes and longitudes of grid points with longitudes in
+/// the range `[-180, 180]`.
+///
+/// This `struct` is created by the [`signed_longitudes`] method on
+/// [`GridPointIterator`]. See its documentation for more.
+///
+/// [`signed_longitudes`]: GridPointIterator::signed_longitudes
+#[derive(Clone)]
+pub struct SignedLongitudeIterator(GridPointIterator);
+
+impl Iterator for SignedLongitudeIterator {
+    type Item = (f32, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (lat, lon) = self.0.next()?;
+        let lon = if lon > 180. { lon - 360. } else { lon };
+        Some((lat, lon))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
 /// An iterator over `(i, j)` of grid points.
 ///
+/// `(i, j)` is yielded in the order in which grid point values are stored,
+/// following the scanning mode: `j` varies fastest if points are consecutive in
+/// the `j` direction (bit 3 of the scanning mode), and every other row or
+/// column is reversed if adjacent rows or columns scan in opposite directions
+/// (bit 4).
+///
 /// This `struct` is created by the [`ij`] method. See its documentation for
 /// more.
 ///
@@ -106,12 +183,20 @@ impl Iterator for GridPointIndexIterator {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = (self.major_len - self.major_pos) * self.minor_len - self.minor_pos;
-        (len, Some(len))
+        // The product of the numbers of points along rows and columns, each of
+        // which is up to `u32::MAX`, can overflow `usize` on 32-bit platforms.
+        match (self.major_len - self.major_pos)
+            .checked_mul(self.minor_len)
+            .map(|len| len - self.minor_pos)
+        {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScanningMode(pub u8);
 
 impl ScanningMode {
@@ -157,6 +242,18 @@ impl ScanningMode {
         self.0 & 0b00100000 == 0
     }
 
+    /// Returns `true` if adjacent points in `j` (`y`) direction are
+    /// consecutive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(grib::ScanningMode(0b00100000).is_consecutive_for_j(), true);
+    /// ```
+    pub fn is_consecutive_for_j(&self) -> bool {
+        !self.is_consecutive_for_i()
+    }
+
     /// Returns `true` if adjacent rows scans in the opposite direction.
     ///
     /// # Examples
@@ -177,6 +274,7 @@ impl ScanningMode {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProjectionCentreFlag(pub u8);
 
 impl ProjectionCentreFlag {
@@ -213,9 +311,179 @@ impl ProjectionCentreFlag {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::grid::helpers::test_helpers::assert_coord_almost_eq;
+
+    macro_rules! test_scanning_mode_flags {
+        ($((
+            $name:ident,
+            $byte:expr,
+            $positive_i:expr,
+            $positive_j:expr,
+            $consecutive_i:expr,
+            $alternating:expr
+        ),)*) => ($(
+            #[test]
+            fn $name() {
+                let mode = ScanningMode($byte);
+                assert_eq!(mode.scans_positively_for_i(), $positive_i);
+                assert_eq!(mode.scans_positively_for_j(), $positive_j);
+                assert_eq!(mode.is_consecutive_for_i(), $consecutive_i);
+                assert_eq!(mode.is_consecutive_for_j(), !$consecutive_i);
+                assert_eq!(mode.scans_alternating_rows(), $alternating);
+                assert!(!mode.has_unsupported_flags());
+            }
+        )*);
+    }
+
+    test_scanning_mode_flags! {
+        (scanning_mode_0b0000, 0b00000000, true, false, true, false),
+        (scanning_mode_0b0001, 0b00010000, true, false, true, true),
+        (scanning_mode_0b0010, 0b00100000, true, false, false, false),
+        (scanning_mode_0b0011, 0b00110000, true, false, false, true),
+        (scanning_mode_0b0100, 0b01000000, true, true, true, false),
+        (scanning_mode_0b0101, 0b01010000, true, true, true, true),
+        (scanning_mode_0b0110, 0b01100000, true, true, false, false),
+        (scanning_mode_0b0111, 0b01110000, true, true, false, true),
+        (scanning_mode_0b1000, 0b10000000, false, false, true, false),
+        (scanning_mode_0b1001, 0b10010000, false, false, true, true),
+        (scanning_mode_0b1010, 0b10100000, false, false, false, false),
+        (scanning_mode_0b1011, 0b10110000, false, false, false, true),
+        (scanning_mode_0b1100, 0b11000000, false, true, true, false),
+        (scanning_mode_0b1101, 0b11010000, false, true, true, true),
+        (scanning_mode_0b1110, 0b11100000, false, true, false, false),
+        (scanning_mode_0b1111, 0b11110000, false, true, false, true),
+    }
+
+    macro_rules! test_grid_point_index_iterator {
+        ($(($name:ident, $scanning_mode:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let iter = GridPointIndexIterator::new(3, 2, ScanningMode($scanning_mode));
+                assert_eq!(iter.size_hint(), (6, Some(6)));
+                assert_eq!(iter.collect::<Vec<_>>(), $expected);
+            }
+        )*);
+    }
+
+    test_grid_point_index_iterator! {
+        (
+            grid_point_index_iterator_with_scanning_mode_0b00000000,
+            0b00000000,
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        ),
+        (
+            grid_point_index_iterator_with_scanning_mode_0b00100000,
+            0b00100000,
+            vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]
+        ),
+        (
+            grid_point_index_iterator_with_scanning_mode_0b00010000,
+            0b00010000,
+            vec![(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]
+        ),
+        (
+            grid_point_index_iterator_with_scanning_mode_0b00110000,
+            0b00110000,
+            vec![(0, 0), (0, 1), (1, 1), (1, 0), (2, 0), (2, 1)]
+        ),
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn grid_point_index_iterator_with_number_of_points_exceeding_u32() {
+        let mut iter = GridPointIndexIterator::new(65536, 65537, ScanningMode(0b00000000));
+        let len = 65536 * 65537;
+        assert!(len > u32::MAX as usize);
+        assert_eq!(iter.size_hint(), (len, Some(len)));
+        assert_eq!(iter.nth(65536), Some((0, 1)));
+        assert_eq!(iter.size_hint(), (len - 65537, Some(len - 65537)));
+    }
+
+    #[test]
+    fn grid_point_index_iterator_reversing_only_odd_rows() {
+        let iter = GridPointIndexIterator::new(2, 4, ScanningMode(0b00010000));
+        let actual = iter.collect::<Vec<_>>();
+        let expected = vec![
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (1, 3),
+            (0, 3),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ij_of_grid_with_points_consecutive_in_j_direction() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let def = |scanning_mode| LatLonGridDefinition {
+            ni: 3,
+            nj: 2,
+            first_point_lat: 0,
+            first_point_lon: 0,
+            last_point_lat: 1_000_000,
+            last_point_lon: 2_000_000,
+            scanning_mode: ScanningMode(scanning_mode),
+            points_per_row: None,
+        };
+        let default = def(0b01000000).ij()?.take(3).collect::<Vec<_>>();
+        let j_consecutive = def(0b01100000).ij()?.take(3).collect::<Vec<_>>();
+        assert_eq!(default, vec![(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(j_consecutive, vec![(0, 0), (0, 1), (1, 0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn signed_longitudes_of_global_grid() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = std::fs::File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        let (_, submessage) = grib2.iter().next().ok_or("first message is not found")?;
+        let unsigned = submessage.latlons()?.collect::<Vec<_>>();
+        let signed = submessage
+            .latlons()?
+            .signed_longitudes()
+            .collect::<Vec<_>>();
+
+        assert_eq!(signed.len(), unsigned.len());
+        for ((lat, lon), (signed_lat, signed_lon)) in unsigned.iter().zip(signed.iter()) {
+            assert_eq!(signed_lat, lat);
+            assert!((-180. ..=180.).contains(signed_lon));
+            if *lon > 180. {
+                assert_eq!(*signed_lon, lon - 360.);
+            } else {
+                assert_eq!(signed_lon, lon);
+            }
+        }
+        let delta = 1e-4;
+        // may be wrapped into -180 due to the floating point error
+        assert_coord_almost_eq((signed[720].0, signed[720].1.abs()), (90., 180.), delta);
+        assert_coord_almost_eq(signed[721], (90., -179.75), delta);
+        assert_coord_almost_eq(signed[1439], (90., -0.25), delta);
+        Ok(())
+    }
+}
+
 mod earth;
 mod gaussian;
 mod helpers;
 mod lambert;
+mod lambert_azimuthal;
 mod latlon;
 mod polar_stereographic;
+#[cfg(feature = "std")]
+mod resample;
+mod space_view;
+mod spherical_harmonic;
@@ -1,6 +1,32 @@
-use crate::helpers::read_as;
+use crate::{error::GribError, helpers::read_as};
+
+/// Shape of the Earth with concrete dimensions in metres, resolved from Code
+/// Table 3.2.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EarthShape {
+    /// A sphere with the given radius.
+    Spherical { radius: f64 },
+    /// An oblate spheroid with the given semi-major and semi-minor axes.
+    Oblate { major_axis: f64, minor_axis: f64 },
+}
+
+impl EarthShape {
+    /// Returns a tuple of the semi-major and semi-minor axes. Both values are
+    /// the radius for a sphere.
+    pub fn radii(&self) -> (f64, f64) {
+        match *self {
+            Self::Spherical { radius } => (radius, radius),
+            Self::Oblate {
+                major_axis,
+                minor_axis,
+            } => (major_axis, minor_axis),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EarthShapeDefinition {
     pub shape_of_the_earth: u8,
     pub scale_factor_of_radius_of_spherical_earth: u8,
@@ -12,37 +38,79 @@ pub struct EarthShapeDefinition {
 }
 
 impl EarthShapeDefinition {
-    pub fn radii(&self) -> Option<(f64, f64)> {
-        let radii = match self.shape_of_the_earth {
-            0 => (6367470.0, 6367470.0),
-            1 => {
-                let radius = f64::from(self.scaled_value_of_radius_of_spherical_earth)
-                    * f64::powf(
-                        10.,
-                        f64::from(self.scale_factor_of_radius_of_spherical_earth),
-                    );
-                (radius, radius)
-            }
-            2 => (6378160.0, 6356775.0),
+    /// Returns the shape of the Earth with concrete dimensions, or `None` if
+    /// the value of Code Table 3.2 is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grib::{EarthShape, EarthShapeDefinition};
+    ///
+    /// let def = EarthShapeDefinition {
+    ///     shape_of_the_earth: 6,
+    ///     scale_factor_of_radius_of_spherical_earth: 0,
+    ///     scaled_value_of_radius_of_spherical_earth: 0,
+    ///     scale_factor_of_earth_major_axis: 0,
+    ///     scaled_value_of_earth_major_axis: 0,
+    ///     scale_factor_of_earth_minor_axis: 0,
+    ///     scaled_value_of_earth_minor_axis: 0,
+    /// };
+    /// assert_eq!(
+    ///     def.shape(),
+    ///     Some(EarthShape::Spherical { radius: 6371229.0 })
+    /// );
+    /// ```
+    pub fn shape(&self) -> Option<EarthShape> {
+        let shape = match self.shape_of_the_earth {
+            0 => EarthShape::Spherical { radius: 6367470.0 },
+            1 => EarthShape::Spherical {
+                radius: unscale(
+                    self.scaled_value_of_radius_of_spherical_earth,
+                    self.scale_factor_of_radius_of_spherical_earth,
+                ),
+            },
+            2 => oblate(6378160.0, 6356775.0),
             3 => {
                 let (major, minor) = self.radii_defined();
-                (major * 1000., minor * 1000.)
+                oblate(major * 1000., minor * 1000.)
+            }
+            4 => oblate(6378137.0, 6356752.314),
+            5 => oblate(6378137.0, 6356752.3142), // WGS84
+            6 => EarthShape::Spherical { radius: 6371229.0 },
+            7 => {
+                let (major, minor) = self.radii_defined();
+                oblate(major, minor)
             }
-            4 => (6378137.0, 6356752.314),
-            5 => (6378137.0, 6356752.3142), // WGS84
-            6 => (6371229.0, 6371229.0),
-            7 => self.radii_defined(),
-            8 => (6371200.0, 6371200.0),
+            8 => EarthShape::Spherical { radius: 6371200.0 },
             9.. => return None,
         };
-        Some(radii)
+        Some(shape)
+    }
+
+    /// Returns a tuple of the semi-major and semi-minor axes of the Earth, or
+    /// `None` if the value of Code Table 3.2 is unknown.
+    pub fn radii(&self) -> Option<(f64, f64)> {
+        self.shape().map(|shape| shape.radii())
+    }
+
+    pub(crate) fn resolve(&self) -> Result<EarthShape, GribError> {
+        self.shape().ok_or_else(|| {
+            GribError::NotSupported(format!(
+                "unknown value of Code Table 3.2 (shape of the Earth): {}",
+                self.shape_of_the_earth
+            ))
+        })
     }
 
     fn radii_defined(&self) -> (f64, f64) {
-        let major = f64::from(self.scaled_value_of_earth_major_axis)
-            * f64::powf(10., f64::from(self.scale_factor_of_earth_major_axis));
-        let minor = f64::from(self.scaled_value_of_earth_minor_axis)
-            * f64::powf(10., f64::from(self.scale_factor_of_earth_minor_axis));
+        let major = unscale(
+            self.scaled_value_of_earth_major_axis,
+            self.scale_factor_of_earth_major_axis,
+        );
+        let minor = unscale(
+            self.scaled_value_of_earth_minor_axis,
+            self.scale_factor_of_earth_minor_axis,
+        );
         (major, minor)
     }
 
@@ -66,6 +134,18 @@ impl EarthShapeDefinition {
     }
 }
 
+// Scaled values are divided by 10 to the power of scale factors.
+fn unscale(value: u32, factor: u8) -> f64 {
+    f64::from(value) / 10_f64.powi(i32::from(factor))
+}
+
+fn oblate(major_axis: f64, minor_axis: f64) -> EarthShape {
+    EarthShape::Oblate {
+        major_axis,
+        minor_axis,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -179,4 +259,31 @@ mod tests {
 
         Ok(())
     }
+
+    macro_rules! test_shape {
+        ($(($name:ident, $shape:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let def = EarthShapeDefinition {
+                    shape_of_the_earth: $shape,
+                    scale_factor_of_radius_of_spherical_earth: 0,
+                    scaled_value_of_radius_of_spherical_earth: 6371000,
+                    scale_factor_of_earth_major_axis: 2,
+                    scaled_value_of_earth_major_axis: 637813700,
+                    scale_factor_of_earth_minor_axis: 2,
+                    scaled_value_of_earth_minor_axis: 635675231,
+                };
+                assert_eq!(def.shape(), $expected);
+            }
+        )*);
+    }
+
+    test_shape! {
+        (shape_for_spherical_earth_with_fixed_radius, 0, Some(EarthShape::Spherical { radius: 6367470.0 })),
+        (shape_for_spherical_earth_with_specified_radius, 1, Some(EarthShape::Spherical { radius: 6371000.0 })),
+        (shape_for_wgs84, 5, Some(oblate(6378137.0, 6356752.3142))),
+        (shape_for_spherical_earth_with_radius_6371229, 6, Some(EarthShape::Spherical { radius: 6371229.0 })),
+        (shape_for_oblate_earth_with_specified_axes, 7, Some(oblate(6378137.0, 6356752.31))),
+        (shape_for_unknown_code, 9, None),
+    }
 }
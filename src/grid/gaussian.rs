@@ -1,5 +1,5 @@
 use super::{
-    helpers::{evenly_spaced_longitudes, RegularGridIterator},
+    helpers::{evenly_spaced_longitudes, to_microdegrees, RegularGridIterator},
     GridPointIndexIterator, ScanningMode,
 };
 use crate::{
@@ -8,6 +8,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GaussianGridDefinition {
     pub ni: u32,
     pub nj: u32,
@@ -53,6 +54,10 @@ impl GaussianGridDefinition {
     /// Returns an iterator over latitudes and longitudes of grid points in
     /// degrees.
     ///
+    /// Gaussian latitudes are computed once for each row and longitudes once
+    /// for each column before iteration, so that no trigonometric functions
+    /// are evaluated for each grid point.
+    ///
     /// Note that this is a low-level API and it is not checked that the number
     /// of iterator iterations is consistent with the number of grid points
     /// defined in the data.
@@ -90,11 +95,18 @@ impl GaussianGridDefinition {
     pub(crate) fn from_buf(buf: &[u8]) -> Self {
         let ni = read_as!(u32, buf, 0);
         let nj = read_as!(u32, buf, 4);
-        let first_point_lat = read_as!(u32, buf, 16).as_grib_int();
-        let first_point_lon = read_as!(u32, buf, 20).as_grib_int();
-        let last_point_lat = read_as!(u32, buf, 25).as_grib_int();
-        let last_point_lon = read_as!(u32, buf, 29).as_grib_int();
-        let i_direction_inc = read_as!(u32, buf, 33);
+        let basic_angle = read_as!(u32, buf, 8);
+        let subdivisions = read_as!(u32, buf, 12);
+        let to_microdegrees =
+            |value: u32| to_microdegrees(value.as_grib_int(), basic_angle, subdivisions);
+        let first_point_lat = to_microdegrees(read_as!(u32, buf, 16));
+        let first_point_lon = to_microdegrees(read_as!(u32, buf, 20));
+        let last_point_lat = to_microdegrees(read_as!(u32, buf, 25));
+        let last_point_lon = to_microdegrees(read_as!(u32, buf, 29));
+        let i_direction_inc = match read_as!(u32, buf, 33) {
+            u32::MAX => u32::MAX,
+            inc => to_microdegrees(inc) as u32,
+        };
         let n = read_as!(u32, buf, 37);
         let scanning_mode = read_as!(u8, buf, 41);
         Self {
@@ -210,6 +222,36 @@ mod tests {
     use super::*;
     use crate::grid::helpers::test_helpers::assert_almost_eq;
 
+    #[test]
+    fn gaussian_grid_definition_from_buf_with_basic_angle() {
+        // grid from (60N, 0E) to (60S, 358.5E) in units of 1/2 degrees
+        let buf = vec![
+            0x00, 0x00, 0x00, 0xf0, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x78, 0x00, 0x00, 0x00, 0x00, 0x30, 0x80, 0x00, 0x00,
+            0x78, 0x00, 0x00, 0x02, 0xcd, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x28, 0x00,
+        ];
+        let def = GaussianGridDefinition::from_buf(&buf);
+        assert_eq!(def.first_point_lat, 60_000_000);
+        assert_eq!(def.first_point_lon, 0);
+        assert_eq!(def.last_point_lat, -60_000_000);
+        assert_eq!(def.last_point_lon, 358_500_000);
+        assert_eq!(def.i_direction_inc, 1_500_000);
+    }
+
+    #[test]
+    fn gaussian_grid_definition_from_buf_without_basic_angle() {
+        let buf = vec![
+            0x00, 0x00, 0x00, 0xf0, 0x00, 0x00, 0x00, 0x50, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff,
+            0xff, 0xff, 0x03, 0x93, 0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x83, 0x93, 0x87,
+            0x00, 0x15, 0x5e, 0x46, 0xa0, 0x00, 0x16, 0xe3, 0x60, 0x00, 0x00, 0x00, 0x28, 0x00,
+        ];
+        let def = GaussianGridDefinition::from_buf(&buf);
+        assert_eq!(def.first_point_lat, 60_000_000);
+        assert_eq!(def.last_point_lat, -60_000_000);
+        assert_eq!(def.last_point_lon, 358_500_000);
+        assert_eq!(def.i_direction_inc, 1_500_000);
+    }
+
     #[test]
     fn latlon_computation_for_real_world_gaussian_grid_compared_with_results_from_eccodes(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -355,6 +397,54 @@ mod tests {
         ),
     }
 
+    #[test]
+    fn latlons_identical_to_computation_for_each_point() -> Result<(), Box<dyn std::error::Error>> {
+        // Roots of the Legendre polynomial of degree 4 from north to south.
+        let outer = (3.0_f64 / 7.0 + 2.0 / 7.0 * (6.0_f64 / 5.0).sqrt()).sqrt();
+        let inner = (3.0_f64 / 7.0 - 2.0 / 7.0 * (6.0_f64 / 5.0).sqrt()).sqrt();
+        let roots = [outer, inner, -inner, -outer];
+
+        for (scanning_mode, first_point_lat, last_point_lat) in [
+            (0b00000000, 59_444_408, -59_444_408),
+            (0b01000000, -59_444_408, 59_444_408),
+        ] {
+            let grid = GaussianGridDefinition {
+                ni: 8,
+                nj: 4,
+                first_point_lat,
+                first_point_lon: 0,
+                last_point_lat,
+                last_point_lon: 315_000_000,
+                i_direction_inc: 45_000_000,
+                n: 2,
+                scanning_mode: ScanningMode(scanning_mode),
+            };
+            let actual = grid.latlons()?.collect::<Vec<_>>();
+            let expected = grid
+                .ij()?
+                .map(|(i, j)| {
+                    let row = if grid.scanning_mode.scans_positively_for_j() {
+                        3 - j
+                    } else {
+                        j
+                    };
+                    let lat = roots[row].asin().to_degrees() as f32;
+                    let lon = (i as f64 * 45.0) as f32;
+                    (lat, lon)
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual.len(), expected.len());
+            for ((actual_lat, actual_lon), (expected_lat, expected_lon)) in
+                actual.into_iter().zip(expected)
+            {
+                assert_almost_eq!(actual_lat, expected_lat, 1.0e-5);
+                assert_almost_eq!(actual_lon, expected_lon, 1.0e-5);
+            }
+        }
+        Ok(())
+    }
+
     // Values are copied and pasted from ["Features for ERA-40 grids"](https://web.archive.org/web/20160925045844/http://rda.ucar.edu/datasets/common/ecmwf/ERA40/docs/std-transformations/dss_code_glwp.html).
     #[test]
     fn gaussian_latitudes_computation_compared_with_numerical_solutions() {
@@ -5,6 +5,18 @@ use super::ScanningMode;
 #[allow(unused_imports)]
 use crate::{GribError, GridPointIndexIterator};
 
+// Converts a coordinate value in units of `basic_angle / subdivisions` degrees
+// to microdegrees. Following the specification, values are in units of 10^-6
+// degrees if the basic angle is 0 or missing.
+pub(crate) fn to_microdegrees(value: i32, basic_angle: u32, subdivisions: u32) -> i32 {
+    if basic_angle == 0 || basic_angle == u32::MAX || subdivisions == 0 || subdivisions == u32::MAX
+    {
+        return value;
+    }
+    let microdegrees = f64::from(value) * f64::from(basic_angle) * 1e6 / f64::from(subdivisions);
+    microdegrees.round() as i32
+}
+
 pub(crate) fn evenly_spaced_longitudes(
     start_microdegree: i32,
     end_microdegree: i32,
@@ -5,6 +5,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LambertGridDefinition {
     pub earth_shape: EarthShapeDefinition,
     pub ni: u32,
@@ -114,6 +115,10 @@ impl LambertGridDefinition {
     /// Returns an iterator over latitudes and longitudes of grid points in
     /// degrees.
     ///
+    /// Coordinates are converted from the Lambert conformal projection by PROJ
+    /// in a single call for all grid points, so no trigonometric functions are
+    /// evaluated in this crate for each row or grid point.
+    ///
     /// Note that this is a low-level API and it is not checked that the number
     /// of iterator iterations is consistent with the number of grid points
     /// defined in the data.
@@ -123,12 +128,7 @@ impl LambertGridDefinition {
         let lov = self.lov as f64 * 1e-6;
         let latin1 = self.latin1 as f64 * 1e-6;
         let latin2 = self.latin2 as f64 * 1e-6;
-        let (a, b) = self.earth_shape.radii().ok_or_else(|| {
-            GribError::NotSupported(format!(
-                "unknown value of Code Table 3.2 (shape of the Earth): {}",
-                self.earth_shape.shape_of_the_earth
-            ))
-        })?;
+        let (a, b) = self.earth_shape.resolve()?.radii();
         let proj_def = format!(
             "+a={a} +b={b} +proj=lcc +lat_0={lad} +lon_0={lov} +lat_1={latin1} +lat_2={latin2}"
         );
@@ -0,0 +1,450 @@
+use super::{earth::EarthShapeDefinition, GridPointIndexIterator, ScanningMode};
+use crate::{
+    error::GribError,
+    helpers::{read_as, GribInt},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LambertAzimuthalEqualAreaGridDefinition {
+    pub earth_shape: EarthShapeDefinition,
+    pub ni: u32,
+    pub nj: u32,
+    pub first_point_lat: i32,
+    pub first_point_lon: i32,
+    pub standard_parallel: i32,
+    pub central_lon: i32,
+    pub dx: u32,
+    pub dy: u32,
+    pub scanning_mode: ScanningMode,
+}
+
+impl LambertAzimuthalEqualAreaGridDefinition {
+    /// Returns the shape of the grid, i.e. a tuple of the number of grids in
+    /// the i and j directions.
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// let def = grib::LambertAzimuthalEqualAreaGridDefinition {
+    ///     earth_shape: grib::EarthShapeDefinition {
+    ///         shape_of_the_earth: 4,
+    ///         scale_factor_of_radius_of_spherical_earth: 0xff,
+    ///         scaled_value_of_radius_of_spherical_earth: 0xffffffff,
+    ///         scale_factor_of_earth_major_axis: 0xff,
+    ///         scaled_value_of_earth_major_axis: 0xffffffff,
+    ///         scale_factor_of_earth_minor_axis: 0xff,
+    ///         scaled_value_of_earth_minor_axis: 0xffffffff,
+    ///     },
+    ///     ni: 2,
+    ///     nj: 3,
+    ///     first_point_lat: 52000000,
+    ///     first_point_lon: 10000000,
+    ///     standard_parallel: 52000000,
+    ///     central_lon: 10000000,
+    ///     dx: 1000000,
+    ///     dy: 1000000,
+    ///     scanning_mode: grib::ScanningMode(0b01000000),
+    /// };
+    /// let shape = def.grid_shape();
+    /// assert_eq!(shape, (2, 3));
+    /// ```
+    pub fn grid_shape(&self) -> (usize, usize) {
+        (self.ni as usize, self.nj as usize)
+    }
+
+    /// Returns the grid type.
+    pub fn short_name(&self) -> &'static str {
+        "lambert_azimuthal_equal_area"
+    }
+
+    /// Returns an iterator over `(i, j)` of grid points.
+    ///
+    /// Note that this is a low-level API and it is not checked that the number
+    /// of iterator iterations is consistent with the number of grid points
+    /// defined in the data.
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// let def = grib::LambertAzimuthalEqualAreaGridDefinition {
+    ///     earth_shape: grib::EarthShapeDefinition {
+    ///         shape_of_the_earth: 4,
+    ///         scale_factor_of_radius_of_spherical_earth: 0xff,
+    ///         scaled_value_of_radius_of_spherical_earth: 0xffffffff,
+    ///         scale_factor_of_earth_major_axis: 0xff,
+    ///         scaled_value_of_earth_major_axis: 0xffffffff,
+    ///         scale_factor_of_earth_minor_axis: 0xff,
+    ///         scaled_value_of_earth_minor_axis: 0xffffffff,
+    ///     },
+    ///     ni: 2,
+    ///     nj: 3,
+    ///     first_point_lat: 52000000,
+    ///     first_point_lon: 10000000,
+    ///     standard_parallel: 52000000,
+    ///     central_lon: 10000000,
+    ///     dx: 1000000,
+    ///     dy: 1000000,
+    ///     scanning_mode: grib::ScanningMode(0b01000000),
+    /// };
+    /// let ij = def.ij();
+    /// assert!(ij.is_ok());
+    ///
+    /// let mut ij = ij.unwrap();
+    /// assert_eq!(ij.next(), Some((0, 0)));
+    /// assert_eq!(ij.next(), Some((1, 0)));
+    /// assert_eq!(ij.next(), Some((0, 1)));
+    /// ```
+    pub fn ij(&self) -> Result<GridPointIndexIterator, GribError> {
+        if self.scanning_mode.has_unsupported_flags() {
+            let ScanningMode(mode) = self.scanning_mode;
+            return Err(GribError::NotSupported(format!("scanning mode {mode}")));
+        }
+
+        let iter =
+            GridPointIndexIterator::new(self.ni as usize, self.nj as usize, self.scanning_mode);
+        Ok(iter)
+    }
+
+    /// Returns an iterator over latitudes and longitudes of grid points in
+    /// degrees.
+    ///
+    /// Unlike other projected grids, the inverse projection is computed by
+    /// this library itself and does not require the `gridpoints-proj` feature.
+    /// Terms depending only on the row or the column are computed once for
+    /// each row and each column before iteration.
+    ///
+    /// Note that this is a low-level API and it is not checked that the number
+    /// of iterator iterations is consistent with the number of grid points
+    /// defined in the data.
+    pub fn latlons(&self) -> Result<std::vec::IntoIter<(f32, f32)>, GribError> {
+        let (a, b) = self.earth_shape.resolve()?.radii();
+        let projection = Projection::new(
+            a,
+            b,
+            self.standard_parallel as f64 * 1e-6,
+            self.central_lon as f64 * 1e-6,
+        );
+
+        let (first_point_x, first_point_y) = projection.forward(
+            self.first_point_lat as f64 * 1e-6,
+            self.first_point_lon as f64 * 1e-6,
+        );
+
+        let dx = self.dx as f64 * 1e-3;
+        let dy = self.dy as f64 * 1e-3;
+        let dx = if !self.scanning_mode.scans_positively_for_i() && dx > 0. {
+            -dx
+        } else {
+            dx
+        };
+        let dy = if !self.scanning_mode.scans_positively_for_j() && dy > 0. {
+            -dy
+        } else {
+            dy
+        };
+
+        let (ni, nj) = self.grid_shape();
+        let columns = (0..ni)
+            .map(|i| projection.x_terms(first_point_x + dx * i as f64))
+            .collect::<Vec<_>>();
+        let rows = (0..nj)
+            .map(|j| projection.y_terms(first_point_y + dy * j as f64))
+            .collect::<Vec<_>>();
+
+        let latlons = self
+            .ij()?
+            .map(|(i, j)| {
+                let (lat, lon) = projection.inverse_with_terms(&columns[i], &rows[j]);
+                (lat as f32, lon as f32)
+            })
+            .collect::<Vec<_>>();
+        Ok(latlons.into_iter())
+    }
+
+    pub(crate) fn from_buf(buf: &[u8]) -> Self {
+        let earth_shape = EarthShapeDefinition::from_buf(buf);
+        let ni = read_as!(u32, buf, 16);
+        let nj = read_as!(u32, buf, 20);
+        let first_point_lat = read_as!(u32, buf, 24).as_grib_int();
+        let first_point_lon = read_as!(u32, buf, 28).as_grib_int();
+        let standard_parallel = read_as!(u32, buf, 32).as_grib_int();
+        let central_lon = read_as!(u32, buf, 36).as_grib_int();
+        let dx = read_as!(u32, buf, 41);
+        let dy = read_as!(u32, buf, 45);
+        let scanning_mode = read_as!(u8, buf, 49);
+        Self {
+            earth_shape,
+            ni,
+            nj,
+            first_point_lat,
+            first_point_lon,
+            standard_parallel,
+            central_lon,
+            dx,
+            dy,
+            scanning_mode: ScanningMode(scanning_mode),
+        }
+    }
+}
+
+// Lambert azimuthal equal-area projection on the ellipsoid, following the
+// formulae in Snyder (1987), "Map Projections: A Working Manual", pp. 187-190.
+// The polar aspects are handled as the limit of the oblique aspect.
+struct Projection {
+    a: f64,
+    e: f64,
+    qp: f64,
+    rq: f64,
+    d: f64,
+    sin_beta1: f64,
+    cos_beta1: f64,
+    lon0: f64,
+    // coefficients of the series for the latitude from the authalic latitude
+    authalic_coeffs: [f64; 3],
+}
+
+// Terms of the inverse projection depending only on the x coordinate.
+struct XTerms {
+    x: f64,
+    x_over_d_sq: f64,
+}
+
+// Terms of the inverse projection depending only on the y coordinate.
+struct YTerms {
+    y_d: f64,
+    y_d_sq: f64,
+}
+
+impl Projection {
+    fn new(a: f64, b: f64, lat0_in_degrees: f64, lon0_in_degrees: f64) -> Self {
+        let e = (1. - (b * b) / (a * a)).sqrt();
+        let qp = q(e, 1.);
+        let rq = a * (qp / 2.).sqrt();
+
+        let lat0 = lat0_in_degrees.to_radians();
+        let (sin_lat0, cos_lat0) = lat0.sin_cos();
+        let beta1 = (q(e, sin_lat0) / qp).clamp(-1., 1.).asin();
+        let (sin_beta1, cos_beta1) = beta1.sin_cos();
+        let d = if cos_beta1.abs() < 1e-12 {
+            1.
+        } else {
+            let m1 = cos_lat0 / (1. - e * e * sin_lat0 * sin_lat0).sqrt();
+            a * m1 / (rq * cos_beta1)
+        };
+
+        let e2 = e * e;
+        let e4 = e2 * e2;
+        let e6 = e4 * e2;
+        let authalic_coeffs = [
+            e2 / 3. + 31. * e4 / 180. + 517. * e6 / 5040.,
+            23. * e4 / 360. + 251. * e6 / 3780.,
+            761. * e6 / 45360.,
+        ];
+
+        Self {
+            a,
+            e,
+            qp,
+            rq,
+            d,
+            sin_beta1,
+            cos_beta1,
+            lon0: lon0_in_degrees.to_radians(),
+            authalic_coeffs,
+        }
+    }
+
+    fn forward(&self, lat_in_degrees: f64, lon_in_degrees: f64) -> (f64, f64) {
+        let beta = (q(self.e, lat_in_degrees.to_radians().sin()) / self.qp)
+            .clamp(-1., 1.)
+            .asin();
+        let (sin_beta, cos_beta) = beta.sin_cos();
+        let (sin_dlon, cos_dlon) = (lon_in_degrees.to_radians() - self.lon0).sin_cos();
+
+        let denom = 1. + self.sin_beta1 * sin_beta + self.cos_beta1 * cos_beta * cos_dlon;
+        if denom <= 0. {
+            // the antipode of the projection origin
+            return (f64::NAN, f64::NAN);
+        }
+        let b = self.rq * (2. / denom).sqrt();
+        let x = b * self.d * cos_beta * sin_dlon;
+        let y = (b / self.d) * (self.cos_beta1 * sin_beta - self.sin_beta1 * cos_beta * cos_dlon);
+        (x, y)
+    }
+
+    #[cfg(test)]
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        self.inverse_with_terms(&self.x_terms(x), &self.y_terms(y))
+    }
+
+    fn x_terms(&self, x: f64) -> XTerms {
+        XTerms {
+            x,
+            x_over_d_sq: (x / self.d).powi(2),
+        }
+    }
+
+    fn y_terms(&self, y: f64) -> YTerms {
+        let y_d = self.d * y;
+        YTerms {
+            y_d,
+            y_d_sq: y_d * y_d,
+        }
+    }
+
+    fn inverse_with_terms(&self, x_terms: &XTerms, y_terms: &YTerms) -> (f64, f64) {
+        let &XTerms { x, x_over_d_sq } = x_terms;
+        let &YTerms { y_d, y_d_sq } = y_terms;
+        let rho = (x_over_d_sq + y_d_sq).sqrt();
+        if rho < 1e-9 * self.a {
+            let beta = self.sin_beta1.asin();
+            return (
+                self.latitude_from_authalic(beta).to_degrees(),
+                normalize_lon(self.lon0).to_degrees(),
+            );
+        }
+
+        let ce = 2. * (rho / (2. * self.rq)).clamp(-1., 1.).asin();
+        let (sin_ce, cos_ce) = ce.sin_cos();
+        let beta = (cos_ce * self.sin_beta1 + y_d * sin_ce * self.cos_beta1 / rho)
+            .clamp(-1., 1.)
+            .asin();
+        let lon = self.lon0
+            + (x * sin_ce)
+                .atan2(self.d * (rho * self.cos_beta1 * cos_ce - y_d * self.sin_beta1 * sin_ce));
+        (
+            self.latitude_from_authalic(beta).to_degrees(),
+            normalize_lon(lon).to_degrees(),
+        )
+    }
+
+    fn latitude_from_authalic(&self, beta: f64) -> f64 {
+        // sin(4β) and sin(6β) are derived from sin(2β) and cos(2β) using the
+        // multiple-angle formulae.
+        let (sin_2beta, cos_2beta) = (2. * beta).sin_cos();
+        let sin_4beta = 2. * sin_2beta * cos_2beta;
+        let sin_6beta = sin_2beta * (3. - 4. * sin_2beta * sin_2beta);
+        let [c2, c4, c6] = self.authalic_coeffs;
+        beta + c2 * sin_2beta + c4 * sin_4beta + c6 * sin_6beta
+    }
+}
+
+fn q(e: f64, sin_lat: f64) -> f64 {
+    if e < 1e-12 {
+        return 2. * sin_lat;
+    }
+    let e_sin_lat = e * sin_lat;
+    (1. - e * e)
+        * (sin_lat / (1. - e_sin_lat * e_sin_lat)
+            - (1. / (2. * e)) * ((1. - e_sin_lat) / (1. + e_sin_lat)).ln())
+}
+
+fn normalize_lon(lon: f64) -> f64 {
+    let lon = lon % std::f64::consts::TAU;
+    if lon < 0. {
+        lon + std::f64::consts::TAU
+    } else {
+        lon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::helpers::test_helpers::assert_coord_almost_eq;
+
+    fn etrs89_laea_grid() -> LambertAzimuthalEqualAreaGridDefinition {
+        // The first point is taken from the example of ETRS89-LAEA (EPSG:3035)
+        // in EPSG Guidance Note 7-2: (50N, 5E) is projected to (-358200.55 m,
+        // -210281.15 m) from the projection origin (52N, 10E). The grid is
+        // defined so that its center point is the projection origin.
+        LambertAzimuthalEqualAreaGridDefinition {
+            earth_shape: EarthShapeDefinition {
+                shape_of_the_earth: 4,
+                scale_factor_of_radius_of_spherical_earth: 0xff,
+                scaled_value_of_radius_of_spherical_earth: 0xffffffff,
+                scale_factor_of_earth_major_axis: 0xff,
+                scaled_value_of_earth_major_axis: 0xffffffff,
+                scale_factor_of_earth_minor_axis: 0xff,
+                scaled_value_of_earth_minor_axis: 0xffffffff,
+            },
+            ni: 3,
+            nj: 3,
+            first_point_lat: 50000000,
+            first_point_lon: 5000000,
+            standard_parallel: 52000000,
+            central_lon: 10000000,
+            dx: 358200550,
+            dy: 210281150,
+            scanning_mode: ScanningMode(0b01000000),
+        }
+    }
+
+    #[test]
+    fn lambert_azimuthal_equal_area_grid_definition_from_buf() {
+        let buf = vec![
+            0x04, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x03, 0x02, 0xfa, 0xf0, 0x80,
+            0x00, 0x4c, 0x4b, 0x40, 0x03, 0x19, 0x75, 0x00, 0x00, 0x98, 0x96, 0x80, 0x30, 0x15,
+            0x59, 0xb4, 0xe6, 0x0c, 0x88, 0xa2, 0xbe, 0x40,
+        ];
+        let actual = LambertAzimuthalEqualAreaGridDefinition::from_buf(&buf);
+        assert_eq!(actual, etrs89_laea_grid());
+    }
+
+    #[test]
+    fn lambert_azimuthal_equal_area_grid_center_maps_to_projection_origin(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let latlons = etrs89_laea_grid().latlons()?.collect::<Vec<_>>();
+        assert_eq!(latlons.len(), 9);
+
+        let delta = 1e-5;
+        assert_coord_almost_eq(latlons[0], (50., 5.), delta);
+        assert_coord_almost_eq(latlons[4], (52., 10.), delta);
+        Ok(())
+    }
+
+    #[test]
+    fn lambert_azimuthal_equal_area_grid_points_projected_back_onto_grid(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let grid = LambertAzimuthalEqualAreaGridDefinition {
+            ni: 40,
+            nj: 30,
+            dx: 50000000,
+            dy: 50000000,
+            scanning_mode: ScanningMode(0b00000000),
+            ..etrs89_laea_grid()
+        };
+        let latlons = grid.latlons()?.collect::<Vec<_>>();
+        assert_eq!(latlons.len(), 1200);
+
+        let (a, b) = grid.earth_shape.resolve()?.radii();
+        let projection = Projection::new(a, b, 52., 10.);
+        let (first_point_x, first_point_y) = projection.forward(50., 5.);
+        for ((i, j), (lat, lon)) in grid.ij()?.zip(latlons) {
+            let (x, y) = projection.forward(lat as f64, lon as f64);
+            // errors of latitudes and longitudes in `f32` are up to about 1 m
+            assert!((x - (first_point_x + 50000. * i as f64)).abs() < 2.);
+            assert!((y - (first_point_y - 50000. * j as f64)).abs() < 2.);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn lambert_azimuthal_equal_area_projection_round_trip() {
+        let projection = Projection::new(6378137.0, 6356752.314, 52., 10.);
+        let (x, y) = projection.forward(50., 5.);
+        assert!((x - -358200.55).abs() < 1e-2);
+        assert!((y - -210281.15).abs() < 1e-2);
+
+        for (lat, lon) in [(90., 0.), (-60., 200.), (10., 350.)] {
+            let (x, y) = projection.forward(lat, lon);
+            let (actual_lat, actual_lon) = projection.inverse(x, y);
+            assert!((actual_lat - lat).abs() < 1e-6);
+            if lat != 90. {
+                assert!((actual_lon - lon).abs() < 1e-6);
+            }
+        }
+    }
+}
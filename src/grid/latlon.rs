@@ -1,6 +1,8 @@
 use super::{
-    helpers::{evenly_spaced_degrees, evenly_spaced_longitudes, RegularGridIterator},
-    GridPointIndexIterator, ScanningMode,
+    helpers::{
+        evenly_spaced_degrees, evenly_spaced_longitudes, to_microdegrees, RegularGridIterator,
+    },
+    GridPointIndexIterator, GridPointIterator, ScanningMode,
 };
 use crate::{
     error::GribError,
@@ -8,6 +10,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LatLonGridDefinition {
     pub ni: u32,
     pub nj: u32,
@@ -16,6 +19,10 @@ pub struct LatLonGridDefinition {
     pub last_point_lat: i32,
     pub last_point_lon: i32,
     pub scanning_mode: ScanningMode,
+    /// Numbers of points along each parallel, given for quasi-regular
+    /// (reduced) grids by the optional list of numbers of points following
+    /// the template.
+    pub points_per_row: Option<Vec<u32>>,
 }
 
 impl LatLonGridDefinition {
@@ -33,6 +40,7 @@ impl LatLonGridDefinition {
     ///     last_point_lat: 2_000_000,
     ///     last_point_lon: 1_000_000,
     ///     scanning_mode: grib::ScanningMode(0b01000000),
+    ///     points_per_row: None,
     /// };
     /// let shape = def.grid_shape();
     /// assert_eq!(shape, (2, 3));
@@ -52,6 +60,9 @@ impl LatLonGridDefinition {
     /// of iterator iterations is consistent with the number of grid points
     /// defined in the data.
     ///
+    /// For quasi-regular (reduced) grids, `(i, j)` is not available and an
+    /// error is returned.
+    ///
     /// Examples
     ///
     /// ```
@@ -63,6 +74,7 @@ impl LatLonGridDefinition {
     ///     last_point_lat: 2_000_000,
     ///     last_point_lon: 1_000_000,
     ///     scanning_mode: grib::ScanningMode(0b01000000),
+    ///     points_per_row: None,
     /// };
     /// let ij = def.ij();
     /// assert!(ij.is_ok());
@@ -73,6 +85,12 @@ impl LatLonGridDefinition {
     /// assert_eq!(ij.next(), Some((0, 1)));
     /// ```
     pub fn ij(&self) -> Result<GridPointIndexIterator, GribError> {
+        if self.points_per_row.is_some() {
+            return Err(GribError::NotSupported(
+                "(i, j) for quasi-regular grids".to_owned(),
+            ));
+        }
+
         if self.scanning_mode.has_unsupported_flags() {
             let ScanningMode(mode) = self.scanning_mode;
             return Err(GribError::NotSupported(format!("scanning mode {mode}")));
@@ -90,6 +108,10 @@ impl LatLonGridDefinition {
     /// of iterator iterations is consistent with the number of grid points
     /// defined in the data.
     ///
+    /// For quasi-regular (reduced) grids, longitudes are evenly spaced along
+    /// each parallel according to the number of points in the row. If a row
+    /// covers the whole circle, the points are distributed over 360 degrees.
+    ///
     /// Examples
     ///
     /// ```
@@ -101,6 +123,7 @@ impl LatLonGridDefinition {
     ///     last_point_lat: 2_000_000,
     ///     last_point_lon: 1_000_000,
     ///     scanning_mode: grib::ScanningMode(0b01000000),
+    ///     points_per_row: None,
     /// };
     /// let latlons = def.latlons();
     /// assert!(latlons.is_ok());
@@ -110,7 +133,7 @@ impl LatLonGridDefinition {
     /// assert_eq!(latlons.next(), Some((0.0, 1.0)));
     /// assert_eq!(latlons.next(), Some((1.0, 0.0)));
     /// ```
-    pub fn latlons(&self) -> Result<RegularGridIterator, GribError> {
+    pub fn latlons(&self) -> Result<GridPointIterator, GribError> {
         if !self.is_consistent_for_j() {
             return Err(GribError::InvalidValueError(
                 "Latitudes for first/last grid points are not consistent with scanning mode"
@@ -118,21 +141,100 @@ impl LatLonGridDefinition {
             ));
         }
 
+        let lat_div = self.nj.checked_sub(1).ok_or_else(no_grid_points_error)?;
+        if let Some(points_per_row) = &self.points_per_row {
+            return self.reduced_latlons(points_per_row, lat_div);
+        }
+        let lon_div = self.ni.checked_sub(1).ok_or_else(no_grid_points_error)?;
+
         let ij = self.ij()?;
         let lat = evenly_spaced_degrees(
             self.first_point_lat as f32,
             self.last_point_lat as f32,
-            (self.nj - 1) as usize,
+            lat_div as usize,
         );
         let lon = evenly_spaced_longitudes(
             self.first_point_lon,
             self.last_point_lon,
-            (self.ni - 1) as usize,
+            lon_div as usize,
             self.scanning_mode,
         );
 
         let iter = RegularGridIterator::new(lat, lon, ij);
-        Ok(iter)
+        Ok(GridPointIterator::LatLon(iter))
+    }
+
+    fn reduced_latlons(
+        &self,
+        points_per_row: &[u32],
+        lat_div: u32,
+    ) -> Result<GridPointIterator, GribError> {
+        if self.scanning_mode.has_unsupported_flags()
+            || !self.scanning_mode.is_consecutive_for_i()
+            || self.scanning_mode.scans_alternating_rows()
+        {
+            let ScanningMode(mode) = self.scanning_mode;
+            return Err(GribError::NotSupported(format!(
+                "scanning mode {mode} for quasi-regular grids"
+            )));
+        }
+
+        if points_per_row.len() != self.nj as usize {
+            return Err(GribError::InvalidValueError(format!(
+                "length of list of numbers of points does not match Nj: {} vs {}",
+                points_per_row.len(),
+                self.nj
+            )));
+        }
+
+        let lat = evenly_spaced_degrees(
+            self.first_point_lat as f32,
+            self.last_point_lat as f32,
+            lat_div as usize,
+        );
+        let latlons = lat
+            .into_iter()
+            .zip(points_per_row)
+            .flat_map(|(lat, num_points)| {
+                self.row_longitudes(*num_points as usize)
+                    .into_iter()
+                    .map(move |lon| (lat, lon))
+            })
+            .collect::<Vec<_>>();
+        Ok(GridPointIterator::ReducedLatLon(latlons.into_iter()))
+    }
+
+    fn row_longitudes(&self, num_points: usize) -> Vec<f32> {
+        const FULL_CIRCLE: f64 = 360_000_000.;
+
+        match num_points {
+            0 => Vec::new(),
+            1 => vec![self.first_point_lon as f32 / 1_000_000_f32],
+            _ => {
+                let span = f64::from(self.last_point_lon - self.first_point_lon).abs();
+                if span + FULL_CIRCLE / num_points as f64 >= FULL_CIRCLE {
+                    let delta = if self.scanning_mode.scans_positively_for_i() {
+                        FULL_CIRCLE / num_points as f64
+                    } else {
+                        -FULL_CIRCLE / num_points as f64
+                    };
+                    (0..num_points)
+                        .map(|i| {
+                            let lon = (f64::from(self.first_point_lon) + delta * i as f64)
+                                .rem_euclid(FULL_CIRCLE);
+                            (lon / 1_000_000.) as f32
+                        })
+                        .collect()
+                } else {
+                    evenly_spaced_longitudes(
+                        self.first_point_lon,
+                        self.last_point_lon,
+                        num_points - 1,
+                        self.scanning_mode,
+                    )
+                }
+            }
+        }
     }
 
     pub(crate) fn is_consistent_for_j(&self) -> bool {
@@ -143,10 +245,14 @@ impl LatLonGridDefinition {
     pub(crate) fn from_buf(buf: &[u8]) -> Self {
         let ni = read_as!(u32, buf, 0);
         let nj = read_as!(u32, buf, 4);
-        let first_point_lat = read_as!(u32, buf, 16).as_grib_int();
-        let first_point_lon = read_as!(u32, buf, 20).as_grib_int();
-        let last_point_lat = read_as!(u32, buf, 25).as_grib_int();
-        let last_point_lon = read_as!(u32, buf, 29).as_grib_int();
+        let basic_angle = read_as!(u32, buf, 8);
+        let subdivisions = read_as!(u32, buf, 12);
+        let to_microdegrees =
+            |value: u32| to_microdegrees(value.as_grib_int(), basic_angle, subdivisions);
+        let first_point_lat = to_microdegrees(read_as!(u32, buf, 16));
+        let first_point_lon = to_microdegrees(read_as!(u32, buf, 20));
+        let last_point_lat = to_microdegrees(read_as!(u32, buf, 25));
+        let last_point_lon = to_microdegrees(read_as!(u32, buf, 29));
         let scanning_mode = read_as!(u8, buf, 41);
         Self {
             ni,
@@ -156,13 +262,81 @@ impl LatLonGridDefinition {
             last_point_lat,
             last_point_lon,
             scanning_mode: ScanningMode(scanning_mode),
+            points_per_row: None,
         }
     }
 }
 
+fn no_grid_points_error() -> GribError {
+    GribError::InvalidValueError("grid has no grid points in the i or j direction".to_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::helpers::test_helpers::assert_coord_almost_eq;
+
+    #[test]
+    fn lat_lon_grid_definition_from_buf_with_basic_angle() {
+        // 1/12-degree grid from (2N, 0E) to (0N, 1E) in units of 1/12 degrees
+        let buf = vec![
+            0x00, 0x00, 0x00, 0x0d, 0x00, 0x00, 0x00, 0x19, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x0c, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00,
+        ];
+        let def = LatLonGridDefinition::from_buf(&buf);
+        assert_eq!(def.first_point_lat, 2_000_000);
+        assert_eq!(def.first_point_lon, 0);
+        assert_eq!(def.last_point_lat, 0);
+        assert_eq!(def.last_point_lon, 1_000_000);
+
+        let latlons = def.latlons().unwrap().collect::<Vec<_>>();
+        assert_eq!(latlons.len(), 13 * 25);
+        for (index, actual) in latlons.into_iter().enumerate() {
+            let (i, j) = (index % 13, index / 13);
+            let expected = ((24 - j) as f32 / 12., i as f32 / 12.);
+            assert_coord_almost_eq(actual, expected, 1e-6);
+        }
+    }
+
+    #[test]
+    fn lat_lon_calculation_for_grid_scanning_alternating_rows() {
+        let grid = LatLonGridDefinition {
+            ni: 3,
+            nj: 3,
+            first_point_lat: 0,
+            first_point_lon: 0,
+            last_point_lat: 2_000_000,
+            last_point_lon: 2_000_000,
+            scanning_mode: ScanningMode(0b01010000),
+            points_per_row: None,
+        };
+        let actual = grid.latlons().unwrap().collect::<Vec<_>>();
+        let expected = vec![
+            (0.0, 0.0),
+            (0.0, 1.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn lat_lon_grid_definition_from_buf_without_basic_angle() {
+        let buf = vec![
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff,
+            0xff, 0xff, 0x00, 0x1e, 0x84, 0x80, 0x00, 0x00, 0x00, 0x00, 0x30, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x0f, 0x42, 0x40, 0x00, 0x0f, 0x42, 0x40, 0x00, 0x1e, 0x84, 0x80, 0x00,
+        ];
+        let def = LatLonGridDefinition::from_buf(&buf);
+        assert_eq!(def.first_point_lat, 2_000_000);
+        assert_eq!(def.last_point_lon, 1_000_000);
+    }
 
     macro_rules! test_lat_lon_calculation_for_inconsistent_longitude_definitions {
         ($((
@@ -201,6 +375,7 @@ mod tests {
                 last_point_lat: 90000000,
                 last_point_lon: 359760000,
                 scanning_mode: ScanningMode(0b01000000),
+                points_per_row: None,
             },
             vec![(-90.0, 0.0), (-90.0, 0.24), (-90.0, 0.48)],
             vec![(90.0, 359.28), (90.0, 359.52), (90.0, 359.76)]
@@ -217,6 +392,7 @@ mod tests {
                 last_point_lat: 90000000,
                 last_point_lon: 179760000,
                 scanning_mode: ScanningMode(0b01000000),
+                points_per_row: None,
             },
             vec![(-90.0, 180.0), (-90.0, 180.24), (-90.0, 180.48)],
             vec![(90.0, 179.28003), (90.0, 179.52002), (90.0, 179.76001)]
@@ -231,6 +407,7 @@ mod tests {
                 last_point_lat: 90000000,
                 last_point_lon: 0,
                 scanning_mode: ScanningMode(0b11000000),
+                points_per_row: None,
             },
             vec![(-90.0, 359.76), (-90.0, 359.52), (-90.0, 359.28)],
             vec![(90.0, 0.48), (90.0, 0.24), (90.0, 0.0)]
@@ -245,6 +422,7 @@ mod tests {
                 last_point_lat: 90000000,
                 last_point_lon: 180000000,
                 scanning_mode: ScanningMode(0b11000000),
+                points_per_row: None,
             },
             vec![(-90.0, 179.76001), (-90.0, 179.52002), (-90.0, 179.28003)],
             vec![(90.0, 180.48), (90.0, 180.24), (90.0, 180.0)]
@@ -271,6 +449,7 @@ mod tests {
                     last_point_lat: $last_point_lat,
                     last_point_lon: $last_point_lon,
                     scanning_mode: ScanningMode($scanning_mode),
+                    points_per_row: None,
                 };
                 assert_eq!(grid.is_consistent_for_j(), $expected_for_j);
             }
@@ -315,4 +494,110 @@ mod tests {
             true
         ),
     }
+
+    macro_rules! test_lat_lon_calculation_for_quasi_regular_grids {
+        ($((
+            $name:ident,
+            $first_point_lon:expr,
+            $last_point_lon:expr,
+            $points_per_row:expr,
+            $expected:expr
+        ),)*) => ($(
+            #[test]
+            fn $name() {
+                let grid = LatLonGridDefinition {
+                    ni: 0xffffffff,
+                    nj: 3,
+                    first_point_lat: 10_000_000,
+                    first_point_lon: $first_point_lon,
+                    last_point_lat: -10_000_000,
+                    last_point_lon: $last_point_lon,
+                    scanning_mode: ScanningMode(0b00000000),
+                    points_per_row: Some($points_per_row),
+                };
+                let actual = grid.latlons().unwrap().collect::<Vec<_>>();
+                assert_eq!(actual, $expected);
+            }
+        )*);
+    }
+
+    test_lat_lon_calculation_for_quasi_regular_grids! {
+        (
+            lat_lon_calculation_for_regional_quasi_regular_grid,
+            0,
+            90_000_000,
+            vec![2, 4, 2],
+            vec![
+                (10.0, 0.0),
+                (10.0, 90.0),
+                (0.0, 0.0),
+                (0.0, 30.0),
+                (0.0, 60.0),
+                (0.0, 90.0),
+                (-10.0, 0.0),
+                (-10.0, 90.0),
+            ]
+        ),
+        (
+            lat_lon_calculation_for_global_quasi_regular_grid,
+            0,
+            270_000_000,
+            vec![2, 4, 1],
+            vec![
+                (10.0, 0.0),
+                (10.0, 180.0),
+                (0.0, 0.0),
+                (0.0, 90.0),
+                (0.0, 180.0),
+                (0.0, 270.0),
+                (-10.0, 0.0),
+            ]
+        ),
+    }
+
+    #[test]
+    fn lat_lon_calculation_for_quasi_regular_grid_with_inconsistent_list_length() {
+        let grid = LatLonGridDefinition {
+            ni: 0xffffffff,
+            nj: 3,
+            first_point_lat: 10_000_000,
+            first_point_lon: 0,
+            last_point_lat: -10_000_000,
+            last_point_lon: 90_000_000,
+            scanning_mode: ScanningMode(0b00000000),
+            points_per_row: Some(vec![2, 4]),
+        };
+        assert!(grid.latlons().is_err());
+        assert!(grid.ij().is_err());
+    }
+
+    macro_rules! test_lat_lon_calculation_for_grid_without_points {
+        ($(($name:ident, $ni:expr, $nj:expr, $points_per_row:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let grid = LatLonGridDefinition {
+                    ni: $ni,
+                    nj: $nj,
+                    first_point_lat: 10_000_000,
+                    first_point_lon: 0,
+                    last_point_lat: -10_000_000,
+                    last_point_lon: 90_000_000,
+                    scanning_mode: ScanningMode(0b00000000),
+                    points_per_row: $points_per_row,
+                };
+                assert_eq!(
+                    grid.latlons().err(),
+                    Some(GribError::InvalidValueError(
+                        "grid has no grid points in the i or j direction".to_owned()
+                    ))
+                );
+            }
+        )*);
+    }
+
+    test_lat_lon_calculation_for_grid_without_points! {
+        (lat_lon_calculation_for_regular_grid_with_zero_ni, 0, 3, None),
+        (lat_lon_calculation_for_regular_grid_with_zero_nj, 3, 0, None),
+        (lat_lon_calculation_for_quasi_regular_grid_with_zero_nj, 0xffffffff, 0, Some(vec![])),
+    }
 }
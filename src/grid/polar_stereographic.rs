@@ -6,6 +6,7 @@ use crate::{
 };
 
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PolarStereographicGridDefinition {
     pub earth_shape: EarthShapeDefinition,
     pub ni: u32,
@@ -112,6 +113,10 @@ impl PolarStereographicGridDefinition {
     /// Returns an iterator over latitudes and longitudes of grid points in
     /// degrees.
     ///
+    /// Coordinates are converted from the polar stereographic projection by PROJ
+    /// in a single call for all grid points, so no trigonometric functions are
+    /// evaluated in this crate for each row or grid point.
+    ///
     /// Note that this is a low-level API and it is not checked that the number
     /// of iterator iterations is consistent with the number of grid points
     /// defined in the data.
@@ -119,12 +124,7 @@ impl PolarStereographicGridDefinition {
     pub fn latlons(&self) -> Result<std::vec::IntoIter<(f32, f32)>, GribError> {
         let lad = self.lad as f64 * 1e-6;
         let lov = self.lov as f64 * 1e-6;
-        let (a, b) = self.earth_shape.radii().ok_or_else(|| {
-            GribError::NotSupported(format!(
-                "unknown value of Code Table 3.2 (shape of the Earth): {}",
-                self.earth_shape.shape_of_the_earth
-            ))
-        })?;
+        let (a, b) = self.earth_shape.resolve()?.radii();
 
         if self.projection_centre.has_unsupported_flags() {
             let ProjectionCentreFlag(flag) = self.projection_centre;
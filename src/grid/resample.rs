@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+// Resamples values at source grid points to target points with the
+// nearest-neighbor method. Target points with no source points nearby, i.e.
+// outside the domain of the source grid, get NaN.
+pub(crate) fn resample_nearest<I>(
+    src_latlons: &[(f32, f32)],
+    src_values: &[f32],
+    target_latlons: I,
+) -> Vec<f32>
+where
+    I: Iterator<Item = (f32, f32)>,
+{
+    let index = BinIndex::new(src_latlons);
+    target_latlons
+        .map(|(lat, lon)| {
+            index
+                .nearest(lat, lon)
+                .and_then(|i| src_values.get(i).copied())
+                .unwrap_or(f32::NAN)
+        })
+        .collect()
+}
+
+// Index of points binned by latitude and longitude. The size of bins is twice
+// the average spacing of the points, so that the nearest point of a target
+// within the domain is found in the bin of the target or its neighbors.
+// Longitude bins of each latitude band are widened towards the poles so that
+// their width along the parallel is still no less than the size of bins.
+struct BinIndex<'a> {
+    points: &'a [(f32, f32)],
+    bin_size: f64,
+    bins: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl<'a> BinIndex<'a> {
+    fn new(points: &'a [(f32, f32)]) -> Self {
+        let bin_size = 2. * average_spacing(points);
+        let mut index = Self {
+            points,
+            bin_size,
+            bins: HashMap::new(),
+        };
+        for (i, (lat, lon)) in points.iter().enumerate() {
+            let key = index.bin_of(f64::from(*lat), f64::from(*lon));
+            index.bins.entry(key).or_default().push(i);
+        }
+        index
+    }
+
+    // Returns the number of longitude bins of a latitude band. The width of
+    // bins is determined at the most poleward latitude of the band and its
+    // neighbors, since targets in the neighboring bands are also searched.
+    fn num_lon_bins(&self, lat_bin: i64) -> i64 {
+        let south = (lat_bin - 1) as f64 * self.bin_size - 90.;
+        let north = (lat_bin + 2) as f64 * self.bin_size - 90.;
+        let lat = south.abs().max(north.abs()).min(90.);
+        (360. * lat.to_radians().cos() / self.bin_size)
+            .floor()
+            .max(1.) as i64
+    }
+
+    fn lon_bin(&self, lat_bin: i64, lon: f64) -> i64 {
+        let num_lon_bins = self.num_lon_bins(lat_bin);
+        let lon_bin = (lon.rem_euclid(360.) / 360. * num_lon_bins as f64).floor() as i64;
+        lon_bin.min(num_lon_bins - 1)
+    }
+
+    fn bin_of(&self, lat: f64, lon: f64) -> (i64, i64) {
+        let lat_bin = ((lat + 90.) / self.bin_size).floor() as i64;
+        (lat_bin, self.lon_bin(lat_bin, lon))
+    }
+
+    fn nearest(&self, lat: f32, lon: f32) -> Option<usize> {
+        let (lat, lon) = (f64::from(lat), f64::from(lon));
+        let lat_bin = ((lat + 90.) / self.bin_size).floor() as i64;
+        let cos_lat = lat.to_radians().cos();
+        let squared_distance = |(src_lat, src_lon): (f32, f32)| {
+            let dlat = f64::from(src_lat) - lat;
+            let dlon = (f64::from(src_lon) - lon + 180.).rem_euclid(360.) - 180.;
+            dlat * dlat + (dlon * cos_lat) * (dlon * cos_lat)
+        };
+
+        let (nearest, squared_distance) = (lat_bin - 1..=lat_bin + 1)
+            .flat_map(|j| {
+                let (num_lon_bins, lon_bin) = (self.num_lon_bins(j), self.lon_bin(j, lon));
+                // Each bin of bands with fewer than 3 bins is searched just once.
+                let num_neighbors = num_lon_bins.min(3);
+                (0..num_neighbors).map(move |di| (j, (lon_bin + di - 1).rem_euclid(num_lon_bins)))
+            })
+            .filter_map(|key| self.bins.get(&key))
+            .flatten()
+            .map(|i| (*i, squared_distance(self.points[*i])))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        (squared_distance <= self.bin_size * self.bin_size).then_some(nearest)
+    }
+}
+
+// Estimates the average spacing of points in degrees from the area of their
+// bounding box on the sphere, in which the latitude span is weighted by the
+// cosine of latitudes.
+fn average_spacing(points: &[(f32, f32)]) -> f64 {
+    let (lat_min, lat_max, lon_min, lon_max) = points.iter().fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(lat_min, lat_max, lon_min, lon_max), (lat, lon)| {
+            let (lat, lon) = (f64::from(*lat), f64::from(*lon).rem_euclid(360.));
+            (
+                lat_min.min(lat),
+                lat_max.max(lat),
+                lon_min.min(lon),
+                lon_max.max(lon),
+            )
+        },
+    );
+    let num_points = points.len() as f64;
+    let lat_span = (lat_max.to_radians().sin() - lat_min.to_radians().sin()).to_degrees();
+    let lon_span = lon_max - lon_min;
+    let spacing = if lat_span > 0. && lon_span > 0. {
+        (lat_span * lon_span / num_points).sqrt()
+    } else {
+        lat_span.max(lon_span) / num_points
+    };
+    if spacing.is_finite() && spacing > 0. {
+        spacing
+    } else {
+        1.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_neighbor_resampling_across_date_line() {
+        let src_latlons = [(0., 358.), (0., 359.), (1., 358.), (1., 359.)];
+        let src_values = [1., 2., 3., 4.];
+        let target = [(0.9, -1.2), (0.1, -0.9), (0.5, 180.)];
+
+        let actual = resample_nearest(&src_latlons, &src_values, target.into_iter());
+        assert_eq!(actual[..2], [4., 2.]);
+        assert!(actual[2].is_nan());
+    }
+
+    #[test]
+    fn nearest_neighbor_resampling_around_pole() {
+        // Points spaced by 1 degree of colatitude on a plane tangent to the
+        // North Pole, as in a polar stereographic grid.
+        let src_latlons = (-10..=10)
+            .flat_map(|y| (-10..=10).map(move |x| (f64::from(x), f64::from(y))))
+            .map(|(x, y): (f64, f64)| {
+                let lat = 90. - x.hypot(y);
+                let lon = y.atan2(x).to_degrees().rem_euclid(360.);
+                (lat as f32, lon as f32)
+            })
+            .collect::<Vec<_>>();
+        let src_values = (0..src_latlons.len()).map(|i| i as f32).collect::<Vec<_>>();
+        let target = (0..8)
+            .flat_map(|j| (0..36).map(move |i| (82. + j as f32, i as f32 * 10.)))
+            .collect::<Vec<_>>();
+
+        let actual = resample_nearest(&src_latlons, &src_values, target.iter().copied());
+        let expected = target
+            .iter()
+            .map(|(lat, lon)| {
+                let cos_lat = f64::from(*lat).to_radians().cos();
+                let squared_distance = |(src_lat, src_lon): &(f32, f32)| {
+                    let dlat = f64::from(*src_lat) - f64::from(*lat);
+                    let dlon =
+                        (f64::from(*src_lon) - f64::from(*lon) + 180.).rem_euclid(360.) - 180.;
+                    dlat * dlat + (dlon * cos_lat) * (dlon * cos_lat)
+                };
+                src_latlons
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| squared_distance(a).total_cmp(&squared_distance(b)))
+                    .map(|(i, _)| i as f32)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+}
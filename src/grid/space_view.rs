@@ -0,0 +1,164 @@
+use super::{earth::EarthShapeDefinition, GridPointIndexIterator, ScanningMode};
+use crate::{
+    error::GribError,
+    helpers::{read_as, GribInt},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpaceViewGridDefinition {
+    pub earth_shape: EarthShapeDefinition,
+    pub nx: u32,
+    pub ny: u32,
+    pub sub_satellite_point_lat: i32,
+    pub sub_satellite_point_lon: i32,
+    pub resolution_and_component_flags: u8,
+    pub dx: u32,
+    pub dy: u32,
+    pub xp: u32,
+    pub yp: u32,
+    pub scanning_mode: ScanningMode,
+    pub orientation: i32,
+    pub nr: u32,
+    pub xo: u32,
+    pub yo: u32,
+}
+
+impl SpaceViewGridDefinition {
+    /// Returns the shape of the grid, i.e. a tuple of the number of grids in
+    /// the x and y directions.
+    ///
+    /// Examples
+    ///
+    /// ```
+    /// let def = grib::SpaceViewGridDefinition {
+    ///     earth_shape: grib::EarthShapeDefinition {
+    ///         shape_of_the_earth: 7,
+    ///         scale_factor_of_radius_of_spherical_earth: 0xff,
+    ///         scaled_value_of_radius_of_spherical_earth: 0xffffffff,
+    ///         scale_factor_of_earth_major_axis: 1,
+    ///         scaled_value_of_earth_major_axis: 63781370,
+    ///         scale_factor_of_earth_minor_axis: 1,
+    ///         scaled_value_of_earth_minor_axis: 63567523,
+    ///     },
+    ///     nx: 2,
+    ///     ny: 3,
+    ///     sub_satellite_point_lat: 0,
+    ///     sub_satellite_point_lon: 140700000,
+    ///     resolution_and_component_flags: 0b00110000,
+    ///     dx: 1000,
+    ///     dy: 1000,
+    ///     xp: 1000,
+    ///     yp: 1500,
+    ///     scanning_mode: grib::ScanningMode(0b00000000),
+    ///     orientation: 0,
+    ///     nr: 6610710,
+    ///     xo: 0,
+    ///     yo: 0,
+    /// };
+    /// let shape = def.grid_shape();
+    /// assert_eq!(shape, (2, 3));
+    /// ```
+    pub fn grid_shape(&self) -> (usize, usize) {
+        (self.nx as usize, self.ny as usize)
+    }
+
+    /// Returns the grid type.
+    pub fn short_name(&self) -> &'static str {
+        "space_view"
+    }
+
+    /// Returns an iterator over `(i, j)` of grid points.
+    ///
+    /// Note that this is a low-level API and it is not checked that the number
+    /// of iterator iterations is consistent with the number of grid points
+    /// defined in the data.
+    pub fn ij(&self) -> Result<GridPointIndexIterator, GribError> {
+        if self.scanning_mode.has_unsupported_flags() {
+            let ScanningMode(mode) = self.scanning_mode;
+            return Err(GribError::NotSupported(format!("scanning mode {mode}")));
+        }
+
+        let iter =
+            GridPointIndexIterator::new(self.nx as usize, self.ny as usize, self.scanning_mode);
+        Ok(iter)
+    }
+
+    pub(crate) fn from_buf(buf: &[u8]) -> Self {
+        let earth_shape = EarthShapeDefinition::from_buf(buf);
+        let nx = read_as!(u32, buf, 16);
+        let ny = read_as!(u32, buf, 20);
+        let sub_satellite_point_lat = read_as!(u32, buf, 24).as_grib_int();
+        let sub_satellite_point_lon = read_as!(u32, buf, 28).as_grib_int();
+        let resolution_and_component_flags = read_as!(u8, buf, 32);
+        let dx = read_as!(u32, buf, 33);
+        let dy = read_as!(u32, buf, 37);
+        let xp = read_as!(u32, buf, 41);
+        let yp = read_as!(u32, buf, 45);
+        let scanning_mode = read_as!(u8, buf, 49);
+        let orientation = read_as!(u32, buf, 50).as_grib_int();
+        let nr = read_as!(u32, buf, 54);
+        let xo = read_as!(u32, buf, 58);
+        let yo = read_as!(u32, buf, 62);
+        Self {
+            earth_shape,
+            nx,
+            ny,
+            sub_satellite_point_lat,
+            sub_satellite_point_lon,
+            resolution_and_component_flags,
+            dx,
+            dy,
+            xp,
+            yp,
+            scanning_mode: ScanningMode(scanning_mode),
+            orientation,
+            nr,
+            xo,
+            yo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn space_view_grid_definition_from_buf() {
+        let buf = vec![
+            0x07, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x03, 0xcd, 0x39, 0xfa, 0x01, 0x03, 0xc9,
+            0xf6, 0xa3, 0x00, 0x00, 0x0d, 0xec, 0x00, 0x00, 0x0d, 0xec, 0x00, 0x00, 0x00, 0x00,
+            0x08, 0x62, 0xe9, 0x60, 0x30, 0x00, 0x00, 0x0d, 0xa1, 0x00, 0x00, 0x0d, 0xa1, 0x00,
+            0x1b, 0x13, 0x38, 0x00, 0x1b, 0x13, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x64,
+            0xdf, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let actual = SpaceViewGridDefinition::from_buf(&buf);
+        let expected = SpaceViewGridDefinition {
+            earth_shape: EarthShapeDefinition {
+                shape_of_the_earth: 7,
+                scale_factor_of_radius_of_spherical_earth: 0xff,
+                scaled_value_of_radius_of_spherical_earth: 0xffffffff,
+                scale_factor_of_earth_major_axis: 1,
+                scaled_value_of_earth_major_axis: 63781370,
+                scale_factor_of_earth_minor_axis: 1,
+                scaled_value_of_earth_minor_axis: 63567523,
+            },
+            nx: 3564,
+            ny: 3564,
+            sub_satellite_point_lat: 0,
+            sub_satellite_point_lon: 140700000,
+            resolution_and_component_flags: 0b00110000,
+            dx: 3489,
+            dy: 3489,
+            xp: 1774392,
+            yp: 1774392,
+            scanning_mode: ScanningMode(0b00000000),
+            orientation: 0,
+            nr: 6610710,
+            xo: 0,
+            yo: 0,
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{error::GribError, helpers::read_as};
+
+/// Definition of spherical harmonic coefficients (Grid Definition Template
+/// 3.50).
+///
+/// Data defined with this template are spectral coefficients rather than grid
+/// point values. `j`, `k`, and `m` are the pentagonal resolution parameters
+/// defining the truncation; for triangular truncation, all of them are equal.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SphericalHarmonicDefinition {
+    pub j: u32,
+    pub k: u32,
+    pub m: u32,
+    /// Representation type defined in Code Table 3.6.
+    pub representation_type: u8,
+    /// Representation mode defined in Code Table 3.7.
+    pub representation_mode: u8,
+}
+
+impl SphericalHarmonicDefinition {
+    /// Returns the number of complex coefficients defined by the pentagonal
+    /// resolution parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // triangular truncation T3
+    /// let def = grib::SphericalHarmonicDefinition {
+    ///     j: 3,
+    ///     k: 3,
+    ///     m: 3,
+    ///     representation_type: 1,
+    ///     representation_mode: 1,
+    /// };
+    /// assert_eq!(def.num_coefficients(), 10);
+    /// ```
+    pub fn num_coefficients(&self) -> usize {
+        let (j, k) = (u64::from(self.j), u64::from(self.k));
+        let num = (0..=u64::from(self.m))
+            .map(|m| {
+                let n_max = (j + m).min(k);
+                if n_max >= m {
+                    n_max - m + 1
+                } else {
+                    0
+                }
+            })
+            .sum::<u64>();
+        num as usize
+    }
+
+    /// Returns the "shape" of the data, i.e. a tuple of the number of complex
+    /// coefficients and 1, since there is no grid.
+    pub fn grid_shape(&self) -> (usize, usize) {
+        (self.num_coefficients(), 1)
+    }
+
+    /// Returns the grid type.
+    pub fn short_name(&self) -> &'static str {
+        "sh"
+    }
+
+    pub(crate) fn from_buf(buf: &[u8]) -> Self {
+        let j = read_as!(u32, buf, 0);
+        let k = read_as!(u32, buf, 4);
+        let m = read_as!(u32, buf, 8);
+        let representation_type = read_as!(u8, buf, 12);
+        let representation_mode = read_as!(u8, buf, 13);
+        Self {
+            j,
+            k,
+            m,
+            representation_type,
+            representation_mode,
+        }
+    }
+
+    pub(crate) fn not_grid_point_error() -> GribError {
+        GribError::NotSupported(
+            "grid point computation for spherical harmonic coefficients, which are not a grid-point field"
+                .to_owned(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_num_coefficients {
+        ($(($name:ident, $j:expr, $k:expr, $m:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() {
+                let def = SphericalHarmonicDefinition {
+                    j: $j,
+                    k: $k,
+                    m: $m,
+                    representation_type: 1,
+                    representation_mode: 1,
+                };
+                assert_eq!(def.num_coefficients(), $expected);
+            }
+        )*);
+    }
+
+    test_num_coefficients! {
+        (num_coefficients_for_t0, 0, 0, 0, 1),
+        (num_coefficients_for_t639, 639, 639, 639, 640 * 641 / 2),
+        (num_coefficients_for_rhomboidal_truncation, 2, 4, 2, 9),
+    }
+}
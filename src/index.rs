@@ -0,0 +1,253 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{
+    context::{Grib2, SectionBody, SectionInfo},
+    datatypes::*,
+    error::*,
+    reader::{Grib2Read, SeekableGrib2Reader, SECT0_IS_SIZE},
+};
+
+/// Lightweight index of GRIB2 data, built with [`Grib2::build_index`].
+///
+/// The index holds positions of sections and summaries of submessages, and
+/// can be persisted with the `serde` feature (e.g. as JSON) to open the same
+/// data later with [`open_with_index`] without scanning it again.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grib2Index {
+    sections: Vec<IndexedSection>,
+    submessages: Vec<Grib2IndexEntry>,
+}
+
+impl Grib2Index {
+    /// Returns summaries of submessages in the data.
+    pub fn entries(&self) -> &[Grib2IndexEntry] {
+        &self.submessages
+    }
+}
+
+/// Summary of a submessage contained in [`Grib2Index`].
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grib2IndexEntry {
+    /// Index of the submessage.
+    pub index: MessageIndex,
+    /// Range of bytes of the message containing the submessage. See
+    /// [`SubMessage::byte_range`](crate::SubMessage::byte_range).
+    pub byte_range: (usize, usize),
+    pub parameter: Option<Parameter>,
+    /// First fixed surface.
+    pub level: Option<FixedSurface>,
+    pub forecast_time: Option<ForecastTime>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct IndexedSection {
+    num: u8,
+    offset: usize,
+    size: usize,
+}
+
+impl<R: Grib2Read> Grib2<R> {
+    /// Builds an index of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let index = grib2.build_index();
+    ///     assert_eq!(index.entries().len(), 7);
+    ///     assert_eq!(index.entries()[1].index, (0, 1));
+    ///     assert_eq!(
+    ///         index.entries()[1].forecast_time,
+    ///         Some(grib::ForecastTime::from_numbers(0, 10))
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn build_index(&self) -> Grib2Index {
+        let sections = self
+            .sections()
+            .map(|sect| IndexedSection {
+                num: sect.num,
+                offset: sect.offset,
+                size: sect.size,
+            })
+            .collect();
+        let submessages = self
+            .iter()
+            .map(|(index, submessage)| {
+                let prod_def = submessage.prod_def();
+                Grib2IndexEntry {
+                    index,
+                    byte_range: submessage.byte_range(),
+                    parameter: submessage.parameter(),
+                    level: prod_def.fixed_surfaces().map(|(first, _)| first),
+                    forecast_time: prod_def.forecast_time(),
+                }
+            })
+            .collect();
+        Grib2Index {
+            sections,
+            submessages,
+        }
+    }
+}
+
+/// Reads a [`Grib2`] instance from a reader using an index built with
+/// [`Grib2::build_index`] for the same data.
+///
+/// Sections are read directly at the positions recorded in the index instead
+/// of scanning the data. An error is returned if the data do not match the
+/// index.
+///
+/// # Examples
+///
+/// ```
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let path =
+///         "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin";
+///     let f = std::io::BufReader::new(std::fs::File::open(path)?);
+///     let index = grib::from_reader(f)?.build_index();
+///
+///     let f = std::io::BufReader::new(std::fs::File::open(path)?);
+///     let grib2 = grib::open_with_index(f, &index)?;
+///     assert_eq!(grib2.len(), 7);
+///     Ok(())
+/// }
+/// ```
+pub fn open_with_index<SR: Read + Seek>(
+    reader: SR,
+    index: &Grib2Index,
+) -> Result<Grib2<SeekableGrib2Reader<SR>>, GribError> {
+    let mut reader = SeekableGrib2Reader::new(reader);
+    let sects = index
+        .sections
+        .iter()
+        .map(|sect| read_indexed_section(&mut reader, sect));
+    let (sections, submessages) = Grib2::<SeekableGrib2Reader<SR>>::index_sections(sects)?;
+    Ok(Grib2::from_parts(reader, sections, submessages))
+}
+
+fn read_indexed_section<R: Grib2Read>(
+    reader: &mut R,
+    sect: &IndexedSection,
+) -> Result<SectionInfo, ParseError> {
+    let IndexedSection { num, offset, size } = *sect;
+    reader.seek(SeekFrom::Start(offset as u64))?;
+    let body = match num {
+        0 => {
+            let buf = reader.read_slice_without_offset_check(SECT0_IS_SIZE)?;
+            if !buf.starts_with(b"GRIB") {
+                return Err(ParseError::NotGRIB);
+            }
+            Some(SectionBody::Section0(Indicator::from_slice(&buf)?))
+        }
+        8 => None,
+        _ => {
+            let header = reader
+                .read_sect_header()?
+                .ok_or(ParseError::UnexpectedEndOfData(offset))?;
+            if header != (size, num) {
+                return Err(ParseError::ReadError(format!(
+                    "section at offset {offset} does not match the index"
+                )));
+            }
+            Some(reader.read_sect_payload(&header)?)
+        }
+    };
+    Ok(SectionInfo {
+        num,
+        offset,
+        size,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{BufReader, Cursor},
+    };
+
+    use super::*;
+
+    fn gdas_data() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    #[test]
+    fn opening_data_with_index() -> Result<(), Box<dyn std::error::Error>> {
+        let buf = gdas_data()?;
+        let grib2 = crate::from_slice(&buf)?;
+        let index = grib2.build_index();
+
+        let reopened = open_with_index(Cursor::new(&buf), &index)?;
+        assert_eq!(
+            reopened.sections().collect::<Vec<_>>(),
+            grib2.sections().collect::<Vec<_>>()
+        );
+        assert_eq!(reopened.build_index(), index);
+
+        let (_, mut expected) = grib2.iter().nth(3).ok_or("submessage not found")?;
+        let expected = expected.values()?.to_vec();
+        let (_, mut actual) = reopened.iter().nth(3).ok_or("submessage not found")?;
+        assert_eq!(actual.values()?, &expected[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn opening_mismatching_data_with_index() -> Result<(), Box<dyn std::error::Error>> {
+        let buf = gdas_data()?;
+        let index = crate::from_slice(&buf)?.build_index();
+
+        let mut shifted = vec![0; 1];
+        shifted.extend_from_slice(&buf);
+        let result = open_with_index(Cursor::new(&shifted), &index);
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn index_round_trip_through_json() -> Result<(), Box<dyn std::error::Error>> {
+        let buf = gdas_data()?;
+        let index = crate::from_slice(&buf)?.build_index();
+
+        let json = serde_json::to_string(&index)?;
+        let deserialized: Grib2Index = serde_json::from_str(&json)?;
+        assert_eq!(deserialized, index);
+
+        let entry = deserialized
+            .entries()
+            .iter()
+            .find(|entry| {
+                entry
+                    .parameter
+                    .as_ref()
+                    .is_some_and(|param| param.is_identical_to(crate::codetables::NCEP::PRMSL))
+            })
+            .ok_or("entry not found")?;
+        let grib2 = open_with_index(Cursor::new(&buf), &deserialized)?;
+        let (_, submessage) = grib2
+            .iter()
+            .find(|(index, _)| *index == entry.index)
+            .ok_or("submessage not found")?;
+        assert_eq!(submessage.byte_range(), entry.byte_range);
+        assert_eq!(submessage.parameter(), entry.parameter);
+        Ok(())
+    }
+}
@@ -1,28 +1,52 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod codetables;
+#[cfg(feature = "std")]
 mod context;
+#[cfg(feature = "std")]
 pub mod cookbook;
+#[cfg(feature = "std")]
 mod datatypes;
 mod decoder;
 mod error;
+#[cfg(feature = "std")]
 mod grid;
 mod helpers;
+#[cfg(feature = "std")]
+mod index;
+#[cfg(feature = "std")]
 mod parser;
+#[cfg(feature = "std")]
 mod reader;
+#[cfg(feature = "std")]
 pub mod utils;
+#[cfg(feature = "std")]
+mod verification;
+#[cfg(feature = "std")]
+mod writer;
 
 pub use crate::{
     codetables::Code::{self, Name, Num},
-    context::*,
-    datatypes::*,
     decoder::*,
     error::*,
+};
+#[cfg(feature = "std")]
+pub use crate::{
+    context::*,
+    datatypes::*,
     grid::{
-        EarthShapeDefinition, GaussianGridDefinition, GridPointIndexIterator, GridPointIterator,
-        LambertGridDefinition, LatLonGridDefinition, PolarStereographicGridDefinition,
-        ProjectionCentreFlag, ScanningMode,
+        EarthShape, EarthShapeDefinition, GaussianGridDefinition, GridPointIndexIterator,
+        GridPointIterator, LambertAzimuthalEqualAreaGridDefinition, LambertGridDefinition,
+        LatLonGridDefinition, PolarStereographicGridDefinition, ProjectionCentreFlag, ScanningMode,
+        SpaceViewGridDefinition, SphericalHarmonicDefinition,
     },
+    index::*,
     parser::*,
     reader::*,
+    verification::*,
+    writer::*,
 };
 
 #[doc = include_str!("../README.md")]
@@ -2,13 +2,47 @@ use std::io::{self, Read, Seek, SeekFrom};
 
 use crate::{datatypes::*, error::*, helpers::read_as, SectionBody, SectionInfo};
 
-const SECT0_IS_MAGIC: &[u8] = b"GRIB";
+pub(crate) const SECT0_IS_MAGIC: &[u8] = b"GRIB";
 const SECT0_IS_MAGIC_SIZE: usize = SECT0_IS_MAGIC.len();
-const SECT0_IS_SIZE: usize = 16;
-const SECT_HEADER_SIZE: usize = 5;
-const SECT8_ES_MAGIC: &[u8] = b"7777";
+pub(crate) const SECT0_IS_SIZE: usize = 16;
+pub(crate) const SECT_HEADER_SIZE: usize = 5;
+pub(crate) const SECT8_ES_MAGIC: &[u8] = b"7777";
 pub(crate) const SECT8_ES_SIZE: usize = SECT8_ES_MAGIC.len();
 
+/// Options for reading GRIB2 data.
+///
+/// By default, reading is lenient: the End Section (Section 8) of each
+/// message is accepted even if it does not consist of "7777", since the end
+/// of the message is already determined by the total length in Section 0.
+/// In strict mode, such a message is rejected with
+/// [`ParseError::EndSectionMismatch`].
+///
+/// # Examples
+///
+/// ```
+/// let options = grib::ReadOptions::default();
+/// assert!(!options.is_strict());
+///
+/// let options = options.strict(true);
+/// assert!(options.is_strict());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    strict: bool,
+}
+
+impl ReadOptions {
+    /// Enables or disables strict mode.
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict }
+    }
+
+    /// Returns `true` if strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
+
 /// # Example
 /// ```
 /// use grib::{Grib2SectionStream, Indicator, SectionBody, SectionInfo, SeekableGrib2Reader};
@@ -40,6 +74,9 @@ pub struct Grib2SectionStream<R> {
     reader: R,
     whole_size: usize,
     rest_size: usize,
+    last_sect_num: u8,
+    finished: bool,
+    options: ReadOptions,
 }
 
 impl<R> Grib2SectionStream<R> {
@@ -62,6 +99,17 @@ impl<R> Grib2SectionStream<R> {
             reader,
             whole_size: 0,
             rest_size: 0,
+            last_sect_num: 0,
+            finished: false,
+            options: ReadOptions::default(),
+        }
+    }
+
+    /// Sets options for reading. See [`ReadOptions`] for details.
+    pub fn with_options(self, options: &ReadOptions) -> Self {
+        Self {
+            options: options.clone(),
+            ..self
         }
     }
 
@@ -102,6 +150,7 @@ where
                     body: Some(SectionBody::Section0(indicator)),
                 };
                 self.rest_size = message_size - SECT0_IS_SIZE;
+                self.last_sect_num = 0;
                 sect_info
             });
         Some(result)
@@ -115,42 +164,72 @@ where
 
     #[inline]
     fn next_sect8(&mut self) -> Option<Result<SectionInfo, ParseError>> {
-        let result = self.reader.read_sect8().transpose()?.map(|_| {
-            let sect_info = SectionInfo {
-                num: 8,
-                offset: self.whole_size - self.rest_size,
-                size: SECT8_ES_SIZE,
-                body: None,
-            };
-            self.rest_size -= SECT8_ES_SIZE;
-            sect_info
-        });
+        let offset = self.whole_size - self.rest_size;
+        let result = match self.reader.read_sect8() {
+            Ok(Some(())) => Ok(()),
+            Ok(None) => Err(unexpected_end_of_data()),
+            Err(ParseError::EndSectionMismatch) if !self.options.is_strict() => Ok(()),
+            Err(e) => Err(e),
+        };
+        let result = result
+            .map(|_| {
+                self.rest_size -= SECT8_ES_SIZE;
+                SectionInfo {
+                    num: 8,
+                    offset,
+                    size: SECT8_ES_SIZE,
+                    body: None,
+                }
+            })
+            .map_err(|e| locate_read_error(e, format!("Section 8 at offset {offset}")));
         Some(result)
     }
 
     #[inline]
     fn next_sect(&mut self) -> Option<Result<SectionInfo, ParseError>> {
-        let result = self.reader.read_sect_header().transpose()?;
-        match result {
-            Ok(header) => {
-                let offset = self.whole_size - self.rest_size;
-                match self.reader.read_sect_payload(&header) {
-                    Ok(body) => {
-                        let body = Some(body);
-                        let (size, num) = header;
-                        self.rest_size -= size;
-                        Some(Ok(SectionInfo {
-                            num,
-                            offset,
-                            size,
-                            body,
-                        }))
-                    }
-                    Err(e) => Some(Err(e)),
-                }
+        let offset = self.whole_size - self.rest_size;
+        let header = match self.reader.read_sect_header() {
+            Ok(Some(header)) => header,
+            result => {
+                let e = result.err().unwrap_or_else(unexpected_end_of_data);
+                let location = format!(
+                    "section following Section {} at offset {offset}",
+                    self.last_sect_num
+                );
+                return Some(Err(locate_read_error(e, location)));
             }
-            Err(e) => Some(Err(e)),
-        }
+        };
+        let (size, num) = header;
+        let result = match self.reader.read_sect_payload(&header) {
+            Ok(body) => {
+                self.rest_size -= size;
+                self.last_sect_num = num;
+                Ok(SectionInfo {
+                    num,
+                    offset,
+                    size,
+                    body: Some(body),
+                })
+            }
+            Err(e) => Err(locate_read_error(
+                e,
+                format!("Section {num} at offset {offset}"),
+            )),
+        };
+        Some(result)
+    }
+}
+
+fn unexpected_end_of_data() -> ParseError {
+    ParseError::ReadError("unexpected end of data".to_owned())
+}
+
+// Prepends the location of the section being read to the message of a read
+// error, so that errors for truncated data tell where reading failed.
+fn locate_read_error(e: ParseError, location: String) -> ParseError {
+    match e {
+        ParseError::ReadError(s) => ParseError::ReadError(format!("{location}: {s}")),
+        e => e,
     }
 }
 
@@ -161,11 +240,20 @@ where
     type Item = Result<SectionInfo, ParseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.rest_size {
+        if self.finished {
+            return None;
+        }
+        let result = match self.rest_size {
             0 => self.next_sect0(),
             SECT8_ES_SIZE => self.next_sect8(),
             _ => self.next_sect(),
+        };
+        // Sections in the middle of a message cannot be located after a read
+        // error, so the iteration ends there.
+        if self.rest_size != 0 && matches!(result, Some(Err(_))) {
+            self.finished = true;
         }
+        result
     }
 }
 
@@ -331,7 +419,18 @@ impl<R: Read + Seek> Grib2Read for SeekableGrib2Reader<R> {
     }
 
     fn skip_sect7_payload(&mut self, body_size: usize) -> Result<SectionBody, ParseError> {
-        self.seek(SeekFrom::Current(body_size as i64))?;
+        // Seeking past the end of the stream succeeds, so the end is checked
+        // here to report truncation in Section 7 rather than in the following
+        // section.
+        let body_offset = self.stream_position()?;
+        let end_offset = self.seek(SeekFrom::End(0))?;
+        let body_end_offset = body_offset + body_size as u64;
+        if body_end_offset > end_offset {
+            return Err(ParseError::ReadError(format!(
+                "unexpected end of data at offset {end_offset}"
+            )));
+        }
+        self.seek(SeekFrom::Start(body_end_offset))?;
 
         Ok(SectionBody::Section7)
     }
@@ -424,6 +523,42 @@ mod tests {
         Ok(())
     }
 
+    macro_rules! test_reading_corrupted_end_section {
+        ($(($name:ident, $strict:expr, $expected_ninth:expr),)*) => ($(
+            #[test]
+            fn $name() -> Result<(), Box<dyn std::error::Error>> {
+                let mut buf = std::fs::read(
+                    "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+                )?.repeat(2);
+                buf[189..193].copy_from_slice(b"7778");
+
+                let grib2_reader = SeekableGrib2Reader::new(Cursor::new(buf));
+                let options = ReadOptions::default().strict($strict);
+                let sect_stream = Grib2SectionStream::new(grib2_reader).with_options(&options);
+                let actual = sect_stream
+                    .map(|result| result.map(|sect| (sect.num, sect.offset, sect.size)))
+                    .collect::<Vec<_>>();
+                assert_eq!(actual.get(8), Some(&$expected_ninth));
+                let expected_len = if $strict { 9 } else { 18 };
+                assert_eq!(actual.len(), expected_len);
+                Ok(())
+            }
+        )*);
+    }
+
+    test_reading_corrupted_end_section! {
+        (
+            reading_corrupted_end_section_in_lenient_mode,
+            false,
+            Ok((8, 189, 4))
+        ),
+        (
+            reading_corrupted_end_section_in_strict_mode,
+            true,
+            Err(ParseError::EndSectionMismatch)
+        ),
+    }
+
     #[test]
     fn read_grib2_message_with_incomplete_section_0() -> Result<(), Box<dyn std::error::Error>> {
         let f = std::fs::File::open(
@@ -492,7 +627,8 @@ mod tests {
                 Ok((8, 189, 4)),
                 Ok((0, 193, 16)),
                 Err(ParseError::ReadError(
-                    "failed to fill whole buffer".to_owned()
+                    "section following Section 0 at offset 209: failed to fill whole buffer"
+                        .to_owned()
                 ))
             ]
         );
@@ -539,7 +675,7 @@ mod tests {
                 Ok((6, 371, 6)),
                 Ok((7, 377, 5)),
                 Err(ParseError::ReadError(
-                    "failed to fill whole buffer".to_owned()
+                    "Section 8 at offset 382: failed to fill whole buffer".to_owned()
                 ))
             ]
         );
@@ -547,6 +683,47 @@ mod tests {
         Ok(())
     }
 
+    macro_rules! test_reading_truncated_data {
+        ($(($name:ident, $len:expr, $expected:expr),)*) => ($(
+            #[test]
+            fn $name() -> Result<(), Box<dyn std::error::Error>> {
+                let buf = std::fs::read(
+                    "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+                )?;
+                let f = Cursor::new(&buf[..$len]);
+
+                let grib2_reader = SeekableGrib2Reader::new(f);
+                let sect_stream = Grib2SectionStream::new(grib2_reader);
+                let last = sect_stream.last();
+                assert_eq!(last, Some(Err(ParseError::ReadError($expected.to_owned()))));
+                Ok(())
+            }
+        )*);
+    }
+
+    test_reading_truncated_data! {
+        (
+            reading_data_truncated_after_section_5,
+            0xa6,
+            "section following Section 5 at offset 166: unexpected end of data"
+        ),
+        (
+            reading_data_truncated_in_header_of_section_6,
+            0xa8,
+            "section following Section 5 at offset 166: failed to fill whole buffer"
+        ),
+        (
+            reading_data_truncated_in_section_6,
+            0xab,
+            "Section 6 at offset 166: failed to fill whole buffer"
+        ),
+        (
+            reading_data_truncated_in_section_7,
+            0x200,
+            "Section 7 at offset 172: unexpected end of data at offset 512"
+        ),
+    }
+
     fn create_grib2_message_starting_from_non_zero_position(
         header: &[u8],
     ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
@@ -0,0 +1,300 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    context::{Grib2, SectionBody, SubMessage},
+    datatypes::*,
+    reader::Grib2Read,
+};
+
+/// A problem found in the structure of GRIB2 data by [`Grib2::verify`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VerificationIssue {
+    /// The number of data points in Section 5 is not consistent with the
+    /// number of grid points in Section 3 and the bit map in Section 6.
+    ///
+    /// Holds the index of the submessage, the number of data points expected
+    /// from Sections 3 and 6, and the number of data points in Section 5.
+    NumPointsMismatch(MessageIndex, usize, usize),
+    /// The bit map in Section 6 does not cover all grid points in Section 3.
+    ///
+    /// Holds the index of the submessage, the number of grid points in Section
+    /// 3, and the number of bits in the bit map.
+    BitMapTooShort(MessageIndex, usize, usize),
+    /// The size declared in the section header is smaller than the size
+    /// required by the template.
+    ///
+    /// Holds the index of the submessage, the section number, the required
+    /// size, and the declared size.
+    SectionSizeTooSmall(MessageIndex, u8, usize, usize),
+    /// The template number is unknown or not supported by this library.
+    ///
+    /// Holds the index of the submessage, the section number, and the
+    /// template number.
+    UnsupportedTemplate(MessageIndex, u8, u16),
+    /// Reading data needed for verification failed.
+    ///
+    /// Holds the index of the submessage and the error message.
+    ReadError(MessageIndex, String),
+}
+
+impl Display for VerificationIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NumPointsMismatch((m, s), expected, actual) => write!(
+                f,
+                "{m}.{s}: number of data points in Section 5 does not match: {expected} (expected) vs {actual} (Section 5)"
+            ),
+            Self::BitMapTooShort((m, s), expected, actual) => write!(
+                f,
+                "{m}.{s}: bit map is too short: {expected} (grid points) vs {actual} (bits)"
+            ),
+            Self::SectionSizeTooSmall((m, s), num, expected, actual) => write!(
+                f,
+                "{m}.{s}: Section {num} is too small: {expected} (required) vs {actual} (declared)"
+            ),
+            Self::UnsupportedTemplate((m, s), num, template) => {
+                write!(f, "{m}.{s}: template {num}.{template} is not supported")
+            }
+            Self::ReadError((m, s), e) => write!(f, "{m}.{s}: read error: {e}"),
+        }
+    }
+}
+
+impl<R: Grib2Read> Grib2<R> {
+    /// Checks the structure of all submessages and returns all problems found.
+    ///
+    /// Unlike parsing, which stops at the first error, this method continues
+    /// the checks and collects the following issues:
+    ///
+    /// * inconsistencies in the number of points between Sections 3, 5, and 6
+    /// * section sizes smaller than those required by the templates
+    /// * template numbers unknown to or not supported by this library
+    ///
+    /// Sections shared by multiple submessages are checked only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = std::fs::File::open(
+    ///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+    ///     )?;
+    ///     let f = std::io::BufReader::new(f);
+    ///     let grib2 = grib::from_reader(f)?;
+    ///
+    ///     let issues = grib2.verify();
+    ///     assert_eq!(
+    ///         issues,
+    ///         vec![grib::VerificationIssue::UnsupportedTemplate((0, 0), 3, 101)]
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn verify(&self) -> Vec<VerificationIssue> {
+        let mut issues = Vec::new();
+        let mut checked = HashSet::new();
+        for (index, submessage) in self.iter() {
+            verify_section_sizes_and_templates(index, &submessage, &mut checked, &mut issues);
+            verify_num_points(index, submessage, &mut issues);
+        }
+        issues
+    }
+}
+
+fn verify_section_sizes_and_templates<R>(
+    index: MessageIndex,
+    submessage: &SubMessage<R>,
+    checked: &mut HashSet<usize>,
+    issues: &mut Vec<VerificationIssue>,
+) {
+    for sect in [&submessage.3, &submessage.4, &submessage.5] {
+        if !checked.insert(sect.index) {
+            continue;
+        }
+
+        let info = sect.body;
+        let (template, required_size) = match info.body.as_ref() {
+            Some(SectionBody::Section3(body)) => {
+                let template = body.grid_tmpl_num();
+                (template, required_sect3_size(template))
+            }
+            Some(SectionBody::Section4(body)) => {
+                let template = body.prod_tmpl_num();
+                let required_size = body.template_supported().then_some(None);
+                (template, required_size)
+            }
+            Some(SectionBody::Section5(body)) => {
+                let template = body.repr_tmpl_num();
+                (template, required_sect5_size(template))
+            }
+            _ => continue,
+        };
+
+        match required_size {
+            None => issues.push(VerificationIssue::UnsupportedTemplate(
+                index, info.num, template,
+            )),
+            Some(Some(required_size)) if info.size < required_size => issues.push(
+                VerificationIssue::SectionSizeTooSmall(index, info.num, required_size, info.size),
+            ),
+            _ => {}
+        }
+    }
+}
+
+// Returns `None` for unsupported templates and `Some(None)` for supported
+// templates without a fixed size.
+fn required_sect3_size(template: u16) -> Option<Option<usize>> {
+    let size = match template {
+        0 => 72,
+        20 => 65,
+        30 => 81,
+        40 => 72,
+        50 => 28,
+        90 => 80,
+        140 => 64,
+        _ => return None,
+    };
+    Some(Some(size))
+}
+
+fn required_sect5_size(template: u16) -> Option<Option<usize>> {
+    let size = match template {
+        0 => 21,
+        2 => 47,
+        3 => 49,
+        4 => 12,
+        #[cfg(jpeg2000)]
+        40 => 23,
+        41 => 21,
+        50 => 24,
+        200 => return Some(None),
+        _ => return None,
+    };
+    Some(Some(size))
+}
+
+fn verify_num_points<R: Grib2Read>(
+    index: MessageIndex,
+    submessage: SubMessage<R>,
+    issues: &mut Vec<VerificationIssue>,
+) {
+    let mut reader = submessage.9;
+    let (sect3_body, sect5_body, sect6_body) = match (
+        submessage.3.body.body.as_ref(),
+        submessage.5.body.body.as_ref(),
+        submessage.6.body.body.as_ref(),
+    ) {
+        (
+            Some(SectionBody::Section3(b3)),
+            Some(SectionBody::Section5(b5)),
+            Some(SectionBody::Section6(b6)),
+        ) => (b3, b5, b6),
+        _ => return,
+    };
+    let num_points_total = sect3_body.num_points() as usize;
+    let num_points_encoded = sect5_body.num_points() as usize;
+
+    let bitmap_sect = match sect6_body.bitmap_indicator {
+        0x00 => Some(submessage.6.body),
+        0xfe => submessage.11.as_ref().map(|sect| sect.body),
+        _ => None,
+    };
+    let expected = match (sect6_body.bitmap_indicator, bitmap_sect) {
+        (_, Some(bitmap_sect)) => {
+            let payload = match reader.read_sect_payload_as_slice(bitmap_sect) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    issues.push(VerificationIssue::ReadError(index, e.to_string()));
+                    return;
+                }
+            };
+            let bitmap = &payload[1..];
+            let num_bits = bitmap.len() * 8;
+            if num_bits < num_points_total {
+                issues.push(VerificationIssue::BitMapTooShort(
+                    index,
+                    num_points_total,
+                    num_bits,
+                ));
+                return;
+            }
+            (0..num_points_total)
+                .filter(|i| bitmap[i / 8] & (0b10000000 >> (i % 8)) != 0)
+                .count()
+        }
+        (0xff, _) => num_points_total,
+        _ => return,
+    };
+
+    if expected != num_points_encoded {
+        issues.push(VerificationIssue::NumPointsMismatch(
+            index,
+            expected,
+            num_points_encoded,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Read};
+
+    use super::*;
+
+    #[test]
+    fn verification_of_valid_data() -> Result<(), Box<dyn std::error::Error>> {
+        let f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let grib2 = crate::from_reader(f)?;
+
+        assert_eq!(grib2.verify(), vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn verification_of_corrupted_data() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let mut f = File::open(
+            "testdata/Z__C_RJTD_20160822020000_NOWC_GPV_Ggis10km_Pphw10_FH0000-0100_grib2.bin",
+        )?;
+        f.read_to_end(&mut buf)?;
+
+        // number of data points in Section 5 of submessage 0.0: 86016 -> 86015
+        buf[0x96] = 0x4f;
+        buf[0x97] = 0xff;
+        // template number in Section 4 of submessage 0.1: 0 -> 65534
+        buf[0x622] = 0xff;
+        buf[0x623] = 0xfe;
+
+        let grib2 = crate::from_slice(&buf)?;
+        assert_eq!(
+            grib2.verify(),
+            vec![
+                VerificationIssue::NumPointsMismatch((0, 0), 86016, 86015),
+                VerificationIssue::UnsupportedTemplate((0, 1), 4, 65534),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn verification_of_bitmap() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = File::open(
+            "testdata/Z__C_RJTD_20190304000000_MSM_GUID_Rjp_P-all_FH03-39_Toorg_grib2.bin.xz",
+        )?;
+        let f = std::io::BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+
+        let grib2 = crate::from_slice(&buf)?;
+        assert_eq!(grib2.verify(), vec![]);
+        Ok(())
+    }
+}
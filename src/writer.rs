@@ -0,0 +1,392 @@
+use std::io::Write;
+
+use crate::{
+    error::*,
+    helpers::read_as,
+    reader::{SECT0_IS_MAGIC, SECT0_IS_SIZE, SECT8_ES_MAGIC, SECT8_ES_SIZE, SECT_HEADER_SIZE},
+};
+
+/// A writer that concatenates already-encoded GRIB2 messages into a
+/// multi-message GRIB2 file.
+///
+/// The total length field in Section 0 of each message is set to the actual
+/// length of the message when it is written.
+///
+/// # Examples
+///
+/// ```
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let message = std::fs::read(
+///         "testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2",
+///     )?;
+///
+///     let mut writer = grib::Grib2Writer::new(Vec::new());
+///     writer.write_message(&message)?;
+///     writer.write_message(&message)?;
+///     assert_eq!(writer.num_messages(), 2);
+///
+///     let buf = writer.into_inner();
+///     let grib2 = grib::from_slice(&buf)?;
+///     assert_eq!(grib2.len(), 2);
+///     Ok(())
+/// }
+/// ```
+pub struct Grib2Writer<W> {
+    writer: W,
+    num_messages: usize,
+}
+
+impl<W: Write> Grib2Writer<W> {
+    /// Creates a new writer writing messages to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_messages: 0,
+        }
+    }
+
+    /// Writes a single encoded GRIB2 message, from the beginning of Section 0
+    /// to the end of Section 8.
+    ///
+    /// All sections including Section 2 (Local Use Section) are written as
+    /// they are.
+    ///
+    /// Returns an error if `message` does not start with Section 0 of GRIB
+    /// edition 2 or does not end with Section 8.
+    pub fn write_message(&mut self, message: &[u8]) -> Result<(), GribError> {
+        check_message(message)?;
+
+        let total_length = (message.len() as u64).to_be_bytes();
+        self.write_all(&message[..8])?;
+        self.write_all(&total_length)?;
+        self.write_all(&message[SECT0_IS_SIZE..])?;
+        self.num_messages += 1;
+        Ok(())
+    }
+
+    /// Writes a single encoded GRIB2 message with Section 2 (Local Use
+    /// Section) following Section 1 replaced.
+    ///
+    /// If `local_use` is `Some`, Section 2 containing `local_use` as its
+    /// payload is written immediately after Section 1, regardless of whether
+    /// `message` has Section 2 there. If `local_use` is `None`, Section 2
+    /// following Section 1 is omitted. The length of the section and the total
+    /// length of the message are set accordingly. Section 2 appearing later in
+    /// the message, i.e. one for a subsequent submessage, is written as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let message = std::fs::read(
+    ///         "testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2",
+    ///     )?;
+    ///
+    ///     let mut writer = grib::Grib2Writer::new(Vec::new());
+    ///     writer.write_message_with_local_use(&message, Some(b"local"))?;
+    ///     let buf = writer.into_inner();
+    ///     assert_eq!(buf.len(), message.len() + 5 + 5);
+    ///
+    ///     let grib2 = grib::from_slice(&buf)?;
+    ///     let (_, submessage) = grib2.iter().next().unwrap();
+    ///     let local_use = submessage.2.ok_or("Section 2 not found")?;
+    ///     assert_eq!(local_use.body.size, 10);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn write_message_with_local_use(
+        &mut self,
+        message: &[u8],
+        local_use: Option<&[u8]>,
+    ) -> Result<(), GribError> {
+        check_message(message)?;
+
+        let sect1_end = next_section_end(message, SECT0_IS_SIZE, 1)?;
+        let sect2_end = match message.get(sect1_end + 4) {
+            Some(2) => next_section_end(message, sect1_end, 2)?,
+            _ => sect1_end,
+        };
+        let sect2 = local_use
+            .map(|payload| {
+                let size = u32::try_from(SECT_HEADER_SIZE + payload.len()).map_err(|_| {
+                    GribError::InvalidValueError("local use data too large".to_owned())
+                })?;
+                let mut sect2 = Vec::with_capacity(size as usize);
+                sect2.extend_from_slice(&size.to_be_bytes());
+                sect2.push(2);
+                sect2.extend_from_slice(payload);
+                Ok::<_, GribError>(sect2)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let total_length = sect1_end + sect2.len() + (message.len() - sect2_end);
+        let total_length = (total_length as u64).to_be_bytes();
+        self.write_all(&message[..8])?;
+        self.write_all(&total_length)?;
+        self.write_all(&message[SECT0_IS_SIZE..sect1_end])?;
+        self.write_all(&sect2)?;
+        self.write_all(&message[sect2_end..])?;
+        self.num_messages += 1;
+        Ok(())
+    }
+
+    /// Writes all encoded GRIB2 messages in `messages`.
+    pub fn write_messages<I, M>(&mut self, messages: I) -> Result<(), GribError>
+    where
+        I: IntoIterator<Item = M>,
+        M: AsRef<[u8]>,
+    {
+        for message in messages {
+            self.write_message(message.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), GribError> {
+        self.writer.flush().map_err(ParseError::from)?;
+        Ok(())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), GribError> {
+        self.writer.write_all(buf).map_err(ParseError::from)?;
+        Ok(())
+    }
+}
+
+fn check_message(message: &[u8]) -> Result<(), GribError> {
+    if message.len() < SECT0_IS_SIZE + SECT8_ES_SIZE || !message.starts_with(SECT0_IS_MAGIC) {
+        return Err(ParseError::NotGRIB.into());
+    }
+    let version = message[7];
+    if version != 2 {
+        return Err(ParseError::GRIBVersionMismatch(version).into());
+    }
+    if !message.ends_with(SECT8_ES_MAGIC) {
+        return Err(ParseError::EndSectionMismatch.into());
+    }
+    Ok(())
+}
+
+// Returns the end position of the section with the number `num` starting at
+// `start`.
+fn next_section_end(message: &[u8], start: usize, num: u8) -> Result<usize, GribError> {
+    let header = message
+        .get(start..start + SECT_HEADER_SIZE)
+        .ok_or(ParseError::UnexpectedEndOfData(start))?;
+    if header[4] != num {
+        return Err(ParseError::InvalidSectionOrder(start).into());
+    }
+    let size = read_as!(u32, header, 0) as usize;
+    let end = start + size;
+    if size < SECT_HEADER_SIZE || end > message.len() - SECT8_ES_SIZE {
+        return Err(ParseError::UnexpectedEndOfData(start).into());
+    }
+    Ok(end)
+}
+
+impl<W> Grib2Writer<W> {
+    /// Returns the number of messages written so far.
+    pub fn num_messages(&self) -> usize {
+        self.num_messages
+    }
+
+    /// Unwraps this `Grib2Writer`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor, Read};
+
+    use super::*;
+
+    fn simple_packing_message() -> Result<Vec<u8>, std::io::Error> {
+        std::fs::read("testdata/icon_global_icosahedral_single-level_2021112018_000_TOT_PREC.grib2")
+    }
+
+    #[test]
+    fn concatenation_of_simple_packing_messages() -> Result<(), Box<dyn std::error::Error>> {
+        let first = simple_packing_message()?;
+        let mut second = simple_packing_message()?;
+        // breaks the total length, which should be corrected by the writer
+        second[8..16].copy_from_slice(&[0; 8]);
+
+        let mut writer = Grib2Writer::new(Vec::new());
+        writer.write_messages([&first, &second])?;
+        assert_eq!(writer.num_messages(), 2);
+        let buf = writer.into_inner();
+        assert_eq!(buf.len(), first.len() * 2);
+
+        let grib2 = crate::from_reader(Cursor::new(&buf))?;
+        assert_eq!(grib2.len(), 2);
+        let lengths = grib2
+            .iter()
+            .map(|(_, submessage)| submessage.indicator().total_length)
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![first.len() as u64; 2]);
+
+        let values = grib2
+            .iter()
+            .map(|(_, submessage)| {
+                let decoder = crate::Grib2SubmessageDecoder::from(submessage)?;
+                decoder.dispatch().map(|values| values.collect::<Vec<_>>())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(values[0].len(), values[1].len());
+        assert!(values[0]
+            .iter()
+            .zip(values[1].iter())
+            .all(|(a, b)| a == b || (a.is_nan() && b.is_nan())));
+        Ok(())
+    }
+
+    #[test]
+    fn concatenation_of_extracted_messages() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+        let f = std::fs::File::open("testdata/gdas.t12z.pgrb2.0p25.f000.0-10.xz")?;
+        let f = BufReader::new(f);
+        let mut f = xz2::bufread::XzDecoder::new(f);
+        f.read_to_end(&mut buf)?;
+        let source = crate::from_slice(&buf)?;
+
+        let mut writer = Grib2Writer::new(Vec::new());
+        writer.write_message(&source.extract_message(2)?)?;
+        writer.write_message(&source.extract_message(0)?)?;
+        let buf = writer.into_inner();
+
+        let grib2 = crate::from_slice(&buf)?;
+        assert_eq!(grib2.len(), 2);
+        Ok(())
+    }
+
+    fn local_use_payload<R>(submessage: &crate::SubMessage<R>) -> Option<Vec<u8>> {
+        match submessage.2.as_ref()?.body.body.as_ref()? {
+            crate::SectionBody::Section2(local_use) => Some(local_use.payload().to_vec()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn round_trip_of_message_with_local_use() -> Result<(), Box<dyn std::error::Error>> {
+        let message = simple_packing_message()?;
+        let local_use = {
+            let grib2 = crate::from_slice(&message)?;
+            let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+            local_use_payload(&submessage).ok_or("Section 2 is not found")?
+        };
+
+        let mut writer = Grib2Writer::new(Vec::new());
+        writer.write_message(&message)?;
+        writer.write_message_with_local_use(&message, Some(&local_use))?;
+        let buf = writer.into_inner();
+        assert_eq!(buf, [message.clone(), message].concat());
+
+        let grib2 = crate::from_slice(&buf)?;
+        let actual = grib2
+            .iter()
+            .map(|(_, submessage)| local_use_payload(&submessage))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![Some(local_use.clone()), Some(local_use)]);
+        Ok(())
+    }
+
+    macro_rules! test_replacement_of_local_use {
+        ($(($name:ident, $message:expr, $local_use:expr, $expected_size_diff:expr),)*) => ($(
+            #[test]
+            fn $name() -> Result<(), Box<dyn std::error::Error>> {
+                let message = $message;
+                let local_use: Option<&[u8]> = $local_use;
+
+                let mut writer = Grib2Writer::new(Vec::new());
+                writer.write_message_with_local_use(&message, local_use)?;
+                let buf = writer.into_inner();
+                assert_eq!(buf.len() as isize, message.len() as isize + $expected_size_diff);
+
+                let grib2 = crate::from_slice(&buf)?;
+                assert_eq!(grib2.len(), 1);
+                let (_, submessage) = grib2.iter().next().ok_or("first submessage is not found")?;
+                assert_eq!(submessage.indicator().total_length, buf.len() as u64);
+                assert_eq!(local_use_payload(&submessage), local_use.map(|s| s.to_vec()));
+                let decoder = crate::Grib2SubmessageDecoder::from(submessage)?;
+                assert_eq!(decoder.dispatch()?.count(), 2949120);
+                Ok(())
+            }
+        )*);
+    }
+
+    test_replacement_of_local_use! {
+        (
+            replacing_local_use,
+            simple_packing_message()?,
+            Some(b"local use data"),
+            14 - 22
+        ),
+        (
+            removing_local_use,
+            simple_packing_message()?,
+            None,
+            -27
+        ),
+        (
+            replacing_local_use_with_empty_data,
+            simple_packing_message()?,
+            Some(b""),
+            -22
+        ),
+    }
+
+    #[test]
+    fn adding_local_use_to_message_without_section_2() -> Result<(), Box<dyn std::error::Error>> {
+        let message =
+            std::fs::read("testdata/CMC_glb_TMP_ISBL_1_latlon.24x.24_2021051800_P000.grib2")?;
+        let mut writer = Grib2Writer::new(Vec::new());
+        writer.write_message_with_local_use(&message, Some(&[0x01, 0x02, 0x03]))?;
+        writer.write_message_with_local_use(&message, None)?;
+        let buf = writer.into_inner();
+
+        let grib2 = crate::from_slice(&buf)?;
+        let actual = grib2
+            .iter()
+            .map(|(_, submessage)| local_use_payload(&submessage))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, vec![Some(vec![0x01, 0x02, 0x03]), None]);
+        assert_eq!(&buf[message.len() + 8..], &message[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn writing_broken_messages() -> Result<(), Box<dyn std::error::Error>> {
+        let message = simple_packing_message()?;
+        let mut writer = Grib2Writer::new(Vec::new());
+
+        let mut edition1 = message.clone();
+        edition1[7] = 1;
+        assert_eq!(
+            writer.write_message(&edition1),
+            Err(GribError::ParseError(ParseError::GRIBVersionMismatch(1)))
+        );
+        assert_eq!(
+            writer.write_message(&message[..message.len() - 1]),
+            Err(GribError::ParseError(ParseError::EndSectionMismatch))
+        );
+        assert_eq!(
+            writer.write_message(b"foo"),
+            Err(GribError::ParseError(ParseError::NotGRIB))
+        );
+        let mut truncated = message[..SECT0_IS_SIZE + 20].to_vec();
+        truncated.extend_from_slice(SECT8_ES_MAGIC);
+        assert_eq!(
+            writer.write_message_with_local_use(&truncated, None),
+            Err(GribError::ParseError(ParseError::UnexpectedEndOfData(
+                SECT0_IS_SIZE
+            )))
+        );
+        assert_eq!(writer.num_messages(), 0);
+        assert!(writer.into_inner().is_empty());
+        Ok(())
+    }
+}